@@ -0,0 +1,217 @@
+use std::cell::RefCell;
+
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{
+    Error, Exec, read_argument, read_envelope, read_envelope_string,
+    report_error,
+};
+
+/// Options for applying a command to a stream of envelopes.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct BatchArgs {
+    /// Read newline-delimited envelopes from stdin, apply the command to each
+    /// one, and write one result per line.
+    ///
+    /// Envelopes are read from stdin only; giving the command's own envelope
+    /// argument, or `--in`, is an error, as is a command that doesn't read
+    /// its input from stdin. Blank lines are skipped.
+    #[arg(long, visible_alias = "batch", global = true)]
+    each: bool,
+
+    /// With `--each`, report failed lines and continue with the rest of the
+    /// stream instead of stopping at the first failure.
+    #[arg(long, global = true)]
+    keep_going: bool,
+}
+
+impl BatchArgs {
+    pub fn is_each(&self) -> bool { self.each }
+
+    /// Runs `command` once for each line read from stdin, passing each result
+    /// to `write`.
+    ///
+    /// Errors are prefixed with the 1-based line number they occurred on. When
+    /// `--keep-going` is set, errors are reported on stderr and processing
+    /// continues; the run still fails at the end if any line failed.
+    pub fn run_each(
+        &self,
+        command: &impl Exec,
+        mut write: impl FnMut(&str) -> Result<()>,
+    ) -> Result<()> {
+        let mut line_number = 0;
+        let mut processed = 0;
+        let mut failures = 0;
+        let mut line = String::new();
+        loop {
+            // Don't hold the stdin lock across `exec`, since commands may read
+            // other arguments from stdin.
+            line.clear();
+            if std::io::stdin().read_line(&mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+            let input = line.trim();
+            if input.is_empty() {
+                continue;
+            }
+            processed += 1;
+            let (result, read) = with_input(input, true, || command.exec());
+            if result.is_ok() && !read {
+                bail!(Error::Usage(
+                    "this command doesn't read its input from stdin, so it \
+                     can't be used with --each"
+                        .to_string()
+                ));
+            }
+            match result {
+                Ok(output) => write(&output)?,
+                Err(error) => {
+                    let error = error.context(format!("line {}", line_number));
+                    if !self.keep_going {
+                        return Err(error);
+                    }
+                    report_error(&error);
+                    failures += 1;
+                }
+            }
+        }
+        if failures > 0 {
+            bail!("{} of {} envelopes failed", failures, processed);
+        }
+        Ok(())
+    }
+}
+
+/// The envelope a command reads when its envelope argument is omitted.
+#[derive(Debug, Clone)]
+struct EnvelopeInput {
+    envelope: String,
+    /// Set by `--each`, whose stream would be ignored by an envelope given on
+    /// the command line.
+    exclusive: bool,
+    /// Set once the command has read the input.
+    read: bool,
+}
+
+thread_local! {
+    static ENVELOPE_INPUT: RefCell<Option<EnvelopeInput>> =
+        const { RefCell::new(None) };
+}
+
+/// Runs `f` with `input` standing in for the line that a command would
+/// otherwise read from stdin when its envelope argument is omitted.
+///
+/// Used by the shell to feed a command the last envelope. An envelope given
+/// on the command line takes precedence.
+pub fn with_envelope_input<T>(input: &str, f: impl FnOnce() -> T) -> T {
+    with_input(input, false, f).0
+}
+
+/// Runs `f` with `input` supplied, returning its result and whether it read
+/// the input.
+fn with_input<T>(
+    input: &str,
+    exclusive: bool,
+    f: impl FnOnce() -> T,
+) -> (T, bool) {
+    let input =
+        EnvelopeInput { envelope: input.to_string(), exclusive, read: false };
+    let previous = ENVELOPE_INPUT.with_borrow_mut(|i| i.replace(input));
+    let result = f();
+    let current = ENVELOPE_INPUT.replace(previous);
+    (result, current.is_some_and(|i| i.read))
+}
+
+/// Takes the supplied input, if there is one, marking it as read.
+fn take_input() -> Option<String> {
+    ENVELOPE_INPUT.with_borrow_mut(|i| {
+        i.as_mut().map(|i| {
+            i.read = true;
+            i.envelope.clone()
+        })
+    })
+}
+
+/// Returns `true` if `--each` or the shell is supplying the envelope that
+/// would otherwise be read from stdin.
+pub fn has_envelope_input() -> bool {
+    ENVELOPE_INPUT.with_borrow(|i| i.is_some())
+}
+
+/// Fails with [`Error::Usage`] under `--each`, for commands given their
+/// envelope on the command line or with `--in`.
+pub fn reject_envelope_argument() -> Result<()> {
+    if ENVELOPE_INPUT.with_borrow(|i| i.as_ref().is_some_and(|i| i.exclusive))
+    {
+        bail!(Error::Usage(
+            "--each reads the envelopes from stdin; omit the envelope argument"
+                .to_string()
+        ));
+    }
+    Ok(())
+}
+
+/// Reads the textual form of a command's envelope: the argument if present,
+/// otherwise the envelope supplied by `--each` or the shell, otherwise the
+/// next line of stdin. Under `--each`, an envelope argument is an error.
+///
+/// This is for the clap adapters; the library readers such as
+/// [`read_envelope_string`] only ever read their argument or stdin.
+pub fn read_command_envelope_string(envelope: Option<&str>) -> Result<String> {
+    if envelope.is_some() {
+        reject_envelope_argument()?;
+    }
+    match envelope {
+        Some(_) => read_envelope_string(envelope),
+        None => match take_input() {
+            Some(input) => read_envelope_string(Some(&input)),
+            None => read_envelope_string(None),
+        },
+    }
+}
+
+/// Reads a command's envelope as [`read_command_envelope_string`] does.
+pub fn read_command_envelope(envelope: Option<&str>) -> Result<Envelope> {
+    read_envelope(Some(&read_command_envelope_string(envelope)?))
+}
+
+/// Returns the input supplied by `--each` or the shell, or `None` if the
+/// command should read stdin itself. Fails with [`Error::MissingInput`] if
+/// the supplied input is empty, as when the shell has no last envelope.
+fn supplied_input() -> Result<Option<String>> {
+    let Some(input) = take_input() else {
+        return Ok(None);
+    };
+    if input.is_empty() {
+        bail!(Error::MissingInput("No input provided".to_string()));
+    }
+    Ok(Some(input))
+}
+
+/// Reads a command's free-form argument: the argument if present, otherwise
+/// the input supplied by `--each` or the shell, otherwise all of stdin.
+///
+/// This is the adapter counterpart of [`read_argument`].
+pub fn read_command_argument(argument: Option<&str>) -> Result<String> {
+    if argument.is_none()
+        && let Some(input) = supplied_input()?
+    {
+        return Ok(input);
+    }
+    read_argument(argument)
+}
+
+/// Reads one line of a command's input: the input supplied by `--each` or
+/// the shell if there is one, otherwise the next line of stdin.
+pub fn read_command_line() -> Result<String> {
+    if let Some(input) = supplied_input()? {
+        return Ok(input);
+    }
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    Ok(line)
+}
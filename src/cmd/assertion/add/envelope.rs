@@ -2,6 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use super::assertion_add;
 use crate::{EnvelopeArgs, EnvelopeArgsLike};
 
 /// Add an assertion to the given envelope.
@@ -21,15 +22,13 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let assertion = Envelope::from_ur_string(&self.assertion)?;
-        Ok(envelope
-            .add_assertion_envelope_salted(assertion, self.salted)?
-            .ur_string())
+        Ok(assertion_add(&envelope, &assertion, self.salted)?.ur_string())
     }
 }
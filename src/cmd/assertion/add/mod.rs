@@ -2,6 +2,7 @@ pub mod envelope;
 pub mod pred_obj;
 
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::{Args, Subcommand};
 
 /// Add an assertion to the given envelope.
@@ -26,3 +27,12 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Adds `assertion` to `envelope`, salting it if `salted` is set.
+pub fn assertion_add(
+    envelope: &Envelope,
+    assertion: &Envelope,
+    salted: bool,
+) -> Result<Envelope> {
+    Ok(envelope.add_assertion_envelope_salted(assertion.clone(), salted)?)
+}
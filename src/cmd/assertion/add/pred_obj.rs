@@ -2,6 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use super::assertion_add;
 use crate::{
     DataType, EnvelopeArgs, EnvelopeArgsLike, PredObjArgs, PredObjArgsLike,
 };
@@ -30,15 +31,13 @@ impl PredObjArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let assertion = self.assertion_envelope()?;
-        Ok(envelope
-            .add_assertion_envelope_salted(assertion, self.salted)?
-            .ur_string())
+        Ok(assertion_add(&envelope, &assertion, self.salted)?.ur_string())
     }
 }
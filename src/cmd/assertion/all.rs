@@ -2,7 +2,10 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, assertions_json, is_json_output,
+    json_string,
+};
 
 /// Retrieve all the envelope's assertions.
 #[derive(Debug, Args)]
@@ -13,13 +16,16 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let assertions = envelope.assertions();
+        let assertions = assertion_all(&envelope);
+        if is_json_output() {
+            return json_string(&assertions_json(&assertions));
+        }
         let output = assertions
             .iter()
             .map(|a| a.ur_string())
@@ -28,3 +34,8 @@ impl crate::Exec for CommandArgs {
         Ok(output)
     }
 }
+
+/// Returns the assertions of `envelope`.
+pub fn assertion_all(envelope: &Envelope) -> Vec<Envelope> {
+    envelope.assertions()
+}
@@ -1,8 +1,8 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Retrieve the assertion at the given index.
 #[derive(Debug, Args)]
@@ -16,16 +16,22 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let assertions = envelope.assertions();
-        let assertion = assertions
-            .get(self.index)
-            .ok_or_else(|| anyhow!("Index out of bounds"))?;
-        Ok(assertion.ur_string())
+        Ok(assertion_at(&envelope, self.index)?.ur_string())
     }
 }
+
+/// Returns the assertion of `envelope` at `index`, failing with
+/// [`Error::NotFound`] if there is none.
+pub fn assertion_at(envelope: &Envelope, index: usize) -> Result<Envelope> {
+    let assertions = envelope.assertions();
+    let assertion = assertions
+        .get(index)
+        .ok_or_else(|| Error::NotFound("Index out of bounds".to_string()))?;
+    Ok(assertion.clone())
+}
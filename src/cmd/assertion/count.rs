@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::Args;
 
 use crate::{EnvelopeArgs, EnvelopeArgsLike};
@@ -12,12 +13,17 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        Ok(envelope.assertions().len().to_string())
+        Ok(assertion_count(&envelope).to_string())
     }
 }
+
+/// Returns the number of assertions on `envelope`.
+pub fn assertion_count(envelope: &Envelope) -> usize {
+    envelope.assertions().len()
+}
@@ -26,10 +26,22 @@ impl PredObjArgsLike for CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let mut result = self.pred_obj_args.assertion_envelope()?;
-        if self.salted {
-            result = result.add_salt();
-        }
-        Ok(result.ur_string())
+        let (predicate, object) = self.pred_obj_args.predicate_and_object()?;
+        Ok(assertion_create(&predicate, &object, self.salted).ur_string())
+    }
+}
+
+/// Returns an assertion of `predicate` and `object`, salted if `salted` is
+/// set.
+pub fn assertion_create(
+    predicate: &Envelope,
+    object: &Envelope,
+    salted: bool,
+) -> Envelope {
+    let assertion = Envelope::new_assertion(predicate.clone(), object.clone());
+    if salted {
+        assertion.add_salt()
+    } else {
+        assertion
     }
 }
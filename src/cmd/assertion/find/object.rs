@@ -1,9 +1,10 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
 use clap::Args;
 
 use crate::{
-    DataType, EnvelopeArgs, EnvelopeArgsLike, SubjectArgs, SubjectArgsLike,
+    DataType, EnvelopeArgs, EnvelopeArgsLike, Error, SubjectArgs,
+    SubjectArgsLike, assertions_json, is_json_output, json_string,
     parse_data_type_to_envelope,
 };
 
@@ -29,7 +30,7 @@ impl SubjectArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
@@ -40,16 +41,11 @@ impl crate::Exec for CommandArgs {
             self.subject_value(),
             self.ur_tag(),
         )?;
-        let assertions = envelope.clone().assertions();
-        let result = assertions
-            .iter()
-            .filter(|&a| {
-                a.as_object()
-                    .map(|o| o.digest() == object.digest())
-                    .unwrap_or(false)
-            })
-            .cloned()
-            .collect::<Vec<_>>()
+        let matches = assertion_find_object(&envelope, &object)?;
+        if is_json_output() {
+            return json_string(&assertions_json(&matches));
+        }
+        let result = matches
             .iter()
             .map(|a| a.ur_string())
             .collect::<Vec<String>>()
@@ -57,3 +53,24 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the assertions of `envelope` whose object is `object`, failing
+/// with [`Error::NotFound`] if there are none.
+pub fn assertion_find_object(
+    envelope: &Envelope,
+    object: &Envelope,
+) -> Result<Vec<Envelope>> {
+    let matches = envelope
+        .assertions()
+        .into_iter()
+        .filter(|a| {
+            a.as_object()
+                .map(|o| o.digest() == object.digest())
+                .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        bail!(Error::NotFound("No assertion with that object".to_string()));
+    }
+    Ok(matches)
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use super::assertion_remove;
 use crate::{EnvelopeArgs, EnvelopeArgsLike};
 
 /// Remove an assertion from the given envelope. The assertion must be a single
@@ -19,13 +20,13 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let assertion = Envelope::from_ur_string(&self.assertion)?;
-        Ok(envelope.remove_assertion(assertion).ur_string())
+        Ok(assertion_remove(&envelope, &assertion).ur_string())
     }
 }
@@ -2,6 +2,7 @@ pub mod envelope;
 pub mod pred_obj;
 
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::{Args, Subcommand};
 
 /// Remove an assertion from the given envelope.
@@ -26,3 +27,9 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Removes `assertion` from `envelope`. The envelope is returned unchanged if
+/// it has no such assertion.
+pub fn assertion_remove(envelope: &Envelope, assertion: &Envelope) -> Envelope {
+    envelope.remove_assertion(assertion.clone())
+}
@@ -2,6 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use super::assertion_remove;
 use crate::{
     DataType, EnvelopeArgs, EnvelopeArgsLike, PredObjArgs, PredObjArgsLike,
 };
@@ -19,7 +20,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl PredObjArgsLike for CommandArgs {
@@ -35,6 +36,6 @@ impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let assertion = self.assertion_envelope()?;
-        Ok(envelope.remove_assertion(assertion).ur_string())
+        Ok(assertion_remove(&envelope, &assertion).ur_string())
     }
 }
@@ -2,7 +2,11 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, read_envelope};
+use super::attachment_add;
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, attachment::create::attachment_create,
+    read_envelope,
+};
 
 /// Add an attachment to the given envelope by specifying its components.
 ///
@@ -25,18 +29,18 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let payload = read_envelope(Some(&self.payload))?;
-        let e = envelope.add_attachment(
-            payload,
-            self.vendor.as_str(),
+        let attachment = attachment_create(
+            &payload,
+            &self.vendor,
             self.conforms_to.as_deref(),
         );
-        Ok(e.ur_string())
+        Ok(attachment_add(&envelope, &attachment)?.ur_string())
     }
 }
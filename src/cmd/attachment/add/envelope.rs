@@ -2,6 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use super::attachment_add;
 use crate::{EnvelopeArgs, EnvelopeArgsLike, read_envelope};
 
 /// Add an attachment to the given envelope.
@@ -18,15 +19,13 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let attachment = read_envelope(Some(&self.attachment))?;
-        attachment.clone().validate_attachment()?;
-        let e = envelope.add_assertion_envelope(attachment.clone())?;
-        Ok(e.ur_string())
+        Ok(attachment_add(&envelope, &attachment)?.ur_string())
     }
 }
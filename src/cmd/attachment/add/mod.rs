@@ -2,6 +2,7 @@ pub mod components;
 pub mod envelope;
 
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::{Args, Subcommand};
 
 /// Add an assertion to the given envelope.
@@ -26,3 +27,13 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Adds `attachment` to `envelope` after checking that it is a well-formed
+/// attachment.
+pub fn attachment_add(
+    envelope: &Envelope,
+    attachment: &Envelope,
+) -> Result<Envelope> {
+    attachment.clone().validate_attachment()?;
+    Ok(envelope.add_assertion_envelope(attachment.clone())?)
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Retrieve all the envelope's assertions.
 #[derive(Debug, Args)]
@@ -13,8 +13,8 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = read_envelope(self.envelope.as_deref())?;
-        let attachments = envelope.attachments()?;
+        let envelope = read_command_envelope(self.envelope.as_deref())?;
+        let attachments = attachment_all(&envelope)?;
         let result = attachments
             .into_iter()
             .map(|a| a.ur_string())
@@ -23,3 +23,8 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the attachments of `envelope`.
+pub fn attachment_all(envelope: &Envelope) -> Result<Vec<Envelope>> {
+    Ok(envelope.attachments()?)
+}
@@ -1,8 +1,8 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::{Error, read_command_envelope};
 
 /// Get the attachment at the specified index.
 #[derive(Debug, Args)]
@@ -14,11 +14,17 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = read_envelope(self.envelope.as_deref())?;
-        let attachments = &envelope.attachments()?;
-        let attachment = attachments
-            .get(self.index)
-            .ok_or_else(|| anyhow!("No attachment at index {}", self.index))?;
-        Ok(attachment.ur_string())
+        let envelope = read_command_envelope(self.envelope.as_deref())?;
+        Ok(attachment_at(&envelope, self.index)?.ur_string())
     }
 }
+
+/// Returns the attachment of `envelope` at `index`, failing with
+/// [`Error::NotFound`] if there is none.
+pub fn attachment_at(envelope: &Envelope, index: usize) -> Result<Envelope> {
+    let attachments = envelope.attachments()?;
+    let attachment = attachments.get(index).ok_or_else(|| {
+        Error::NotFound(format!("No attachment at index {}", index))
+    })?;
+    Ok(attachment.clone())
+}
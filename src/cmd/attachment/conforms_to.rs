@@ -1,7 +1,8 @@
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Get the optional conformance of the attachment.
 #[derive(Debug, Args)]
@@ -13,7 +14,12 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let attachment = read_envelope(self.attachment.as_deref())?;
-        Ok(attachment.attachment_conforms_to()?.unwrap_or_default())
+        let attachment = read_command_envelope(self.attachment.as_deref())?;
+        Ok(attachment_conforms_to(&attachment)?.unwrap_or_default())
     }
 }
+
+/// Returns the optional `conforms-to` value of `attachment`.
+pub fn attachment_conforms_to(attachment: &Envelope) -> Result<Option<String>> {
+    Ok(attachment.attachment_conforms_to()?)
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Print the count of the envelope's assertions.
 #[derive(Debug, Args)]
@@ -12,7 +13,12 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = read_envelope(self.envelope.as_deref())?;
-        Ok(envelope.attachments()?.len().to_string())
+        let envelope = read_command_envelope(self.envelope.as_deref())?;
+        Ok(attachment_count(&envelope)?.to_string())
     }
 }
+
+/// Returns the number of attachments on `envelope`.
+pub fn attachment_count(envelope: &Envelope) -> Result<usize> {
+    Ok(envelope.attachments()?.len())
+}
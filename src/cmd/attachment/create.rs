@@ -2,7 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Create an attachment.
 #[derive(Debug, Args)]
@@ -23,12 +23,22 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let payload = read_envelope(self.payload.as_deref())?;
-        let assertion = Envelope::new_attachment(
-            payload,
-            self.vendor.as_str(),
+        let payload = read_command_envelope(self.payload.as_deref())?;
+        let assertion = attachment_create(
+            &payload,
+            &self.vendor,
             self.conforms_to.as_deref(),
         );
         Ok(assertion.ur_string())
     }
 }
+
+/// Returns an attachment assertion carrying `payload` from `vendor`, with an
+/// optional `conforms-to` value.
+pub fn attachment_create(
+    payload: &Envelope,
+    vendor: &str,
+    conforms_to: Option<&str>,
+) -> Envelope {
+    Envelope::new_attachment(payload.clone(), vendor, conforms_to)
+}
@@ -1,8 +1,8 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::{Error, read_command_envelope};
 
 /// Retrieve attachments having the specified attributes.
 ///
@@ -21,8 +21,9 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let envelope = read_envelope(self.envelope.as_deref())?;
-        let attachments = envelope.attachments_with_vendor_and_conforms_to(
+        let envelope = read_command_envelope(self.envelope.as_deref())?;
+        let attachments = attachment_find(
+            &envelope,
             self.vendor.as_deref(),
             self.conforms_to.as_deref(),
         )?;
@@ -34,3 +35,18 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the attachments of `envelope` matching `vendor` and `conforms_to`
+/// where given, failing with [`Error::NotFound`] if there are none.
+pub fn attachment_find(
+    envelope: &Envelope,
+    vendor: Option<&str>,
+    conforms_to: Option<&str>,
+) -> Result<Vec<Envelope>> {
+    let attachments = envelope
+        .attachments_with_vendor_and_conforms_to(vendor, conforms_to)?;
+    if attachments.is_empty() {
+        bail!(Error::NotFound("No matching attachment".to_string()));
+    }
+    Ok(attachments)
+}
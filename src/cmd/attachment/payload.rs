@@ -2,7 +2,7 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Get the payload of the attachment.
 #[derive(Debug, Args)]
@@ -14,7 +14,12 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let attachment = read_envelope(self.attachment.as_deref())?;
-        Ok(attachment.attachment_payload()?.ur_string())
+        let attachment = read_command_envelope(self.attachment.as_deref())?;
+        Ok(attachment_payload(&attachment)?.ur_string())
     }
 }
+
+/// Returns the payload of `attachment`.
+pub fn attachment_payload(attachment: &Envelope) -> Result<Envelope> {
+    Ok(attachment.attachment_payload()?)
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
+use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::read_envelope;
+use crate::read_command_envelope;
 
 /// Get the vendor of the attachment.
 #[derive(Debug, Args)]
@@ -13,7 +14,12 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let attachment = read_envelope(self.attachment.as_deref())?;
-        Ok(attachment.attachment_vendor()?)
+        let attachment = read_command_envelope(self.attachment.as_deref())?;
+        attachment_vendor(&attachment)
     }
 }
+
+/// Returns the vendor of `attachment`.
+pub fn attachment_vendor(attachment: &Envelope) -> Result<String> {
+    Ok(attachment.attachment_vendor()?)
+}
@@ -17,17 +17,21 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        Ok(if self.subject {
-            envelope.compress_subject()
-        } else {
-            envelope.compress()
-        }?
-        .ur_string())
+        Ok(compress(&envelope, self.subject)?.ur_string())
+    }
+}
+
+/// Compresses `envelope`, or only its subject if `subject_only` is set.
+pub fn compress(envelope: &Envelope, subject_only: bool) -> Result<Envelope> {
+    if subject_only {
+        Ok(envelope.compress_subject()?)
+    } else {
+        Ok(envelope.compress()?)
     }
 }
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::{Args, ValueEnum};
+use serde_json::{Map, Value};
+
+use super::{
+    COMPRESSED_KEY, ELIDED_KEY, ENCRYPTED_KEY, OBSCURED_KEY, SUBJECT_KEY,
+    TYPE_KEY, VALUE_KEY,
+};
+use crate::{DataType, parse_data_type_to_envelope, read_command_argument};
+
+/// Create an envelope from JSON.
+///
+/// Each key of a JSON object becomes a predicate and each value an object.
+/// Arrays produce one assertion per element, and nested objects become nested
+/// envelopes. The subject is given by the `@subject` key, or is the unit value
+/// `''` if there is none.
+///
+/// A string key in single quotes, like `'isA'`, is a known value predicate.
+/// A string predicate that begins with `@` or `'` is written with an extra
+/// `@` in front, so the key `"@@subject"` is the string predicate `"@subject"`.
+/// Values of other types are written as `{"@type": TYPE, "@value": VALUE}`,
+/// where TYPE is one of the types accepted by `subject type`.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The JSON to convert. If not provided, it is read from stdin.
+    #[arg(value_name = "JSON")]
+    json_text: Option<String>,
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let json = read_command_argument(self.json_text.as_deref())?;
+        let value: Value =
+            serde_json::from_str(&json).context("invalid JSON")?;
+        Ok(envelope_from_json(&value)?.ur_string())
+    }
+}
+
+/// Converts a JSON value to an envelope.
+pub fn envelope_from_json(value: &Value) -> Result<Envelope> {
+    match value {
+        Value::Null => Ok(Envelope::null()),
+        Value::Bool(b) => parse_data_type_to_envelope(
+            DataType::Bool,
+            Some(&b.to_string()),
+            None,
+        ),
+        Value::Number(n) => parse_data_type_to_envelope(
+            DataType::Number,
+            Some(&n.to_string()),
+            None,
+        ),
+        Value::String(s) => {
+            parse_data_type_to_envelope(DataType::String, Some(s), None)
+        }
+        Value::Array(_) => {
+            bail!("arrays are only supported as the values of object keys")
+        }
+        Value::Object(object) if object.contains_key(TYPE_KEY) => {
+            typed_value_from_json(object)
+        }
+        Value::Object(object) => node_from_json(object),
+    }
+}
+
+/// Converts a `{"@type": TYPE, "@value": VALUE}` object to an envelope.
+fn typed_value_from_json(object: &Map<String, Value>) -> Result<Envelope> {
+    if let Some(key) =
+        object.keys().find(|k| *k != TYPE_KEY && *k != VALUE_KEY)
+    {
+        bail!("unexpected key \"{}\" in a typed value", key);
+    }
+    let type_name = object[TYPE_KEY]
+        .as_str()
+        .context("\"@type\" must be a string")?;
+    let data_type = DataType::from_str(type_name, true)
+        .map_err(|_| anyhow::anyhow!("unknown type \"{}\"", type_name))?;
+    let value = match object.get(VALUE_KEY) {
+        None => None,
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(value @ (Value::Number(_) | Value::Bool(_))) => {
+            Some(value.to_string())
+        }
+        Some(_) => bail!("\"@value\" must be a string, number, or boolean"),
+    };
+    parse_data_type_to_envelope(data_type, value.as_deref(), None)
+        .with_context(|| format!("invalid value for type \"{}\"", type_name))
+}
+
+/// Converts a JSON object to an envelope with one assertion per key.
+fn node_from_json(object: &Map<String, Value>) -> Result<Envelope> {
+    let mut envelope = match object.get(SUBJECT_KEY) {
+        Some(subject) => envelope_from_json(subject)
+            .context("invalid \"@subject\"")?,
+        None => Envelope::unit(),
+    };
+    for (key, value) in object {
+        match key.as_str() {
+            SUBJECT_KEY => continue,
+            ELIDED_KEY | ENCRYPTED_KEY | COMPRESSED_KEY | OBSCURED_KEY => {
+                bail!(
+                    "\"{}\" nodes can't be converted back to an envelope",
+                    key
+                )
+            }
+            _ if key.starts_with("@@") || key.starts_with("@'") => {}
+            _ if key.starts_with('@') => bail!("unknown key \"{}\"", key),
+            _ => {}
+        }
+        let predicate = predicate_from_key(key)?;
+        let objects = match value {
+            Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        for object in objects {
+            if object.is_array() {
+                bail!("nested arrays are not supported (key \"{}\")", key);
+            }
+            let object = envelope_from_json(object)
+                .with_context(|| format!("invalid value for \"{}\"", key))?;
+            envelope = envelope.add_assertion(predicate.clone(), object);
+        }
+    }
+    Ok(envelope)
+}
+
+/// A key in single quotes names a known value; any other key is a string,
+/// with the leading `@` of an escaped key removed.
+fn predicate_from_key(key: &str) -> Result<Envelope> {
+    if let Some(text) = key.strip_prefix('@') {
+        return parse_data_type_to_envelope(DataType::String, Some(text), None);
+    }
+    match key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')) {
+        Some(name) => {
+            parse_data_type_to_envelope(DataType::Known, Some(name), None)
+                .with_context(|| format!("unknown known value {}", key))
+        }
+        None => parse_data_type_to_envelope(DataType::String, Some(key), None),
+    }
+}
@@ -0,0 +1,44 @@
+pub mod from_json;
+pub mod to_json;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// Convert between envelopes and JSON.
+///
+/// JSON objects map to envelopes: each key becomes a predicate and each value
+/// an object. See `docs/JSON-Conversion.md` for the full mapping.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: ConvertCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConvertCommands {
+    FromJson(from_json::CommandArgs),
+    ToJson(to_json::CommandArgs),
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        match &self.command {
+            ConvertCommands::FromJson(args) => args.exec(),
+            ConvertCommands::ToJson(args) => args.exec(),
+        }
+    }
+}
+
+/// The key holding the subject of an envelope with assertions.
+const SUBJECT_KEY: &str = "@subject";
+/// The key holding the data type of a typed scalar.
+const TYPE_KEY: &str = "@type";
+/// The key holding the value of a typed scalar.
+const VALUE_KEY: &str = "@value";
+/// The key holding assertions that are elided, encrypted, or compressed.
+const OBSCURED_KEY: &str = "@obscured";
+/// The keys marking elided, encrypted, and compressed nodes.
+const ELIDED_KEY: &str = "@elided";
+const ENCRYPTED_KEY: &str = "@encrypted";
+const COMPRESSED_KEY: &str = "@compressed";
@@ -0,0 +1,173 @@
+use anyhow::{Result, bail};
+use bc_components::{ARID, URI, UUID};
+use bc_envelope::prelude::*;
+use clap::Args;
+use dcbor::Simple;
+use serde_json::{Map, Value, json};
+
+use super::{
+    COMPRESSED_KEY, ELIDED_KEY, ENCRYPTED_KEY, OBSCURED_KEY, SUBJECT_KEY,
+    TYPE_KEY, VALUE_KEY,
+};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, json_string};
+
+/// Render an envelope as JSON.
+///
+/// This is the reverse of `convert from-json`. Elided, encrypted, and
+/// compressed nodes are rendered as `{"@elided": DIGEST}`, `{"@encrypted":
+/// DIGEST}`, and `{"@compressed": DIGEST}`. Obscured assertions are listed
+/// under the `@obscured` key.
+///
+/// String predicates become keys, and known value predicates become keys in
+/// single quotes. A string predicate that begins with `@` or `'` is written
+/// with an extra `@` in front, so `"@subject"` becomes `"@@subject"`. JSON
+/// object keys can only be strings, so any other predicate, or a predicate
+/// with assertions of its own, is an error.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let envelope = self.read_envelope()?;
+        json_string(&envelope_to_json(&envelope)?)
+    }
+}
+
+/// Converts an envelope to a JSON value.
+///
+/// Fails with [`Error::Usage`] if a predicate can't be written as a key.
+pub fn envelope_to_json(envelope: &Envelope) -> Result<Value> {
+    let value = match envelope.case() {
+        EnvelopeCase::Node { subject, assertions, .. } => {
+            let mut object = Map::new();
+            if !subject.is_subject_unit() {
+                object.insert(SUBJECT_KEY.into(), envelope_to_json(subject)?);
+            }
+            let mut obscured = Vec::new();
+            for assertion in assertions {
+                let (Some(predicate), Some(value)) =
+                    (assertion.as_predicate(), assertion.as_object())
+                else {
+                    obscured.push(envelope_to_json(assertion)?);
+                    continue;
+                };
+                let value = envelope_to_json(&value)?;
+                let key = predicate_key(&predicate)?;
+                match object.get_mut(&key) {
+                    Some(Value::Array(values)) => values.push(value),
+                    Some(existing) => {
+                        *existing = Value::Array(vec![existing.take(), value])
+                    }
+                    None => {
+                        object.insert(key, value);
+                    }
+                }
+            }
+            if !obscured.is_empty() {
+                object.insert(OBSCURED_KEY.into(), Value::Array(obscured));
+            }
+            Value::Object(object)
+        }
+        EnvelopeCase::Leaf { cbor, .. } => leaf_to_json(cbor),
+        EnvelopeCase::Wrapped { envelope, .. } => {
+            typed_value("wrapped", envelope.ur_string())
+        }
+        EnvelopeCase::KnownValue { value, .. } => {
+            typed_value("known", known_value_name(value))
+        }
+        EnvelopeCase::Elided(digest) => {
+            json!({ ELIDED_KEY: digest.ur_string() })
+        }
+        EnvelopeCase::Encrypted(_) => {
+            json!({ ENCRYPTED_KEY: envelope.digest().ur_string() })
+        }
+        EnvelopeCase::Compressed(_) => {
+            json!({ COMPRESSED_KEY: envelope.digest().ur_string() })
+        }
+        EnvelopeCase::Assertion(_) => {
+            typed_value("envelope", envelope.ur_string())
+        }
+    };
+    Ok(value)
+}
+
+/// Text predicates become plain keys, escaped with a leading `@` if they
+/// could be mistaken for a reserved key or a known value. Known values are
+/// quoted, as in envelope notation.
+fn predicate_key(predicate: &Envelope) -> Result<String> {
+    if predicate.assertions().is_empty() {
+        if let Ok(text) = predicate.extract_subject::<String>() {
+            if text.starts_with(['@', '\'']) {
+                return Ok(format!("@{}", text));
+            }
+            return Ok(text);
+        }
+        if let Some(known_value) = predicate.as_known_value() {
+            return Ok(format!("'{}'", known_value_name(known_value)));
+        }
+    }
+    bail!(Error::Usage(format!(
+        "the predicate {} can't be written as a JSON key",
+        predicate.format_flat()
+    )))
+}
+
+fn known_value_name(known_value: &KnownValue) -> String {
+    with_format_context!(|context: &FormatContext| {
+        KnownValuesStore::name_for_known_value(
+            known_value.clone(),
+            Some(context.known_values()),
+        )
+    })
+}
+
+fn typed_value(type_name: &str, value: impl Into<Value>) -> Value {
+    json!({ TYPE_KEY: type_name, VALUE_KEY: value.into() })
+}
+
+/// JSON strings, numbers, booleans, and null map directly. Other CBOR values
+/// become typed values, falling back to hex-encoded CBOR.
+fn leaf_to_json(cbor: &CBOR) -> Value {
+    match cbor.as_case() {
+        CBORCase::Text(text) => return text.as_str().into(),
+        CBORCase::Unsigned(n) => return (*n).into(),
+        CBORCase::Negative(n) => {
+            if let Ok(n) = i64::try_from(*n) {
+                return (-1 - n).into();
+            }
+        }
+        CBORCase::Simple(Simple::True) => return true.into(),
+        CBORCase::Simple(Simple::False) => return false.into(),
+        CBORCase::Simple(Simple::Null) => return Value::Null,
+        CBORCase::Simple(Simple::Float(f)) => {
+            if let Some(n) = serde_json::Number::from_f64(*f) {
+                return Value::Number(n);
+            }
+        }
+        CBORCase::ByteString(data) => {
+            return typed_value("data", hex::encode(data));
+        }
+        _ => {}
+    }
+    if let Ok(date) = Date::try_from(cbor.clone()) {
+        typed_value("date", date.to_string())
+    } else if let Ok(arid) = ARID::try_from(cbor.clone()) {
+        typed_value("arid", arid.ur_string())
+    } else if let Ok(uri) = URI::try_from(cbor.clone()) {
+        typed_value("uri", uri.to_string())
+    } else if let Ok(uuid) = UUID::try_from(cbor.clone()) {
+        typed_value("uuid", uuid.to_string())
+    } else if let Ok(digest) = Digest::try_from(cbor.clone()) {
+        typed_value("digest", digest.ur_string())
+    } else {
+        typed_value("cbor", cbor.hex())
+    }
+}
@@ -17,17 +17,21 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        Ok(if self.subject {
-            envelope.decompress_subject()
-        } else {
-            envelope.decompress()
-        }?
-        .ur_string())
+        Ok(decompress(&envelope, self.subject)?.ur_string())
+    }
+}
+
+/// Decompresses `envelope`, or only its subject if `subject_only` is set.
+pub fn decompress(envelope: &Envelope, subject_only: bool) -> Result<Envelope> {
+    if subject_only {
+        Ok(envelope.decompress_subject()?)
+    } else {
+        Ok(envelope.decompress()?)
     }
 }
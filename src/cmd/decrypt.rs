@@ -1,10 +1,22 @@
-use anyhow::{Result, bail};
-use bc_components::{PrivateKeyBase, PrivateKeys, SymmetricKey};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use bc_components::{
+    Decrypter, EncapsulationScheme, EncryptedKey, KeyDerivationMethod,
+    PrivateKeyBase, PrivateKeys, SealedMessage, SymmetricKey,
+};
 use bc_envelope::prelude::*;
 use clap::Args;
+use serde_json::{Value, json};
 
 use super::{ASKPASS_HELP, ASKPASS_LONG_HELP};
-use crate::{EnvelopeArgs, EnvelopeArgsLike, read_password};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, Error, is_json_output, json_string,
+    read_password,
+};
 
 /// Decrypt the envelope's subject.
 ///
@@ -15,27 +27,49 @@ use crate::{EnvelopeArgs, EnvelopeArgsLike, read_password};
 ///     - A recipient's private key (ur:crypto-prvkey-base or ur:crypto-prvkeys)
 ///       that was used to lock the content key,
 ///     - An SSH identity that was used to lock the content key.
+///
+/// Several of these may be given, including several recipients and a keyring
+/// file of recipients' private keys; each is tried in turn until one unlocks
+/// the subject. Use `--locks` to list the ways the content key is locked.
 #[derive(Debug, Args)]
 #[group(skip)]
 pub struct CommandArgs {
-    /// The symmetric key to use to decrypt the envelope's subject.
-    /// (ur:crypto-key)
-    ///
-    /// May not be used with the `--password` or `--recipient` options.
+    #[command(flatten)]
+    unlock_args: UnlockArgs,
+
+    /// List the locks on the content key instead of decrypting: one line for
+    /// each recipient, with its encapsulation scheme, and for each secret,
+    /// with its key derivation method.
     #[arg(
         long,
-        short,
-        conflicts_with = "recipient",
-        conflicts_with = "password",
-        conflicts_with = "ssh_id"
+        conflicts_with_all = [
+            "key", "password", "recipient", "keyring", "ssh_id"
+        ]
     )]
+    locks: bool,
+
+    /// Unwrap the decrypted envelope, reversing `encrypt --whole`.
+    #[arg(long, conflicts_with = "locks")]
+    whole: bool,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+/// The credentials that may unlock the content key of an encrypted envelope.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct UnlockArgs {
+    /// The symmetric key to use to decrypt the envelope's subject.
+    /// (ur:crypto-key)
+    #[arg(long, short)]
     key: Option<String>,
 
     /// The password to derive the symmetric key.
     ///
-    /// If not provided, will be prompted. May not be used with the `--key` or
-    /// `--recipient` options.
-    #[arg(long, short, num_args(0..=1), conflicts_with = "key", conflicts_with = "recipient", conflicts_with = "ssh_id")]
+    /// If not provided, will be prompted. The password is only read if the
+    /// envelope is locked with a password.
+    #[arg(long, short, num_args(0..=1))]
     password: Option<Option<String>>,
 
     /// Use the `SSH_ASKPASS` environment variable to read the password.
@@ -44,16 +78,16 @@ pub struct CommandArgs {
     #[arg(long, requires = "password", help = ASKPASS_HELP, long_help = ASKPASS_LONG_HELP)]
     askpass: bool,
 
-    /// The recipient to whom the envelope's subject should be decrypted.
-    /// (ur:crypto-prvkey-base or ur:crypto-prvkeys)
-    #[arg(
-        long,
-        short,
-        conflicts_with = "key",
-        conflicts_with = "password",
-        conflicts_with = "ssh_id"
-    )]
-    recipient: Option<String>,
+    /// A recipient whose private key may decrypt the envelope's subject.
+    /// (ur:crypto-prvkey-base or ur:crypto-prvkeys) May be repeated.
+    #[arg(long, short)]
+    recipient: Vec<String>,
+
+    /// A file of recipients' private keys to try, one UR per line.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE")]
+    keyring: Option<PathBuf>,
 
     /// The SSH identity to use to decrypt the envelope's subject.
     ///
@@ -61,21 +95,64 @@ pub struct CommandArgs {
     ///   associated with the identity.
     /// - If provided but empty, the first available Ed25519 key in the SSH
     ///   agent is used.
-    #[arg(
-        long,
-        short,
-        conflicts_with = "key",
-        conflicts_with = "password",
-        conflicts_with = "recipient"
-    )]
+    #[arg(long, short)]
     ssh_id: Option<String>,
+}
 
-    #[command(flatten)]
-    envelope_args: EnvelopeArgs,
+impl UnlockArgs {
+    /// Returns the unlock methods given on the command line, in the order
+    /// they are tried.
+    ///
+    /// The password is only read if `envelope` is locked with a password. It
+    /// is an error for no method to be given.
+    pub fn methods(&self, envelope: &Envelope) -> Result<Vec<UnlockMethod>> {
+        let mut methods = Vec::new();
+        if let Some(key_ur) = &self.key {
+            let key = SymmetricKey::from_ur_string(key_ur)?;
+            methods.push(UnlockMethod::Key(key));
+        }
+        for recipient_ur in &self.recipient {
+            methods.push(UnlockMethod::parse_recipient(recipient_ur)?);
+        }
+        if let Some(path) = &self.keyring {
+            methods.extend(read_keyring(path)?);
+        }
+        if let Some(ssh_id) = &self.ssh_id {
+            methods.push(UnlockMethod::SshAgent(ssh_id.clone()));
+        }
+        if let Some(password) = &self.password {
+            // Check before prompting, so the user isn't asked for a password
+            // that can't be used.
+            if envelope.is_locked_with_password() {
+                methods.push(UnlockMethod::Password(read_password(
+                    "Decryption password:",
+                    password.as_deref(),
+                    self.askpass,
+                )?));
+            } else if methods.is_empty() {
+                bail!(crypto_error("envelope is not locked with a password"));
+            }
+        }
+        if methods.is_empty() {
+            let locks = envelope_locks(envelope).unwrap_or_default();
+            let mut message = "missing unlock method: either a symmetric key, \
+                               password, recipient's private key, or SSH \
+                               identity must be provided"
+                .to_string();
+            if !locks.is_empty() {
+                message.push_str(&format!(
+                    "; the content key is locked by {}",
+                    describe_locks(&locks)
+                ));
+            }
+            bail!(Error::MissingInput(message));
+        }
+        Ok(methods)
+    }
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
@@ -83,54 +160,395 @@ impl crate::Exec for CommandArgs {
         // Read the envelope from the specified file or stdin
         let envelope = self.read_envelope()?;
 
-        if let Some(key_ur) = &self.key {
-            // If a content key is provided, decrypt the subject using it
-            let key = SymmetricKey::from_ur_string(key_ur)?;
-            let decrypt_subject = envelope.decrypt_subject(&key);
-            match decrypt_subject {
-                Err(_) => bail!("decrypt failed"),
-                Ok(subject) => Ok(subject.ur_string()),
+        if self.locks {
+            let locks = envelope_locks(&envelope)?;
+            if is_json_output() {
+                let locks: Vec<Value> =
+                    locks.iter().map(|lock| lock.kind.to_json()).collect();
+                return json_string(&Value::Array(locks));
             }
-        } else if let Some(password) = &self.password {
-            // If a password is provided, unlock the subject using it
+            return Ok(locks
+                .iter()
+                .map(|lock| lock.kind.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+
+        let methods = self.unlock_args.methods(&envelope)?;
+        let decrypted = decrypt_with_any(&envelope, &methods)?;
+        if self.whole {
+            Ok(unwrap_whole(&decrypted)?.ur_string())
+        } else {
+            Ok(decrypted.ur_string())
+        }
+    }
+}
+
+/// Reads a keyring file of recipients' private keys, one UR per line.
+fn read_keyring(path: &Path) -> Result<Vec<UnlockMethod>> {
+    let text = fs::read_to_string(path).with_context(|| {
+        format!("could not read keyring {}", path.display())
+    })?;
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            UnlockMethod::parse_recipient(line).with_context(|| {
+                format!("{} line {}", path.display(), line_number)
+            })
+        })
+        .collect()
+}
+
+/// A way of decrypting an envelope's subject.
+#[derive(Debug, Clone)]
+pub enum UnlockMethod {
+    /// The content key itself.
+    Key(SymmetricKey),
+    /// A password that locks the content key.
+    Password(String),
+    /// A recipient's private keys.
+    Recipient(PrivateKeys),
+    /// A recipient's private key base.
+    RecipientBase(PrivateKeyBase),
+    /// An SSH agent identity that locks the content key.
+    SshAgent(String),
+}
+
+impl UnlockMethod {
+    /// Parses a recipient's `ur:crypto-prvkeys` or `ur:crypto-prvkey-base`.
+    pub fn parse_recipient(recipient_ur: &str) -> Result<Self> {
+        // Try to parse as PrivateKeys first, then PrivateKeyBase.
+        if let Ok(recipient) = PrivateKeys::from_ur_string(recipient_ur) {
+            Ok(Self::Recipient(recipient))
+        } else if let Ok(recipient) =
+            PrivateKeyBase::from_ur_string(recipient_ur)
+        {
+            Ok(Self::RecipientBase(recipient))
+        } else {
+            bail!(
+                "invalid recipient private key: must be ur:crypto-prvkeys or ur:crypto-prvkey-base"
+            )
+        }
+    }
+}
+
+/// Decrypts the subject of `envelope` using `method`.
+///
+/// Failures are reported as [`Error::Crypto`], with a message that says why
+/// the method didn't work.
+pub fn decrypt(envelope: &Envelope, method: &UnlockMethod) -> Result<Envelope> {
+    if let UnlockMethod::Key(key) = method {
+        if !envelope.subject().is_encrypted() {
+            bail!(crypto_error("envelope subject is not encrypted"));
+        }
+        return decrypt_with_key(envelope, key);
+    }
+    let content_key = unlock_content_key(envelope, method)?;
+    Ok(envelope.decrypt_subject(&content_key)?)
+}
+
+/// Decrypts the subject of `envelope` with the content key itself.
+fn decrypt_with_key(
+    envelope: &Envelope,
+    key: &SymmetricKey,
+) -> Result<Envelope> {
+    envelope.decrypt_subject(key).map_err(|_| {
+        crypto_error("decrypt failed: wrong key or corrupted data").into()
+    })
+}
+
+/// Unwraps an envelope decrypted from one encrypted with
+/// [`EncryptOptions::whole`](super::encrypt::EncryptOptions::whole), returning
+/// the envelope that was encrypted. The locks on the content key are dropped.
+pub fn unwrap_whole(decrypted: &Envelope) -> Result<Envelope> {
+    decrypted.try_unwrap().map_err(|_| {
+        Error::Parse(
+            "the decrypted subject is not a wrapped envelope; it was not \
+             encrypted with --whole"
+                .to_string(),
+        )
+        .into()
+    })
+}
+
+/// Recovers the content key of `envelope`, whose subject is encrypted, using
+/// `method`.
+///
+/// Failures are reported as for [`decrypt`].
+pub fn unlock_content_key(
+    envelope: &Envelope,
+    method: &UnlockMethod,
+) -> Result<SymmetricKey> {
+    if !envelope.subject().is_encrypted() {
+        bail!(crypto_error("envelope subject is not encrypted"));
+    }
+    match method {
+        UnlockMethod::Key(key) => {
+            // Only decrypting shows whether the key is the content key.
+            decrypt_with_key(envelope, key)?;
+            Ok(key.clone())
+        }
+        UnlockMethod::Password(password) => {
             if !envelope.is_locked_with_password() {
-                bail!("envelope is not locked with a password");
-            }
-            let password = read_password(
-                "Decryption password:",
-                password.as_deref(),
-                self.askpass,
-            )?;
-            Ok(envelope.unlock_subject(password.as_bytes())?.ur_string())
-        } else if let Some(recipient_ur) = &self.recipient {
-            // If a recipient's private key is provided, decrypt the subject
-            // using it. Try to parse as PrivateKeys first, then PrivateKeyBase.
-            if let Ok(recipient) = PrivateKeys::from_ur_string(recipient_ur) {
-                Ok(envelope
-                    .decrypt_subject_to_recipient(&recipient)?
-                    .ur_string())
-            } else if let Ok(recipient) =
-                PrivateKeyBase::from_ur_string(recipient_ur)
-            {
-                Ok(envelope
-                    .decrypt_subject_to_recipient(&recipient)?
-                    .ur_string())
-            } else {
-                bail!(
-                    "invalid recipient private key: must be ur:crypto-prvkeys or ur:crypto-prvkey-base"
-                )
+                bail!(crypto_error("envelope is not locked with a password"));
             }
-        } else if let Some(ssh_id) = &self.ssh_id {
-            // If an SSH identity is provided, decrypt the subject using the SSH
-            // agent
+            unlock_secret(envelope, password, EncryptedKey::is_password_based)?
+                .ok_or_else(|| {
+                    crypto_error("decrypt failed: wrong password").into()
+                })
+        }
+        UnlockMethod::Recipient(recipient) => {
+            unlock_recipient(envelope, recipient)
+        }
+        UnlockMethod::RecipientBase(recipient) => {
+            unlock_recipient(envelope, recipient)
+        }
+        UnlockMethod::SshAgent(ssh_id) => {
             if !envelope.is_locked_with_ssh_agent() {
-                bail!("envelope is not locked with an SSH agent");
+                bail!(crypto_error("envelope is not locked with an SSH agent"));
             }
-            Ok(envelope.unlock_subject(ssh_id)?.ur_string())
-        } else {
-            bail!(
-                "missing unlock method: either a symmetric key, password, recipient's private key, or SSH identity must be provided"
-            );
+            unlock_secret(envelope, ssh_id, EncryptedKey::is_ssh_agent)?
+                .ok_or_else(|| {
+                    crypto_error(
+                        "decrypt failed: no matching key in the SSH agent",
+                    )
+                    .into()
+                })
+        }
+    }
+}
+
+/// Tries `secret` on each of the envelope's `hasSecret` locks that
+/// `accepts`, returning the content key if one opens.
+fn unlock_secret(
+    envelope: &Envelope,
+    secret: &str,
+    accepts: fn(&EncryptedKey) -> bool,
+) -> Result<Option<SymmetricKey>> {
+    let assertions =
+        envelope.assertions_with_predicate(known_values::HAS_SECRET);
+    for assertion in assertions {
+        let object = assertion.try_object()?;
+        if object.is_obscured() {
+            continue;
+        }
+        let encrypted_key = object.extract_subject::<EncryptedKey>()?;
+        if accepts(&encrypted_key)
+            && let Ok(content_key) = encrypted_key.unlock(secret)
+        {
+            return Ok(Some(content_key));
+        }
+    }
+    Ok(None)
+}
+
+fn unlock_recipient(
+    envelope: &Envelope,
+    recipient: &dyn Decrypter,
+) -> Result<SymmetricKey> {
+    for sealed_message in envelope.recipients()? {
+        if let Ok(data) = sealed_message.decrypt(recipient) {
+            return Ok(SymmetricKey::from_tagged_cbor_data(data)?);
         }
     }
+    bail!(crypto_error(
+        "decrypt failed: the private key is not a recipient of this envelope"
+    ))
+}
+
+/// Decrypts the subject of `envelope` with the first of `methods` that
+/// unlocks it.
+///
+/// With a single method this is the same as [`decrypt`]. When none of
+/// several methods works, the [`Error::Crypto`] names the envelope's locks
+/// and why each method failed.
+pub fn decrypt_with_any(
+    envelope: &Envelope,
+    methods: &[UnlockMethod],
+) -> Result<Envelope> {
+    with_any(envelope, methods, |method| decrypt(envelope, method))
+}
+
+/// Recovers the content key of `envelope` with the first of `methods` that
+/// unlocks it. Failures are reported as for [`decrypt_with_any`].
+pub fn unlock_content_key_with_any(
+    envelope: &Envelope,
+    methods: &[UnlockMethod],
+) -> Result<SymmetricKey> {
+    with_any(envelope, methods, |method| unlock_content_key(envelope, method))
 }
+
+/// Returns the result of `unlock` for the first of `methods` that unlocks
+/// `envelope`, collecting the reasons the others failed.
+fn with_any<T>(
+    envelope: &Envelope,
+    methods: &[UnlockMethod],
+    unlock: impl Fn(&UnlockMethod) -> Result<T>,
+) -> Result<T> {
+    if let [method] = methods {
+        return unlock(method);
+    }
+    if !envelope.subject().is_encrypted() {
+        bail!(crypto_error("envelope subject is not encrypted"));
+    }
+    let mut failures = Vec::new();
+    let mut recipient_number = 0;
+    for method in methods {
+        let label = match method {
+            UnlockMethod::Key(_) => "key".to_string(),
+            UnlockMethod::Password(_) => "password".to_string(),
+            UnlockMethod::Recipient(_) | UnlockMethod::RecipientBase(_) => {
+                recipient_number += 1;
+                format!("recipient {}", recipient_number)
+            }
+            UnlockMethod::SshAgent(_) => "SSH identity".to_string(),
+        };
+        match unlock(method) {
+            Ok(result) => return Ok(result),
+            Err(error) => match error.downcast_ref::<Error>() {
+                Some(Error::Crypto(message)) => failures.push(format!(
+                    "{}: {}",
+                    label,
+                    message.trim_start_matches("decrypt failed: ")
+                )),
+                _ => return Err(error.context(label)),
+            },
+        }
+    }
+    let locks = envelope_locks(envelope)?;
+    bail!(crypto_error(&format!(
+        "decrypt failed: none of the {} credentials unlocks the content key, \
+         which is locked by {} ({})",
+        methods.len(),
+        describe_locks(&locks),
+        failures.join("; ")
+    )))
+}
+
+/// One of the `hasRecipient` or `hasSecret` assertions that lock the content
+/// key of an encrypted envelope.
+#[derive(Debug, Clone)]
+pub struct Lock {
+    /// The assertion holding the locked content key.
+    pub assertion: Envelope,
+    pub kind: LockKind,
+}
+
+/// How a [`Lock`] locks the content key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockKind {
+    /// The content key is sealed to a recipient's public key.
+    Recipient(EncapsulationScheme),
+    /// The content key is encrypted with a key derived from a secret, such
+    /// as a password or an SSH agent signature.
+    Secret(KeyDerivationMethod),
+}
+
+impl LockKind {
+    pub fn to_json(&self) -> Value {
+        match self {
+            LockKind::Recipient(scheme) => json!({
+                "type": "recipient",
+                "scheme": encapsulation_scheme_name(*scheme),
+            }),
+            LockKind::Secret(method) => json!({
+                "type": "secret",
+                "derivation": method.to_string(),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for LockKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockKind::Recipient(scheme) => {
+                write!(f, "recipient {}", encapsulation_scheme_name(*scheme))
+            }
+            LockKind::Secret(method) => write!(f, "secret {}", method),
+        }
+    }
+}
+
+/// Returns the name of an encapsulation scheme, such as `X25519` or
+/// `MLKEM768`.
+pub fn encapsulation_scheme_name(scheme: EncapsulationScheme) -> &'static str {
+    match scheme {
+        EncapsulationScheme::X25519 => "X25519",
+        EncapsulationScheme::MLKEM512 => "MLKEM512",
+        EncapsulationScheme::MLKEM768 => "MLKEM768",
+        EncapsulationScheme::MLKEM1024 => "MLKEM1024",
+    }
+}
+
+/// Returns the locks on the content key of `envelope`, in the order of its
+/// assertions. Elided locks are skipped.
+pub fn envelope_locks(envelope: &Envelope) -> Result<Vec<Lock>> {
+    let mut locks = Vec::new();
+    for assertion in envelope.assertions() {
+        let (Some(predicate), Some(object)) =
+            (assertion.as_predicate(), assertion.as_object())
+        else {
+            continue;
+        };
+        if object.is_obscured() {
+            continue;
+        }
+        let kind = match predicate.as_known_value() {
+            Some(value) if *value == known_values::HAS_RECIPIENT => {
+                let sealed_message = object.extract_subject::<SealedMessage>()?;
+                LockKind::Recipient(sealed_message.encapsulation_scheme())
+            }
+            Some(value) if *value == known_values::HAS_SECRET => {
+                let encrypted_key = object.extract_subject::<EncryptedKey>()?;
+                let aad = encrypted_key.aad_cbor()?.try_into_array()?;
+                let Some(method) = aad.first() else {
+                    bail!("the secret has no key derivation method");
+                };
+                LockKind::Secret(KeyDerivationMethod::try_from(method)?)
+            }
+            _ => continue,
+        };
+        locks.push(Lock { assertion, kind });
+    }
+    Ok(locks)
+}
+
+/// Describes locks for a message, such as `2 X25519 recipients, 1 Argon2id
+/// secret`.
+pub fn describe_locks(locks: &[Lock]) -> String {
+    if locks.is_empty() {
+        return "no recipients or secrets".to_string();
+    }
+    let mut counts: Vec<(LockKind, usize)> = Vec::new();
+    for lock in locks {
+        match counts.iter_mut().find(|(kind, _)| *kind == lock.kind) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((lock.kind, 1)),
+        }
+    }
+    // Recipients come before secrets, as "recipient" sorts before "secret".
+    counts.sort_by_key(|(kind, _)| kind.to_string());
+    counts
+        .iter()
+        .map(|(kind, count)| {
+            let plural = if *count == 1 { "" } else { "s" };
+            match kind {
+                LockKind::Recipient(scheme) => format!(
+                    "{} {} recipient{}",
+                    count,
+                    encapsulation_scheme_name(*scheme),
+                    plural
+                ),
+                LockKind::Secret(method) => {
+                    format!("{} {} secret{}", count, method, plural)
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn crypto_error(message: &str) -> Error { Error::Crypto(message.to_string()) }
@@ -0,0 +1,368 @@
+use std::collections::HashSet;
+
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+use serde_json::{Value, json};
+
+use super::patch::{Patch, PatchOperation};
+use crate::{
+    Error, digest_json, is_json_output, json_string, read_command_envelope,
+    read_envelope, summary_json,
+};
+
+/// Compare two envelopes.
+///
+/// Walks the digest trees of both envelopes and reports the assertions added
+/// and removed, the subjects that changed, and the elements that are elided
+/// in one envelope but revealed in the other. The first line says whether
+/// the envelopes are identical (same structure), equivalent (same digest,
+/// but elided, encrypted, or compressed differently), or different.
+///
+/// With `--patch`, the differences are written as a patch envelope that
+/// transforms the first envelope into the second.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The original envelope.
+    first: String,
+
+    /// The changed envelope. If not supplied, it is read from stdin.
+    second: Option<String>,
+
+    /// Write the differences as a patch envelope.
+    #[arg(long)]
+    patch: bool,
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        if self.patch && is_json_output() {
+            bail!(Error::Usage(
+                "--patch cannot be combined with --json".to_string()
+            ));
+        }
+        let first = read_envelope(Some(&self.first))?;
+        let second = read_command_envelope(self.second.as_deref())?;
+        let diff = envelope_diff(&first, &second);
+        if self.patch {
+            Ok(diff.patch().into_envelope().ur_string())
+        } else if is_json_output() {
+            json_string(&diff.to_json())
+        } else {
+            Ok(diff.to_string())
+        }
+    }
+}
+
+/// A change found by [`envelope_diff`], with the element of the original
+/// envelope it affects.
+#[derive(Debug, Clone)]
+pub struct DiffChange {
+    pub operation: PatchOperation,
+    /// The element of the original envelope that is removed or replaced.
+    pub original: Option<Envelope>,
+}
+
+/// The differences between two envelopes.
+#[derive(Debug, Clone)]
+pub struct EnvelopeDiff {
+    pub from: Digest,
+    pub to: Digest,
+    /// The envelopes have the same digest.
+    pub equivalent: bool,
+    /// The envelopes have the same digest and the same structure.
+    pub identical: bool,
+    pub changes: Vec<DiffChange>,
+}
+
+impl EnvelopeDiff {
+    /// The patch that transforms the first envelope into the second.
+    pub fn patch(&self) -> Patch {
+        Patch {
+            from: self.from,
+            to: self.to,
+            operations: self
+                .changes
+                .iter()
+                .map(|change| change.operation.clone())
+                .collect(),
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        if self.identical {
+            "identical"
+        } else if self.equivalent {
+            "equivalent"
+        } else {
+            "different"
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let changes: Vec<Value> = self
+            .changes
+            .iter()
+            .map(|change| {
+                let operation = &change.operation;
+                let mut value = json!({
+                    "operation": operation.name(),
+                    "target": operation.target().ur_string(),
+                });
+                if let PatchOperation::RemoveAssertion { assertion, .. } =
+                    operation
+                {
+                    value["assertion"] = json!(assertion.ur_string());
+                }
+                if let Some(original) = &change.original {
+                    value["original"] = summary_json(Some(original.clone()));
+                }
+                if let Some(envelope) = operation.envelope() {
+                    value["summary"] = summary_json(Some(envelope.clone()));
+                    value["envelope"] = json!(envelope.ur_string());
+                }
+                value
+            })
+            .collect();
+        json!({
+            "status": self.status(),
+            "equivalent": self.equivalent,
+            "identical": self.identical,
+            "from": digest_json(&self.from),
+            "to": digest_json(&self.to),
+            "changes": changes,
+        })
+    }
+}
+
+impl std::fmt::Display for EnvelopeDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.status())?;
+        for change in &self.changes {
+            let target = change.operation.target().short_description();
+            let original = change
+                .original
+                .as_ref()
+                .map(|e| e.format_flat())
+                .unwrap_or_default();
+            let inserted = change
+                .operation
+                .envelope()
+                .map(|e| e.format_flat())
+                .unwrap_or_default();
+            match &change.operation {
+                PatchOperation::AddAssertion { .. } => {
+                    write!(f, "\n+ {} {}", target, inserted)?
+                }
+                PatchOperation::RemoveAssertion { .. } => {
+                    write!(f, "\n- {} {}", target, original)?
+                }
+                PatchOperation::ReplaceSubject { .. } => write!(
+                    f,
+                    "\n~ {} subject {} -> {}",
+                    target, original, inserted
+                )?,
+                PatchOperation::Replace { .. } => {
+                    write!(f, "\n~ {} {} -> {}", target, original, inserted)?
+                }
+                PatchOperation::Elide { .. } => {
+                    write!(f, "\n< {} elided {}", target, original)?
+                }
+                PatchOperation::Reveal { .. } => {
+                    write!(f, "\n> {} revealed {}", target, inserted)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares two envelopes, returning the changes that transform the first
+/// into the second.
+pub fn envelope_diff(first: &Envelope, second: &Envelope) -> EnvelopeDiff {
+    let mut changes = Vec::new();
+    diff_elements(first, second, &mut changes);
+    EnvelopeDiff {
+        from: first.digest(),
+        to: second.digest(),
+        equivalent: first.is_equivalent_to(second),
+        identical: first.is_identical_to(second),
+        changes,
+    }
+}
+
+fn diff_elements(a: &Envelope, b: &Envelope, changes: &mut Vec<DiffChange>) {
+    if a.is_identical_to(b) {
+        return;
+    }
+    let target = a.digest();
+    let equivalent = a.is_equivalent_to(b);
+    if equivalent && a.is_elided() {
+        changes.push(DiffChange {
+            operation: PatchOperation::Reveal { target, envelope: b.clone() },
+            original: None,
+        });
+        return;
+    }
+    if equivalent && b.is_elided() {
+        changes.push(DiffChange {
+            operation: PatchOperation::Elide { target },
+            original: Some(a.clone()),
+        });
+        return;
+    }
+    match (a.case(), b.case()) {
+        (EnvelopeCase::Wrapped { envelope: a, .. }, EnvelopeCase::Wrapped {
+            envelope: b,
+            ..
+        }) => diff_elements(a, b, changes),
+        // Only structured objects are compared element by element, so that
+        // a changed value replaces its whole assertion.
+        (EnvelopeCase::Assertion(a), EnvelopeCase::Assertion(b))
+            if equivalent
+                || (a.predicate().digest() == b.predicate().digest()
+                    && is_structured(&a.object())
+                    && is_structured(&b.object())) =>
+        {
+            diff_elements(&a.predicate(), &b.predicate(), changes);
+            diff_elements(&a.object(), &b.object(), changes);
+        }
+        _ if (a.is_node() || b.is_node())
+            && !a.is_obscured()
+            && !b.is_obscured() =>
+        {
+            diff_nodes(a, b, changes)
+        }
+        _ => changes.push(DiffChange {
+            operation: PatchOperation::Replace { target, envelope: b.clone() },
+            original: Some(a.clone()),
+        }),
+    }
+}
+
+fn is_structured(envelope: &Envelope) -> bool {
+    envelope.is_node() || envelope.is_wrapped()
+}
+
+/// Compares the subjects and assertions of two envelopes, at least one of
+/// which has assertions.
+fn diff_nodes(a: &Envelope, b: &Envelope, changes: &mut Vec<DiffChange>) {
+    let target = a.digest();
+    let (subject_a, subject_b) = (a.subject(), b.subject());
+    if subject_a.is_equivalent_to(&subject_b)
+        || (subject_a.is_wrapped() && subject_b.is_wrapped())
+    {
+        diff_elements(&subject_a, &subject_b, changes);
+    } else {
+        changes.push(DiffChange {
+            operation: PatchOperation::ReplaceSubject {
+                target,
+                subject: subject_b.clone(),
+            },
+            original: Some(subject_a),
+        });
+    }
+
+    let assertions_a = a.assertions();
+    let assertions_b = b.assertions();
+    let digests_a: HashSet<Digest> =
+        assertions_a.iter().map(|e| e.digest()).collect();
+    let digests_b: HashSet<Digest> =
+        assertions_b.iter().map(|e| e.digest()).collect();
+    let removed: Vec<&Envelope> = assertions_a
+        .iter()
+        .filter(|e| !digests_b.contains(&e.digest()))
+        .collect();
+    let added: Vec<&Envelope> = assertions_b
+        .iter()
+        .filter(|e| !digests_a.contains(&e.digest()))
+        .collect();
+
+    // Assertions in both are compared for elision.
+    for assertion_a in &assertions_a {
+        if let Some(assertion_b) = assertions_b
+            .iter()
+            .find(|e| e.digest() == assertion_a.digest())
+        {
+            diff_elements(assertion_a, assertion_b, changes);
+        }
+    }
+
+    // An assertion replaced by one with the same predicate is compared
+    // element by element when the pairing is unambiguous: first by predicate
+    // and the subject of the object, as for the keys of a XID document, then
+    // by predicate alone.
+    let mut paired = HashSet::new();
+    let pairs = pair_assertions(&removed, &added, &mut paired, |e| {
+        Some((e.as_predicate()?.digest(), e.as_object()?.subject().digest()))
+    })
+    .into_iter()
+    .chain(pair_assertions(&removed, &added, &mut paired, |e| {
+        Some(e.as_predicate()?.digest())
+    }));
+    for (assertion_a, assertion_b) in pairs {
+        diff_elements(assertion_a, assertion_b, changes);
+    }
+
+    for assertion in removed {
+        if !paired.contains(&assertion.digest()) {
+            changes.push(DiffChange {
+                operation: PatchOperation::RemoveAssertion {
+                    target,
+                    assertion: assertion.digest(),
+                },
+                original: Some(assertion.clone()),
+            });
+        }
+    }
+    for assertion in added {
+        if !paired.contains(&assertion.digest()) {
+            changes.push(DiffChange {
+                operation: PatchOperation::AddAssertion {
+                    target,
+                    assertion: assertion.clone(),
+                },
+                original: None,
+            });
+        }
+    }
+}
+
+/// Pairs each removed assertion with an added one that has the same key,
+/// when the key is unique on both sides. Paired assertions are recorded in
+/// `paired` and skipped by later calls.
+fn pair_assertions<'a, K: PartialEq>(
+    removed: &[&'a Envelope],
+    added: &[&'a Envelope],
+    paired: &mut HashSet<Digest>,
+    key: impl Fn(&Envelope) -> Option<K>,
+) -> Vec<(&'a Envelope, &'a Envelope)> {
+    let unpaired = |list: &[&'a Envelope], paired: &HashSet<Digest>| {
+        list.iter()
+            .copied()
+            .filter(|e| !paired.contains(&e.digest()))
+            .collect::<Vec<_>>()
+    };
+    let (removed, added) = (unpaired(removed, paired), unpaired(added, paired));
+    let unique = |list: &[&'a Envelope], k: &K| {
+        let mut matches = list.iter().filter(|e| key(e).as_ref() == Some(k));
+        match (matches.next(), matches.next()) {
+            (Some(e), None) => Some(*e),
+            _ => None,
+        }
+    };
+    let mut pairs = Vec::new();
+    for assertion_a in &removed {
+        let Some(k) = key(assertion_a) else {
+            continue;
+        };
+        if let (Some(a), Some(b)) = (unique(&removed, &k), unique(&added, &k))
+        {
+            paired.insert(a.digest());
+            paired.insert(b.digest());
+            pairs.push((a, b));
+        }
+    }
+    pairs
+}
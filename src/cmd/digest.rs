@@ -4,7 +4,9 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, digests_json, is_json_output, json_string,
+};
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Depth {
@@ -31,19 +33,16 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let digests: HashSet<Digest> = match self.depth {
-            Depth::Top => {
-                vec![envelope.digest()].into_iter().collect::<HashSet<_>>()
-            }
-            Depth::Shallow => envelope.shallow_digests(),
-            Depth::Deep => envelope.deep_digests(),
-        };
+        let digests = envelope_digests(&envelope, self.depth);
+        if is_json_output() {
+            return json_string(&digests_json(digests));
+        }
         let mut ordered_digests = digests.iter().cloned().collect::<Vec<_>>();
         ordered_digests.sort();
         let output = ordered_digests
@@ -54,3 +53,12 @@ impl crate::Exec for CommandArgs {
         Ok(output)
     }
 }
+
+/// Returns the digests of `envelope` down to `depth`.
+pub fn envelope_digests(envelope: &Envelope, depth: Depth) -> HashSet<Digest> {
+    match depth {
+        Depth::Top => HashSet::from([envelope.digest()]),
+        Depth::Shallow => envelope.shallow_digests(),
+        Depth::Deep => envelope.deep_digests(),
+    }
+}
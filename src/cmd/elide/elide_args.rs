@@ -5,7 +5,7 @@ use bc_components::SymmetricKey;
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
 
-use crate::parse_digests;
+use crate::{Error, parse_digests};
 
 /// The action to take on the elements.
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
@@ -33,7 +33,9 @@ pub trait ElideArgsLike {
             Action::Encrypt => {
                 let key = self
                     .key()
-                    .ok_or_else(|| anyhow::anyhow!("No key provided"))?;
+                    .ok_or_else(|| {
+                        Error::MissingInput("No key provided".to_string())
+                    })?;
                 let key = SymmetricKey::from_ur_string(key)?;
                 ObscureAction::Encrypt(key)
             }
@@ -41,14 +43,6 @@ pub trait ElideArgsLike {
         };
         Ok(action)
     }
-
-    fn run(&self, envelope: Envelope, revealing: bool) -> Result<Envelope> {
-        let target = self.get_target_set()?;
-        let action = self.get_action()?;
-        let result =
-            envelope.elide_set_with_action(&target, revealing, &action);
-        Ok(result)
-    }
 }
 
 #[derive(Debug, Args)]
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
@@ -25,13 +27,23 @@ impl ElideArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let result_envelope = self.run(envelope, true)?;
-        Ok(result_envelope.ur_string())
+        let target = self.get_target_set()?;
+        let action = self.get_action()?;
+        Ok(elide_revealing(&envelope, &target, &action).ur_string())
     }
 }
+
+/// Obscures the elements of `envelope` whose digests are not in `target`.
+pub fn elide_revealing(
+    envelope: &Envelope,
+    target: &HashSet<Digest>,
+    action: &ObscureAction,
+) -> Envelope {
+    envelope.elide_set_with_action(target, true, action)
+}
@@ -29,6 +29,9 @@ impl From<PasswordDerivationType> for KeyDerivationMethod {
 
 /// Encrypt the envelope's subject.
 ///
+/// The envelope's assertions are left in plaintext unless `--whole` is given,
+/// which wraps the envelope first so that they are encrypted too.
+///
 /// If the content key is not provided, an ephemerally-generated key is used.
 ///
 /// In addition, the content key can be locked, potentially several ways:
@@ -85,64 +88,137 @@ pub struct CommandArgs {
     #[arg(long, short)]
     recipient: Vec<String>,
 
+    /// Wrap the envelope before encrypting it, so that its assertions are
+    /// encrypted along with its subject. Use `decrypt --whole` to reverse it.
+    #[arg(long)]
+    whole: bool,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
 
-        // Get the content key
-        let content_key = match self.key {
-            Some(ref key) => SymmetricKey::from_ur_string(key)?,
-            None => SymmetricKey::new(),
+        let password = self
+            .password
+            .as_ref()
+            .map(|password| {
+                read_password(
+                    "Encryption password:",
+                    password.as_deref(),
+                    self.askpass,
+                )
+            })
+            .transpose()?;
+
+        let options = EncryptOptions {
+            key: self
+                .key
+                .as_ref()
+                .map(SymmetricKey::from_ur_string)
+                .transpose()?,
+            recipients: self
+                .recipient
+                .iter()
+                .map(|s| {
+                    PublicKeys::from_ur_string(s).map_err(anyhow::Error::from)
+                })
+                .collect::<Result<_>>()?,
+            password,
+            password_derivation: self.password_derivation.into(),
+            ssh_id: self.ssh_id.clone(),
+            whole: self.whole,
         };
 
-        // Encrypt the subject using the content key.
-        let mut encrypted_envelope = envelope.encrypt_subject(&content_key)?;
+        // Return the encrypted envelope as a UR string.
+        Ok(encrypt(&envelope, &options)?.ur_string())
+    }
+}
 
-        // Convert recipients to `PublicKeys`.
-        let recipients = self
-            .recipient
-            .iter()
-            .map(|s| PublicKeys::from_ur_string(s).map_err(anyhow::Error::from))
-            .collect::<Result<Vec<PublicKeys>, anyhow::Error>>()?;
+/// Options for [`encrypt`].
+#[derive(Debug, Clone)]
+pub struct EncryptOptions {
+    /// The content key. If `None`, an ephemeral key is generated.
+    pub key: Option<SymmetricKey>,
+    /// Public keys to lock the content key to.
+    pub recipients: Vec<PublicKeys>,
+    /// A password to lock the content key with.
+    pub password: Option<String>,
+    /// The key derivation method used with `password`.
+    pub password_derivation: KeyDerivationMethod,
+    /// An SSH agent identity to lock the content key with. An empty string
+    /// selects the only Ed25519 key in the agent.
+    pub ssh_id: Option<String>,
+    /// Whether to wrap the envelope before encrypting it, so that its
+    /// assertions are encrypted too.
+    pub whole: bool,
+}
 
-        // If there are recipients, add them.
-        for recipient in recipients {
-            encrypted_envelope =
-                encrypted_envelope.add_recipient(&recipient, &content_key);
+impl Default for EncryptOptions {
+    fn default() -> Self {
+        Self {
+            key: None,
+            recipients: Vec::new(),
+            password: None,
+            password_derivation: KeyDerivationMethod::Argon2id,
+            ssh_id: None,
+            whole: false,
         }
+    }
+}
 
-        // If there is a password, add it.
-        if let Some(password_argument) = &self.password {
-            let password = read_password(
-                "Encryption password:",
-                password_argument.as_ref().map(|s| s.as_str()),
-                self.askpass,
-            )?;
-            encrypted_envelope = encrypted_envelope.add_secret(
-                self.password_derivation.into(),
-                password.as_bytes(),
-                &content_key,
-            )?;
-        }
+/// Encrypts the subject of `envelope`, or the whole envelope if
+/// `options.whole` is set, and locks the content key with each of the methods
+/// given in `options`.
+pub fn encrypt(
+    envelope: &Envelope,
+    options: &EncryptOptions,
+) -> Result<Envelope> {
+    // Get the content key
+    let content_key = match &options.key {
+        Some(key) => key.clone(),
+        None => SymmetricKey::new(),
+    };
+
+    // Wrap the envelope if its assertions are to be encrypted too.
+    let envelope = if options.whole {
+        envelope.wrap()
+    } else {
+        envelope.clone()
+    };
+
+    // Encrypt the subject using the content key.
+    let mut encrypted_envelope = envelope.encrypt_subject(&content_key)?;
+
+    // If there are recipients, add them.
+    for recipient in &options.recipients {
+        encrypted_envelope =
+            encrypted_envelope.add_recipient(recipient, &content_key);
+    }
 
-        // If there is an SSH ID, add it.
-        if let Some(ref ssh_id) = self.ssh_id {
-            encrypted_envelope = encrypted_envelope.add_secret(
-                KeyDerivationMethod::SSHAgent,
-                ssh_id.as_bytes(),
-                &content_key,
-            )?;
-        }
+    // If there is a password, add it.
+    if let Some(password) = &options.password {
+        encrypted_envelope = encrypted_envelope.add_secret(
+            options.password_derivation,
+            password.as_bytes(),
+            &content_key,
+        )?;
+    }
 
-        // Return the encrypted envelope as a UR string.
-        Ok(encrypted_envelope.ur_string())
+    // If there is an SSH ID, add it.
+    if let Some(ssh_id) = &options.ssh_id {
+        encrypted_envelope = encrypted_envelope.add_secret(
+            KeyDerivationMethod::SSHAgent,
+            ssh_id.as_bytes(),
+            &content_key,
+        )?;
     }
+
+    Ok(encrypted_envelope)
 }
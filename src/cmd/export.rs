@@ -7,7 +7,7 @@ use clap::Args;
 use ssh_key::LineEnding;
 
 use super::{ASKPASS_HELP, ASKPASS_LONG_HELP};
-use crate::{read_argument, read_password};
+use crate::{read_command_argument, read_password};
 
 /// Import the given object to UR form.
 #[derive(Debug, Args)]
@@ -48,53 +48,34 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let object = read_argument(self.ur_string.as_deref())?;
+        let object = read_command_argument(self.ur_string.as_deref())?;
         if let Ok(signing_private_key) =
             SigningPrivateKey::from_ur_string(&object)
         {
-            if let Some(ssh_private_key) = signing_private_key.to_ssh() {
-                if self.encrypt {
-                    let mut rng = rand::rngs::OsRng;
-                    let password = read_password(
-                        "Key encryption password: ",
-                        self.password.as_deref(),
-                        self.askpass,
-                    )?;
-                    let openssh = ssh_private_key
-                        .encrypt(&mut rng, password)?
-                        .to_openssh(LineEnding::LF)?;
-                    Ok(openssh.trim().to_string())
-                } else {
-                    Ok(ssh_private_key
-                        .to_openssh(LineEnding::LF)?
-                        .trim()
-                        .to_string())
-                }
-            } else {
+            if signing_private_key.to_ssh().is_none() {
                 bail!("UR is not an SSH private key.");
             }
+            let password = if self.encrypt {
+                Some(read_password(
+                    "Key encryption password: ",
+                    self.password.as_deref(),
+                    self.askpass,
+                )?)
+            } else {
+                None
+            };
+            export_private_key(&signing_private_key, password.as_deref())
         } else if let Ok(signing_public_key) =
             SigningPublicKey::from_ur_string(&object)
         {
-            if let Some(ssh_public_key) = signing_public_key.to_ssh() {
-                Ok(ssh_public_key.to_openssh()?.to_string())
-            } else {
-                bail!("UR is not an SSH public key.");
-            }
+            export_public_key(&signing_public_key)
         } else if let Ok(public_keys) = PublicKeys::from_ur_string(&object) {
-            if let Some(ssh_public_key) =
-                public_keys.signing_public_key().to_ssh()
-            {
-                Ok(ssh_public_key.to_openssh()?.to_string())
-            } else {
+            if public_keys.signing_public_key().to_ssh().is_none() {
                 bail!("UR is not a PublicKeys with an SSH public key.");
             }
+            export_public_key(public_keys.signing_public_key())
         } else if let Ok(signature) = Signature::from_ur_string(&object) {
-            if let Some(ssh_signature) = signature.to_ssh() {
-                Ok(ssh_signature.to_pem(LineEnding::LF)?.trim().to_string())
-            } else {
-                bail!("UR is not an SSH signature.");
-            }
+            export_signature(&signature)
         } else {
             bail!(
                 "Invalid object for export. Supported types are SSH `ur:signing-private-key`, SSH `ur:signing-public-key`, SSH `ur:crypto-pubkeys`, and `ur:signature`."
@@ -102,3 +83,41 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Exports an SSH signing private key to OpenSSH PEM, encrypting it with
+/// `password` if one is given.
+pub fn export_private_key(
+    signing_private_key: &SigningPrivateKey,
+    password: Option<&str>,
+) -> Result<String> {
+    let Some(ssh_private_key) = signing_private_key.to_ssh() else {
+        bail!("UR is not an SSH private key.");
+    };
+    let openssh = if let Some(password) = password {
+        let mut rng = rand::rngs::OsRng;
+        ssh_private_key
+            .encrypt(&mut rng, password)?
+            .to_openssh(LineEnding::LF)?
+    } else {
+        ssh_private_key.to_openssh(LineEnding::LF)?
+    };
+    Ok(openssh.trim().to_string())
+}
+
+/// Exports an SSH signing public key to single-line OpenSSH text.
+pub fn export_public_key(
+    signing_public_key: &SigningPublicKey,
+) -> Result<String> {
+    let Some(ssh_public_key) = signing_public_key.to_ssh() else {
+        bail!("UR is not an SSH public key.");
+    };
+    Ok(ssh_public_key.to_openssh()?.to_string())
+}
+
+/// Exports an SSH signature to PEM.
+pub fn export_signature(signature: &Signature) -> Result<String> {
+    let Some(ssh_signature) = signature.to_ssh() else {
+        bail!("UR is not an SSH signature.");
+    };
+    Ok(ssh_signature.to_pem(LineEnding::LF)?.trim().to_string())
+}
@@ -1,9 +1,17 @@
 use anyhow::{Result, bail};
-use bc_components::{ARID, Digest, URI, UUID, XID, tags::TAG_ENVELOPE};
+use bc_components::{
+    ARID, Digest, URI, UUID, XID,
+    tags::{TAG_ARID, TAG_DIGEST, TAG_ENVELOPE, TAG_URI, TAG_UUID},
+};
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
+use dcbor::{TAG_NEGATIVE_BIGNUM, TAG_POSITIVE_BIGNUM};
+use serde_json::{Map, Value, json};
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, Error, bignum_string, is_json_output,
+    json_string,
+};
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SubjectType {
@@ -19,6 +27,12 @@ pub enum SubjectType {
     /// ARID: Apparently Random Identifier (hex)
     AridHex,
 
+    /// CBOR array, one element per line
+    Array,
+
+    /// Integer of any size, from a CBOR bignum or integer
+    Bignum,
+
     /// Boolean value
     Bool,
 
@@ -37,15 +51,24 @@ pub enum SubjectType {
     /// Envelope (ur:envelope)
     Envelope,
 
+    /// Integer from -2^64 to 2^64-1
+    Int,
+
     /// Known Value (number or string)
     Known,
 
-    /// Numeric value,
+    /// CBOR map, one `key: value` entry per line
+    Map,
+
+    /// Numeric value; integers are printed exactly
     Number,
 
     /// UTF-8 String
     String,
 
+    /// Unsigned integer from 0 to 2^64-1
+    Uint,
+
     /// Uniform Resource (UR)
     Ur,
 
@@ -78,61 +101,109 @@ pub struct CommandArgs {
     #[arg(long)]
     ur_tag: Option<u64>,
 
+    /// The type of the elements of an array or the values of a map.
+    #[arg(long, default_value = "auto")]
+    element: ElementType,
+
+    /// The type of the keys of a map.
+    #[arg(long, default_value = "auto")]
+    key: ElementType,
+
     #[command(flatten)]
     envelope_args: EnvelopeArgs,
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let string = match self.subject_type {
-            SubjectType::Assertion => extract_assertion(envelope)?,
-            SubjectType::Object => extract_object(envelope)?,
-            SubjectType::Predicate => extract_predicate(envelope)?,
-
-            SubjectType::Arid => {
-                envelope.extract_subject::<ARID>()?.ur_string()
-            }
-            SubjectType::AridHex => envelope.extract_subject::<ARID>()?.hex(),
-            SubjectType::Bool => {
-                envelope.extract_subject::<bool>()?.to_string()
-            }
-            SubjectType::Cbor => extract_cbor_string(envelope)?,
-            SubjectType::Data => hex::encode(
-                envelope
-                    .subject()
-                    .try_leaf()?
-                    .to_cbor()
-                    .try_into_byte_string()?,
-            ),
-            SubjectType::Date => {
-                envelope.extract_subject::<Date>()?.to_string()
-            }
-            SubjectType::Digest => {
-                envelope.extract_subject::<Digest>()?.ur_string()
-            }
-            SubjectType::Envelope => envelope.subject().ur_string(),
-            SubjectType::Known => extract_known_value_string(envelope)?,
-            SubjectType::Number => {
-                envelope.extract_subject::<f64>()?.to_string()
-            }
-            SubjectType::String => envelope.extract_subject::<String>()?,
-            SubjectType::Ur => self.extract_ur(envelope)?,
-            SubjectType::Uri => envelope.extract_subject::<URI>()?.to_string(),
-            SubjectType::Uuid => {
-                envelope.extract_subject::<UUID>()?.to_string()
-            }
-            SubjectType::Wrapped => envelope.try_unwrap()?.ur_string(),
-            SubjectType::Xid => envelope.extract_subject::<XID>()?.ur_string(),
+        let options = ExtractOptions {
+            ur_type: self.ur_type.clone(),
+            ur_tag: self.ur_tag,
+            element: self.element,
+            key: self.key,
+            json: is_json_output(),
         };
-        Ok(string)
+        extract(&envelope, self.subject_type, &options)
+    }
+}
+
+/// Options for [`extract`].
+#[derive(Debug, Clone)]
+pub struct ExtractOptions {
+    /// The type for an extracted UR whose tag has no name.
+    pub ur_type: Option<String>,
+    /// The expected tag for an extracted UR.
+    pub ur_tag: Option<u64>,
+    /// The type of the elements of an array or the values of a map.
+    pub element: ElementType,
+    /// The type of the keys of a map.
+    pub key: ElementType,
+    /// Write arrays and maps as JSON.
+    pub json: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            ur_type: None,
+            ur_tag: None,
+            element: ElementType::Auto,
+            key: ElementType::Auto,
+            json: false,
+        }
     }
 }
 
+/// Extracts the subject of `envelope` as `subject_type`, returning the text
+/// that `extract` prints.
+pub fn extract(
+    envelope: &Envelope,
+    subject_type: SubjectType,
+    options: &ExtractOptions,
+) -> Result<String> {
+    let envelope = envelope.clone();
+    let string = match subject_type {
+        SubjectType::Assertion => extract_assertion(envelope)?,
+        SubjectType::Object => extract_object(envelope)?,
+        SubjectType::Predicate => extract_predicate(envelope)?,
+
+        SubjectType::Arid => envelope.extract_subject::<ARID>()?.ur_string(),
+        SubjectType::AridHex => envelope.extract_subject::<ARID>()?.hex(),
+        SubjectType::Array => extract_array(envelope, options)?,
+        SubjectType::Bignum => bignum_string(&envelope.subject().try_leaf()?)?,
+        SubjectType::Bool => envelope.extract_subject::<bool>()?.to_string(),
+        SubjectType::Cbor => extract_cbor_string(envelope)?,
+        SubjectType::Data => hex::encode(
+            envelope
+                .subject()
+                .try_leaf()?
+                .to_cbor()
+                .try_into_byte_string()?,
+        ),
+        SubjectType::Date => envelope.extract_subject::<Date>()?.to_string(),
+        SubjectType::Digest => {
+            envelope.extract_subject::<Digest>()?.ur_string()
+        }
+        SubjectType::Envelope => envelope.subject().ur_string(),
+        SubjectType::Int => extract_int_string(envelope)?,
+        SubjectType::Known => extract_known_value_string(envelope)?,
+        SubjectType::Map => extract_map(envelope, options)?,
+        SubjectType::Number => extract_number_string(envelope)?,
+        SubjectType::String => envelope.extract_subject::<String>()?,
+        SubjectType::Uint => extract_uint_string(envelope)?,
+        SubjectType::Ur => extract_ur(envelope, options)?,
+        SubjectType::Uri => envelope.extract_subject::<URI>()?.to_string(),
+        SubjectType::Uuid => envelope.extract_subject::<UUID>()?.to_string(),
+        SubjectType::Wrapped => envelope.try_unwrap()?.ur_string(),
+        SubjectType::Xid => envelope.extract_subject::<XID>()?.ur_string(),
+    };
+    Ok(string)
+}
+
 fn extract_assertion(envelope: Envelope) -> Result<String> {
     if let Some(assertion) = envelope.as_assertion() {
         let pred_obj = [
@@ -165,50 +236,254 @@ fn extract_predicate(envelope: Envelope) -> Result<String> {
     }
 }
 
-impl CommandArgs {
-    fn extract_ur(&self, envelope: Envelope) -> Result<String> {
-        Ok(if let Some(cbor) = envelope.clone().subject().as_leaf() {
-            if let CBORCase::Tagged(tag, untagged_cbor) = cbor.into_case() {
-                let known_tag =
-                    with_tags!(|tags: &dyn dcbor::TagsStoreTrait| {
-                        tags.tag_for_value(tag.value())
-                    });
-                // Default to the provided ur_type if there is one.
-                let mut ur_type: Option<String> = self.ur_type.clone();
-                // If there is a known_tag and it has a name, then use that as
-                // the ur_type.
-                if let Some(known_tag) = known_tag
-                    && let Some(name) = known_tag.name()
-                {
-                    ur_type = Some(name.to_string());
-                }
-                // If there is no ur_type, then error.
-                if ur_type.is_none() {
-                    bail!("UR type required");
-                }
-                bc_ur::UR::new(ur_type.unwrap(), untagged_cbor)?.to_string()
-            } else {
-                bail!(
-                    "Can't convert to UR: CBOR in envelope subject has no tag"
-                );
+fn extract_array(
+    envelope: Envelope,
+    options: &ExtractOptions,
+) -> Result<String> {
+    let cbor = envelope.subject().try_leaf()?;
+    let CBORCase::Array(elements) = cbor.as_case() else {
+        bail!("Envelope subject is not a CBOR array.");
+    };
+    let values = elements
+        .iter()
+        .enumerate()
+        .map(|(index, element)| {
+            decode_element(element, options.element)
+                .map_err(|e| e.context(format!("element {}", index)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if options.json {
+        return json_string(&Value::Array(values));
+    }
+    Ok(values.iter().map(value_text).collect::<Vec<_>>().join("\n"))
+}
+
+fn extract_map(envelope: Envelope, options: &ExtractOptions) -> Result<String> {
+    let cbor = envelope.subject().try_leaf()?;
+    let CBORCase::Map(map) = cbor.as_case() else {
+        bail!("Envelope subject is not a CBOR map.");
+    };
+    let mut entries = Vec::new();
+    let mut object = Map::new();
+    for (key, value) in map.iter() {
+        let key = value_text(&decode_element(key, options.key)?);
+        let value = decode_element(value, options.element)
+            .map_err(|e| e.context(format!("value of {}", key)))?;
+        insert_unique(&mut object, key.clone(), value.clone())?;
+        entries.push((key, value));
+    }
+    if options.json {
+        return json_string(&Value::Object(object));
+    }
+    Ok(entries
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value_text(value)))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn extract_ur(envelope: Envelope, options: &ExtractOptions) -> Result<String> {
+    Ok(if let Some(cbor) = envelope.clone().subject().as_leaf() {
+        if let CBORCase::Tagged(tag, untagged_cbor) = cbor.into_case() {
+            let known_tag = with_tags!(|tags: &dyn dcbor::TagsStoreTrait| {
+                tags.tag_for_value(tag.value())
+            });
+            // Default to the provided ur_type if there is one.
+            let mut ur_type: Option<String> = options.ur_type.clone();
+            // If there is a known_tag and it has a name, then use that as
+            // the ur_type.
+            if let Some(known_tag) = known_tag
+                && let Some(name) = known_tag.name()
+            {
+                ur_type = Some(name.to_string());
             }
-        } else if envelope.is_wrapped() {
-            if self.ur_tag.is_some() || self.ur_type.is_some() {
-                if self.ur_tag != Some(TAG_ENVELOPE) {
-                    bail!("UR tag mismatch");
-                }
-                let envelope_name =
-                    with_format_context!(|context: &FormatContext| {
-                        context.tags().name_for_value(TAG_ENVELOPE)
-                    });
-                if self.ur_type != Some(envelope_name) {
-                    bail!("UR type mismatch");
-                }
+            // If there is no ur_type, then error.
+            if ur_type.is_none() {
+                bail!("UR type required");
             }
-            envelope.try_unwrap()?.ur_string()
+            bc_ur::UR::new(ur_type.unwrap(), untagged_cbor)?.to_string()
         } else {
-            bail!("No CBOR data found in envelope subject");
-        })
+            bail!("Can't convert to UR: CBOR in envelope subject has no tag");
+        }
+    } else if envelope.is_wrapped() {
+        if options.ur_tag.is_some() || options.ur_type.is_some() {
+            if options.ur_tag != Some(TAG_ENVELOPE) {
+                bail!("UR tag mismatch");
+            }
+            let envelope_name =
+                with_format_context!(|context: &FormatContext| {
+                    context.tags().name_for_value(TAG_ENVELOPE)
+                });
+            if options.ur_type != Some(envelope_name) {
+                bail!("UR type mismatch");
+            }
+        }
+        envelope.try_unwrap()?.ur_string()
+    } else {
+        bail!("No CBOR data found in envelope subject");
+    })
+}
+
+/// The type of the elements of an extracted array or map.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ElementType {
+    /// Any type; known tagged types are decoded
+    Auto,
+
+    /// Integer of any size, from a CBOR bignum or integer
+    Bignum,
+
+    /// Boolean value
+    Bool,
+
+    /// CBOR data in hex
+    Cbor,
+
+    /// Binary byte string in hex
+    Data,
+
+    /// Date (ISO 8601)
+    Date,
+
+    /// Cryptographic digest (ur:digest)
+    Digest,
+
+    /// Integer from -2^64 to 2^64-1
+    Int,
+
+    /// Numeric value; integers are printed exactly
+    Number,
+
+    /// UTF-8 String
+    String,
+
+    /// URI
+    Uri,
+
+    /// UUID
+    Uuid,
+}
+
+/// Decodes an element of an array or map as `element_type`, returning its
+/// JSON form.
+fn decode_element(cbor: &CBOR, element_type: ElementType) -> Result<Value> {
+    let value = match element_type {
+        ElementType::Auto => return decode_auto(cbor),
+        ElementType::Bignum => json!(bignum_string(cbor)?),
+        ElementType::Bool => json!(bool::try_from(cbor.clone())?),
+        ElementType::Cbor => json!(cbor.hex()),
+        ElementType::Data => {
+            json!(hex::encode(cbor.clone().try_into_byte_string()?))
+        }
+        ElementType::Date => json!(Date::try_from(cbor.clone())?.to_string()),
+        ElementType::Digest => {
+            json!(Digest::try_from(cbor.clone())?.ur_string())
+        }
+        ElementType::Int => match cbor.as_case() {
+            CBORCase::Unsigned(_) | CBORCase::Negative(_) => {
+                integer_json(cbor)?
+            }
+            _ => bail!("not an integer"),
+        },
+        ElementType::Number => match cbor.as_case() {
+            CBORCase::Unsigned(_) | CBORCase::Negative(_) => {
+                integer_json(cbor)?
+            }
+            _ => float_json(f64::try_from(cbor.clone())?)?,
+        },
+        ElementType::String => json!(String::try_from(cbor.clone())?),
+        ElementType::Uri => json!(URI::try_from(cbor.clone())?.to_string()),
+        ElementType::Uuid => json!(UUID::try_from(cbor.clone())?.to_string()),
+    };
+    Ok(value)
+}
+
+/// Decodes an element of any type. Arrays and maps are decoded recursively,
+/// and dates, digests, URIs, UUIDs, ARIDs, bignums, and envelopes are
+/// converted to their usual text forms.
+fn decode_auto(cbor: &CBOR) -> Result<Value> {
+    let value = match cbor.as_case() {
+        CBORCase::Unsigned(_) | CBORCase::Negative(_) => integer_json(cbor)?,
+        CBORCase::ByteString(data) => json!(hex::encode(data)),
+        CBORCase::Text(string) => json!(string),
+        CBORCase::Array(elements) => Value::Array(
+            elements.iter().map(decode_auto).collect::<Result<_>>()?,
+        ),
+        CBORCase::Map(map) => {
+            let mut object = Map::new();
+            for (key, value) in map.iter() {
+                let key = value_text(&decode_auto(key)?);
+                insert_unique(&mut object, key, decode_auto(value)?)?;
+            }
+            Value::Object(object)
+        }
+        CBORCase::Simple(simple) => match simple {
+            dcbor::Simple::True => json!(true),
+            dcbor::Simple::False => json!(false),
+            dcbor::Simple::Null => Value::Null,
+            dcbor::Simple::Float(f) => float_json(*f)?,
+        },
+        CBORCase::Tagged(tag, _) => match tag.value() {
+            dcbor::TAG_DATE => {
+                json!(Date::try_from(cbor.clone())?.to_string())
+            }
+            TAG_DIGEST => json!(Digest::try_from(cbor.clone())?.ur_string()),
+            TAG_URI => json!(URI::try_from(cbor.clone())?.to_string()),
+            TAG_UUID => json!(UUID::try_from(cbor.clone())?.to_string()),
+            TAG_ARID => json!(ARID::try_from(cbor.clone())?.ur_string()),
+            TAG_ENVELOPE => {
+                json!(Envelope::try_from(cbor.clone())?.ur_string())
+            }
+            TAG_POSITIVE_BIGNUM | TAG_NEGATIVE_BIGNUM => {
+                json!(bignum_string(cbor)?)
+            }
+            _ => json!(cbor.diagnostic_flat()),
+        },
+    };
+    Ok(value)
+}
+
+/// The JSON form of a CBOR integer: a number, or a string if it is out of
+/// the range of JSON integers.
+fn integer_json(cbor: &CBOR) -> Result<Value> {
+    Ok(match cbor.as_case() {
+        CBORCase::Unsigned(n) => json!(n),
+        CBORCase::Negative(n) => match i64::try_from(-1 - i128::from(*n)) {
+            Ok(n) => json!(n),
+            Err(_) => json!(bignum_string(cbor)?),
+        },
+        _ => bail!("not an integer"),
+    })
+}
+
+/// The JSON form of a float. NaN and the infinities have no JSON form.
+fn float_json(f: f64) -> Result<Value> {
+    if !f.is_finite() {
+        bail!(Error::Parse(format!("{} cannot be represented in JSON", f)));
+    }
+    Ok(json!(f))
+}
+
+/// Inserts a map entry, failing if its key is the same text as an earlier
+/// key's, such as the number `1` and the string `"1"`.
+fn insert_unique(
+    object: &mut Map<String, Value>,
+    key: String,
+    value: Value,
+) -> Result<()> {
+    if object.contains_key(&key) {
+        bail!(Error::Parse(format!("duplicate map key: {}", key)));
+    }
+    object.insert(key, value);
+    Ok(())
+}
+
+/// The text form of a decoded element. Strings are printed without quotes,
+/// and arrays and maps as JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
     }
 }
 
@@ -217,6 +492,29 @@ fn extract_known_value_string(envelope: Envelope) -> Result<String> {
     Ok(envelope.subject().format())
 }
 
+fn extract_int_string(envelope: Envelope) -> Result<String> {
+    let cbor = envelope.subject().try_leaf()?;
+    match cbor.as_case() {
+        CBORCase::Unsigned(_) | CBORCase::Negative(_) => bignum_string(&cbor),
+        _ => bail!("Envelope subject is not an integer."),
+    }
+}
+
+fn extract_uint_string(envelope: Envelope) -> Result<String> {
+    match envelope.subject().try_leaf()?.as_case() {
+        CBORCase::Unsigned(n) => Ok(n.to_string()),
+        _ => bail!("Envelope subject is not an unsigned integer."),
+    }
+}
+
+fn extract_number_string(envelope: Envelope) -> Result<String> {
+    let cbor = envelope.subject().try_leaf()?;
+    match cbor.as_case() {
+        CBORCase::Unsigned(_) | CBORCase::Negative(_) => bignum_string(&cbor),
+        _ => Ok(envelope.extract_subject::<f64>()?.to_string()),
+    }
+}
+
 fn extract_cbor_string(envelope: Envelope) -> Result<String> {
     let subject = &envelope.subject();
     Ok(if let Some(cbor) = subject.as_leaf() {
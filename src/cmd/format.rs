@@ -1,8 +1,13 @@
 use anyhow::Result;
+use bc_components::ARID;
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use super::parse::parse_envelope_notation;
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, GraphFormatOpts, dot_format, html_format,
+    svg_format,
+};
 
 /// Print the envelope in textual format.
 #[derive(Debug, Args)]
@@ -12,12 +17,20 @@ pub struct CommandArgs {
     #[arg(long = "type", id = "TYPE", default_value = "envelope")]
     format_type: FormatType,
 
-    /// For `tree` and `mermaid`, hides the NODE case and digests, which
-    /// provides a more semantically readable tree output.
+    /// For `tree`, `mermaid`, `dot`, `svg`, and `html`, hides the NODE case
+    /// and digests, which provides a more semantically readable tree output.
     #[arg(long)] // No short because it conflicts with `-h` for help.
     hide_nodes: bool,
 
-    /// For `tree`, specifies the format for displaying digests.
+    /// For `envelope`, writes notation that `parse` reads back as the same
+    /// envelope: strings escape their quotes, byte strings are written as
+    /// `h'HEX'`, ARIDs and digests in full, and other values that the usual
+    /// notation abbreviates as `ur:` literals.
+    #[arg(long)]
+    lossless: bool,
+
+    /// For `tree`, `dot`, `svg`, and `html`, specifies the format for
+    /// displaying digests.
     #[arg(long, short, default_value = "short")]
     digest_format: DigestFormatType,
 
@@ -25,11 +38,12 @@ pub struct CommandArgs {
     #[arg(long, short, default_value = "default")]
     theme: MermaidThemeType,
 
-    /// For `mermaid`, specifies the orientation of the diagram.
+    /// For `mermaid`, `dot`, and `svg`, specifies the orientation of the
+    /// diagram.
     #[arg(long, short, default_value = "left-to-right")]
     orientation: MermaidOrientationType,
 
-    /// For `mermaid`, do not color the nodes or edges.
+    /// For `mermaid`, `dot`, and `svg`, do not color the nodes or edges.
     #[arg(long, short)]
     monochrome: bool,
 
@@ -38,17 +52,23 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
-enum FormatType {
+pub enum FormatType {
     /// Envelope notation.
     Envelope,
     /// Envelope tree.
     Tree,
     /// Mermaid format.
     Mermaid,
+    /// Graphviz DOT format.
+    Dot,
+    /// Standalone SVG image.
+    Svg,
+    /// Self-contained HTML report.
+    Html,
     /// CBOR diagnostic notation.
     Diag,
     /// CBOR hex.
@@ -127,25 +147,210 @@ impl From<MermaidThemeType> for MermaidTheme {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let e = self.read_envelope()?;
-        let output = match self.format_type {
-            FormatType::Envelope => e.format(),
-            FormatType::Tree => e.tree_format_opt(
-                &TreeFormatOpts::default()
-                    .hide_nodes(self.hide_nodes)
-                    .digest_display(self.digest_format.into()),
-            ),
-            FormatType::Mermaid => e.mermaid_format_opt(
-                &MermaidFormatOpts::default()
-                    .hide_nodes(self.hide_nodes)
-                    .theme(self.theme.into())
-                    .monochrome(self.monochrome)
-                    .orientation(self.orientation.into()),
-            ),
-            FormatType::Diag => e.diagnostic(),
-            FormatType::Cbor => hex::encode(e.tagged_cbor_data()),
-            FormatType::UR => e.ur_string(),
+        let envelope = self.read_envelope()?;
+        let options = FormatOptions {
+            hide_nodes: self.hide_nodes,
+            lossless: self.lossless,
+            digest_format: self.digest_format,
+            theme: self.theme,
+            orientation: self.orientation,
+            monochrome: self.monochrome,
         };
-        Ok(output)
+        Ok(format_envelope(&envelope, self.format_type, &options))
     }
 }
+
+/// Options for [`format_envelope`]. Each applies only to the formats named
+/// by the matching `format` option.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub hide_nodes: bool,
+    pub lossless: bool,
+    pub digest_format: DigestFormatType,
+    pub theme: MermaidThemeType,
+    pub orientation: MermaidOrientationType,
+    pub monochrome: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            hide_nodes: false,
+            lossless: false,
+            digest_format: DigestFormatType::Short,
+            theme: MermaidThemeType::Default,
+            orientation: MermaidOrientationType::LeftToRight,
+            monochrome: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    fn graph_opts(&self) -> GraphFormatOpts {
+        GraphFormatOpts::default()
+            .hide_nodes(self.hide_nodes)
+            .digest_display(self.digest_format.into())
+            .orientation(self.orientation.into())
+            .monochrome(self.monochrome)
+    }
+}
+
+/// Writes `envelope` in `format_type`, as `format` prints it.
+pub fn format_envelope(
+    envelope: &Envelope,
+    format_type: FormatType,
+    options: &FormatOptions,
+) -> String {
+    let e = envelope;
+    match format_type {
+        FormatType::Envelope if options.lossless => format_lossless(e),
+        FormatType::Envelope => e.format(),
+        FormatType::Tree => e.tree_format_opt(
+            &TreeFormatOpts::default()
+                .hide_nodes(options.hide_nodes)
+                .digest_display(options.digest_format.into()),
+        ),
+        FormatType::Mermaid => e.mermaid_format_opt(
+            &MermaidFormatOpts::default()
+                .hide_nodes(options.hide_nodes)
+                .theme(options.theme.into())
+                .monochrome(options.monochrome)
+                .orientation(options.orientation.into()),
+        ),
+        FormatType::Dot => dot_format(e, &options.graph_opts()),
+        FormatType::Svg => svg_format(e, &options.graph_opts()),
+        FormatType::Html => html_format(e, &options.graph_opts()),
+        FormatType::Diag => e.diagnostic(),
+        FormatType::Cbor => hex::encode(e.tagged_cbor_data()),
+        FormatType::UR => e.ur_string(),
+    }
+}
+
+/// Writes envelope notation that [`parse_envelope_notation`] reads back as
+/// the same envelope.
+///
+/// The layout is that of [`Envelope::format`]. Elided, encrypted, and
+/// compressed elements are written as `format` writes them, and can't be read
+/// back.
+pub fn format_lossless(envelope: &Envelope) -> String {
+    lossless_content(envelope, 0)
+}
+
+fn indent(level: usize) -> String { " ".repeat(level * 4) }
+
+/// Writes an envelope, or an assertion as `predicate: object`.
+fn lossless_content(envelope: &Envelope, level: usize) -> String {
+    match envelope.case() {
+        EnvelopeCase::Assertion(assertion) => format!(
+            "{}: {}",
+            lossless_envelope(&assertion.predicate(), level),
+            lossless_envelope(&assertion.object(), level)
+        ),
+        _ => lossless_envelope(envelope, level),
+    }
+}
+
+/// Writes an envelope in a position where an assertion can't appear, such as
+/// a predicate or an object.
+fn lossless_envelope(envelope: &Envelope, level: usize) -> String {
+    let EnvelopeCase::Node {
+        subject,
+        assertions,
+        ..
+    } = envelope.case()
+    else {
+        return lossless_element(envelope, level);
+    };
+    // `format` braces an assertion that is the subject of a node.
+    let subject = if subject.is_assertion() {
+        lossless_braced(subject, level)
+    } else {
+        lossless_element(subject, level)
+    };
+    let mut lines: Vec<String> = assertions
+        .iter()
+        .map(|assertion| lossless_content(assertion, level + 1))
+        .collect();
+    lines.sort();
+    let mut output = format!("{} [\n", subject);
+    for line in lines {
+        output += &format!("{}{}\n", indent(level + 1), line);
+    }
+    output + &indent(level) + "]"
+}
+
+fn lossless_braced(envelope: &Envelope, level: usize) -> String {
+    format!(
+        "{{\n{}{}\n{}}}",
+        indent(level + 1),
+        lossless_content(envelope, level + 1),
+        indent(level)
+    )
+}
+
+fn lossless_element(envelope: &Envelope, level: usize) -> String {
+    match envelope.case() {
+        // Braces around an assertion are read as an assertion subject when
+        // `[` follows, so a wrapped assertion is written as a literal.
+        EnvelopeCase::Wrapped {
+            envelope: wrapped, ..
+        } if wrapped.is_assertion() => envelope.ur_string(),
+        EnvelopeCase::Wrapped {
+            envelope: wrapped, ..
+        } => lossless_braced(wrapped, level),
+        EnvelopeCase::Leaf { cbor, .. } => lossless_leaf(envelope, cbor),
+        EnvelopeCase::KnownValue { .. } => {
+            reads_back(envelope.format_flat(), envelope)
+                .unwrap_or_else(|| envelope.ur_string())
+        }
+        EnvelopeCase::Elided(_)
+        | EnvelopeCase::Encrypted(_)
+        | EnvelopeCase::Compressed(_) => envelope.format_flat(),
+        EnvelopeCase::Node { .. } | EnvelopeCase::Assertion(_) => {
+            envelope.ur_string()
+        }
+    }
+}
+
+fn lossless_leaf(envelope: &Envelope, cbor: &CBOR) -> String {
+    match cbor.as_case() {
+        CBORCase::Text(text) => {
+            let escaped = text
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            return format!("\"{}\"", escaped);
+        }
+        CBORCase::ByteString(data) => {
+            return format!("h'{}'", hex::encode(data));
+        }
+        _ => {}
+    }
+    if let Ok(arid) = ARID::try_from(cbor.clone()) {
+        return format!("ARID({})", arid.hex());
+    }
+    if let Ok(digest) = Digest::try_from(cbor.clone()) {
+        return format!("Digest({})", digest.hex());
+    }
+    reads_back(envelope.format_flat(), envelope)
+        .or_else(|| reads_back(tagged_ur_string(cbor)?, envelope))
+        .unwrap_or_else(|| envelope.ur_string())
+}
+
+/// Writes a tagged value as a UR of its tag's name, such as `ur:signature`.
+fn tagged_ur_string(cbor: &CBOR) -> Option<String> {
+    let CBORCase::Tagged(tag, content) = cbor.as_case() else {
+        return None;
+    };
+    let name = with_format_context!(|context: &FormatContext| {
+        context.tags().assigned_name_for_tag(tag)
+    })?;
+    Some(UR::new(name, content.clone()).ok()?.string())
+}
+
+/// Returns `notation` if it parses back to `envelope`.
+fn reads_back(notation: String, envelope: &Envelope) -> Option<String> {
+    parse_envelope_notation(&notation)
+        .is_ok_and(|parsed| parsed.is_identical_to(envelope))
+        .then_some(notation)
+}
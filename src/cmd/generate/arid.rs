@@ -14,7 +14,7 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let arid = ARID::new();
+        let arid = generate_arid();
         if self.hex {
             Ok(hex::encode(arid.as_bytes()))
         } else {
@@ -22,3 +22,6 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Returns a new random ARID.
+pub fn generate_arid() -> ARID { ARID::new() }
@@ -4,6 +4,8 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use crate::{has_envelope_input, read_command_argument};
+
 /// Generate a digest from the input data.
 ///
 /// If the `data` argument is given on the command line, it is taken as a UTF-8
@@ -21,10 +23,15 @@ impl crate::Exec for CommandArgs {
         let mut data = Vec::new();
         if let Some(ref d) = self.data {
             data.extend_from_slice(d.as_bytes());
+        } else if has_envelope_input() {
+            data.extend_from_slice(read_command_argument(None)?.as_bytes());
         } else {
             std::io::stdin().read_to_end(&mut data)?;
         }
-        let digest = Digest::from_image(&data);
+        let digest = generate_digest(&data);
         Ok(digest.ur_string())
     }
 }
+
+/// Returns the digest of `data`.
+pub fn generate_digest(data: &[u8]) -> Digest { Digest::from_image(data) }
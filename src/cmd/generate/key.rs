@@ -10,7 +10,10 @@ pub struct CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let key = SymmetricKey::new();
+        let key = generate_key();
         Ok(key.ur_string())
     }
 }
+
+/// Returns a new random symmetric key.
+pub fn generate_key() -> SymmetricKey { SymmetricKey::new() }
@@ -1,5 +1,7 @@
 use anyhow::Result;
-use bc_components::{EncapsulationScheme, PrivateKeys, SignatureScheme};
+use bc_components::{
+    EncapsulationScheme, PrivateKeys, PublicKeys, SignatureScheme,
+};
 use bc_ur::UREncodable;
 use clap::{Args, ValueEnum};
 
@@ -47,52 +49,38 @@ pub struct CommandArgs {
     encryption: EncapsulationSchemeArg,
 }
 
-impl crate::Exec for CommandArgs {
-    fn exec(&self) -> Result<String> {
-        let (signing_private_key, signing_public_key) = match self.signing {
-            SigningSchemeArg::Schnorr => SignatureScheme::Schnorr.keypair(),
-            SigningSchemeArg::Ecdsa => SignatureScheme::Ecdsa.keypair(),
-            SigningSchemeArg::Ed25519 => SignatureScheme::Ed25519.keypair(),
-            SigningSchemeArg::SshEd25519 => {
-                SignatureScheme::SshEd25519.keypair()
-            }
-            SigningSchemeArg::SshDsa => SignatureScheme::SshDsa.keypair(),
-            SigningSchemeArg::SshEcdsaP256 => {
-                SignatureScheme::SshEcdsaP256.keypair()
-            }
-            SigningSchemeArg::SshEcdsaP384 => {
-                SignatureScheme::SshEcdsaP384.keypair()
-            }
-            SigningSchemeArg::Mldsa44 => SignatureScheme::MLDSA44.keypair(),
-            SigningSchemeArg::Mldsa65 => SignatureScheme::MLDSA65.keypair(),
-            SigningSchemeArg::Mldsa87 => SignatureScheme::MLDSA87.keypair(),
-        };
-
-        let (encapsulation_private_key, encapsulation_public_key) =
-            match self.encryption {
-                EncapsulationSchemeArg::X25519 => {
-                    EncapsulationScheme::X25519.keypair()
-                }
-                EncapsulationSchemeArg::Mlkem512 => {
-                    EncapsulationScheme::MLKEM512.keypair()
-                }
-                EncapsulationSchemeArg::Mlkem768 => {
-                    EncapsulationScheme::MLKEM768.keypair()
-                }
-                EncapsulationSchemeArg::Mlkem1024 => {
-                    EncapsulationScheme::MLKEM1024.keypair()
-                }
-            };
+impl SigningSchemeArg {
+    fn scheme(self) -> SignatureScheme {
+        match self {
+            Self::Schnorr => SignatureScheme::Schnorr,
+            Self::Ecdsa => SignatureScheme::Ecdsa,
+            Self::Ed25519 => SignatureScheme::Ed25519,
+            Self::SshEd25519 => SignatureScheme::SshEd25519,
+            Self::SshDsa => SignatureScheme::SshDsa,
+            Self::SshEcdsaP256 => SignatureScheme::SshEcdsaP256,
+            Self::SshEcdsaP384 => SignatureScheme::SshEcdsaP384,
+            Self::Mldsa44 => SignatureScheme::MLDSA44,
+            Self::Mldsa65 => SignatureScheme::MLDSA65,
+            Self::Mldsa87 => SignatureScheme::MLDSA87,
+        }
+    }
+}
 
-        let private_keys = PrivateKeys::with_keys(
-            signing_private_key,
-            encapsulation_private_key,
-        );
-        let public_keys = bc_components::PublicKeys::new(
-            signing_public_key,
-            encapsulation_public_key,
-        );
+impl EncapsulationSchemeArg {
+    fn scheme(self) -> EncapsulationScheme {
+        match self {
+            Self::X25519 => EncapsulationScheme::X25519,
+            Self::Mlkem512 => EncapsulationScheme::MLKEM512,
+            Self::Mlkem768 => EncapsulationScheme::MLKEM768,
+            Self::Mlkem1024 => EncapsulationScheme::MLKEM1024,
+        }
+    }
+}
 
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let (private_keys, public_keys) =
+            generate_keypairs(&self.signing.scheme(), self.encryption.scheme());
         Ok(format!(
             "{} {}",
             private_keys.ur_string(),
@@ -100,3 +88,19 @@ impl crate::Exec for CommandArgs {
         ))
     }
 }
+
+/// Returns new random private keys and their public keys, using the given
+/// signing and encapsulation schemes.
+pub fn generate_keypairs(
+    signing: &SignatureScheme,
+    encryption: EncapsulationScheme,
+) -> (PrivateKeys, PublicKeys) {
+    let (signing_private_key, signing_public_key) = signing.keypair();
+    let (encapsulation_private_key, encapsulation_public_key) =
+        encryption.keypair();
+    let private_keys =
+        PrivateKeys::with_keys(signing_private_key, encapsulation_private_key);
+    let public_keys =
+        PublicKeys::new(signing_public_key, encapsulation_public_key);
+    (private_keys, public_keys)
+}
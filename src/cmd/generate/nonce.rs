@@ -10,7 +10,10 @@ pub struct CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let nonce = Nonce::new();
+        let nonce = generate_nonce();
         Ok(nonce.ur_string())
     }
 }
+
+/// Returns a new random nonce.
+pub fn generate_nonce() -> Nonce { Nonce::new() }
@@ -1,9 +1,12 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use bc_components::{PrivateKeyBase, PrivateKeys, SignatureScheme};
 use bc_envelope::prelude::*;
 use clap::{Args, ValueEnum};
 use dcbor::prelude::Date;
 // use ssh_key::HashAlg; // Disabled due to RSA being unsupported
 
+use crate::Error;
+
 /// Supported signature schemes for private key generation.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 #[value(rename_all = "kebab-case")]
@@ -45,62 +48,90 @@ pub struct CommandArgs {
     #[arg(long, default_value = "schnorr")]
     signing: SigningSchemeArg,
 
-    /// The encapsulation scheme to use for the encryption key.
+    /// The encapsulation scheme to use for the encryption key. For ML-KEM,
+    /// whose public keys can't be derived from the private keys, use
+    /// `generate keypairs --encryption mlkem512|mlkem768|mlkem1024`.
     #[arg(long, default_value = "x25519")]
     encryption: EncapsulationSchemeArg,
 }
 
+impl SigningSchemeArg {
+    fn scheme(self) -> SignatureScheme {
+        match self {
+            Self::Schnorr => SignatureScheme::Schnorr,
+            Self::Ecdsa => SignatureScheme::Ecdsa,
+            Self::Ed25519 => SignatureScheme::Ed25519,
+            Self::SshEd25519 => SignatureScheme::SshEd25519,
+            Self::SshDsa => SignatureScheme::SshDsa,
+            Self::SshEcdsaP256 => SignatureScheme::SshEcdsaP256,
+            Self::SshEcdsaP384 => SignatureScheme::SshEcdsaP384,
+        }
+    }
+}
+
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let private_key_base = if let Some(input_ur) = &self.input {
             parse_input(input_ur)?
         } else {
-            bc_components::PrivateKeyBase::new()
-        };
-
-        let private_keys = match self.signing {
-            SigningSchemeArg::Schnorr => {
-                private_key_base.schnorr_private_keys()
-            }
-            SigningSchemeArg::Ecdsa => private_key_base.ecdsa_private_keys(),
-            SigningSchemeArg::Ed25519 => bc_components::PrivateKeys::with_keys(
-                private_key_base.ed25519_signing_private_key(),
-                bc_components::EncapsulationPrivateKey::X25519(
-                    private_key_base.x25519_private_key(),
-                ),
-            ),
-            SigningSchemeArg::SshEd25519 => private_key_base
-                .ssh_private_keys(ssh_key::Algorithm::Ed25519, "")?,
-            SigningSchemeArg::SshDsa => private_key_base
-                .ssh_private_keys(ssh_key::Algorithm::Dsa, "")?,
-            SigningSchemeArg::SshEcdsaP256 => private_key_base
-                .ssh_private_keys(
-                    ssh_key::Algorithm::Ecdsa {
-                        curve: ssh_key::EcdsaCurve::NistP256,
-                    },
-                    "",
-                )?,
-            SigningSchemeArg::SshEcdsaP384 => private_key_base
-                .ssh_private_keys(
-                    ssh_key::Algorithm::Ecdsa {
-                        curve: ssh_key::EcdsaCurve::NistP384,
-                    },
-                    "",
-                )?,
-            // Disabled due to tests not working correctly for undiagnosed
-            // reasons. SigningSchemeArg::SshRsaSha256 =>
-            // private_key_base
-            //     .ssh_private_keys(ssh_key::Algorithm::Rsa { hash:
-            // Some(HashAlg::Sha256) }, "")?,
-            // SigningSchemeArg::SshRsaSha512 => private_key_base
-            //     .ssh_private_keys(ssh_key::Algorithm::Rsa { hash:
-            // Some(HashAlg::Sha512) }, "")?,
+            PrivateKeyBase::new()
         };
-
+        let private_keys =
+            derive_private_keys(&private_key_base, &self.signing.scheme())?;
         Ok(private_keys.ur_string())
     }
 }
 
+/// Derives private keys for the `signing` scheme from `private_key_base`.
+///
+/// Schemes whose keys can't be derived, such as ML-DSA, fail with
+/// [`Error::Usage`].
+pub fn derive_private_keys(
+    private_key_base: &PrivateKeyBase,
+    signing: &SignatureScheme,
+) -> Result<PrivateKeys> {
+    let private_keys = match signing {
+        SignatureScheme::Schnorr => private_key_base.schnorr_private_keys(),
+        SignatureScheme::Ecdsa => private_key_base.ecdsa_private_keys(),
+        SignatureScheme::Ed25519 => PrivateKeys::with_keys(
+            private_key_base.ed25519_signing_private_key(),
+            bc_components::EncapsulationPrivateKey::X25519(
+                private_key_base.x25519_private_key(),
+            ),
+        ),
+        SignatureScheme::SshEd25519 => private_key_base
+            .ssh_private_keys(ssh_key::Algorithm::Ed25519, "")?,
+        SignatureScheme::SshDsa => {
+            private_key_base.ssh_private_keys(ssh_key::Algorithm::Dsa, "")?
+        }
+        SignatureScheme::SshEcdsaP256 => private_key_base.ssh_private_keys(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP256,
+            },
+            "",
+        )?,
+        SignatureScheme::SshEcdsaP384 => private_key_base.ssh_private_keys(
+            ssh_key::Algorithm::Ecdsa {
+                curve: ssh_key::EcdsaCurve::NistP384,
+            },
+            "",
+        )?,
+        // Disabled due to tests not working correctly for undiagnosed
+        // reasons. SigningSchemeArg::SshRsaSha256 =>
+        // private_key_base
+        //     .ssh_private_keys(ssh_key::Algorithm::Rsa { hash:
+        // Some(HashAlg::Sha256) }, "")?,
+        // SigningSchemeArg::SshRsaSha512 => private_key_base
+        //     .ssh_private_keys(ssh_key::Algorithm::Rsa { hash:
+        // Some(HashAlg::Sha512) }, "")?,
+        _ => bail!(Error::Usage(format!(
+            "private keys for {:?} can't be derived",
+            signing
+        ))),
+    };
+    Ok(private_keys)
+}
+
 fn parse_input(input: &str) -> Result<bc_components::PrivateKeyBase> {
     // Try parsing as PrivateKeyBase first
     if let Ok(private_key_base) =
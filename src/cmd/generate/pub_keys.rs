@@ -1,7 +1,12 @@
 use anyhow::{Result, bail};
+use bc_components::{
+    PrivateKeys, PublicKeys, SigningPrivateKey, SigningPublicKey,
+};
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use crate::{Error, read_command_line};
+
 /// Convert private keys to public keys.
 ///
 /// Takes a ur:crypto-prvkeys or ur:signing-private-key and converts it to
@@ -21,14 +26,13 @@ pub struct CommandArgs {
 
 impl CommandArgs {
     fn read_prv_keys(&self) -> Result<String> {
-        let mut ur_string = String::new();
-        if self.prv_keys.is_none() {
-            std::io::stdin().read_line(&mut ur_string)?;
+        let ur_string = if self.prv_keys.is_none() {
+            read_command_line()?
         } else {
-            ur_string = self.prv_keys.as_ref().unwrap().to_string();
-        }
+            self.prv_keys.as_ref().unwrap().to_string()
+        };
         if ur_string.is_empty() {
-            bail!("No private keys provided");
+            bail!(Error::MissingInput("No private keys provided".to_string()));
         }
         Ok(ur_string.trim().to_string())
     }
@@ -39,43 +43,13 @@ impl crate::Exec for CommandArgs {
         let ur_string = self.read_prv_keys()?;
 
         // Try to parse as PrivateKeys first
-        if let Ok(private_keys) =
-            bc_components::PrivateKeys::from_ur_string(&ur_string)
-        {
-            let mut public_keys = private_keys.public_keys()?;
-
-            // If a comment is provided and the signing key is SSH, update the
-            // comment
-            if !self.comment.is_empty()
-                && let bc_components::SigningPublicKey::SSH(ssh_key) =
-                    public_keys.signing_public_key()
-            {
-                // Create a new SSH key with the updated comment
-                let mut new_ssh_key = ssh_key.clone();
-                new_ssh_key.set_comment(&self.comment);
-                public_keys = bc_components::PublicKeys::new(
-                    bc_components::SigningPublicKey::SSH(new_ssh_key),
-                    public_keys.enapsulation_public_key().clone(),
-                );
-            }
-
-            Ok(public_keys.ur_string())
+        if let Ok(private_keys) = PrivateKeys::from_ur_string(&ur_string) {
+            Ok(public_keys(&private_keys, &self.comment)?.ur_string())
         } else if let Ok(signing_private_key) =
-            bc_components::SigningPrivateKey::from_ur_string(&ur_string)
+            SigningPrivateKey::from_ur_string(&ur_string)
         {
-            // Try to derive the public key from the signing private key
-            let mut signing_public_key = signing_private_key.public_key()?;
-
-            // If a comment is provided and the signing key is SSH, update the
-            // comment
-            if !self.comment.is_empty()
-                && let bc_components::SigningPublicKey::SSH(ref mut ssh_key) =
-                    signing_public_key
-            {
-                ssh_key.set_comment(&self.comment);
-            }
-
-            Ok(signing_public_key.ur_string())
+            Ok(signing_public_key(&signing_private_key, &self.comment)?
+                .ur_string())
         } else {
             bail!(
                 "invalid private key: must be ur:crypto-prvkeys or ur:signing-private-key"
@@ -83,3 +57,45 @@ impl crate::Exec for CommandArgs {
         }
     }
 }
+
+/// Returns the public keys of `private_keys`, giving an SSH signing key the
+/// `comment` if it is not empty.
+pub fn public_keys(
+    private_keys: &PrivateKeys,
+    comment: &str,
+) -> Result<PublicKeys> {
+    let mut public_keys = private_keys.public_keys()?;
+
+    // If a comment is provided and the signing key is SSH, update the
+    // comment
+    if !comment.is_empty()
+        && let SigningPublicKey::SSH(ssh_key) = public_keys.signing_public_key()
+    {
+        // Create a new SSH key with the updated comment
+        let mut new_ssh_key = ssh_key.clone();
+        new_ssh_key.set_comment(comment);
+        public_keys = PublicKeys::new(
+            SigningPublicKey::SSH(new_ssh_key),
+            public_keys.enapsulation_public_key().clone(),
+        );
+    }
+    Ok(public_keys)
+}
+
+/// Returns the public key of `signing_private_key`, giving an SSH key the
+/// `comment` if it is not empty.
+pub fn signing_public_key(
+    signing_private_key: &SigningPrivateKey,
+    comment: &str,
+) -> Result<SigningPublicKey> {
+    let mut signing_public_key = signing_private_key.public_key()?;
+
+    // If a comment is provided and the signing key is SSH, update the
+    // comment
+    if !comment.is_empty()
+        && let SigningPublicKey::SSH(ref mut ssh_key) = signing_public_key
+    {
+        ssh_key.set_comment(comment);
+    }
+    Ok(signing_public_key)
+}
@@ -22,15 +22,20 @@ impl crate::Exec for CommandArgs {
             let bytes = hex::decode(hex)?;
             seed = bc_components::Seed::new_opt(bytes, None, None, None)?;
         } else {
-            let count = self.count.unwrap();
-            if count < bc_components::Seed::MIN_SEED_LENGTH {
-                bail!("Seed length must be at least 16 bytes");
-            }
-            if count > 256 {
-                bail!("Seed length must be at most 256 bytes");
-            }
-            seed = bc_components::Seed::new_with_len(count)?;
+            seed = generate_seed(self.count.unwrap())?;
         }
         Ok(seed.ur_string())
     }
 }
+
+/// Returns a new random seed of `count` bytes, which must be in the range
+/// 16..=256.
+pub fn generate_seed(count: usize) -> Result<bc_components::Seed> {
+    if count < bc_components::Seed::MIN_SEED_LENGTH {
+        bail!("Seed length must be at least 16 bytes");
+    }
+    if count > 256 {
+        bail!("Seed length must be at most 256 bytes");
+    }
+    Ok(bc_components::Seed::new_with_len(count)?)
+}
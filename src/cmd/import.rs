@@ -8,7 +8,7 @@ use ssh_key::{
 };
 
 use super::{ASKPASS_HELP, ASKPASS_LONG_HELP};
-use crate::{read_argument, read_password};
+use crate::{Error, read_command_argument, read_password};
 
 /// Import the given object to UR form.
 #[derive(Debug, Args)]
@@ -37,25 +37,25 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let object = read_argument(self.object.as_deref())?;
+        let object = read_command_argument(self.object.as_deref())?;
         let result = if let Ok(ssh_private_key) =
             SSHPrivateKey::from_openssh(&object)
         {
-            if ssh_private_key.is_encrypted() {
-                let password = read_password(
+            let password = if ssh_private_key.is_encrypted() {
+                Some(read_password(
                     "Key decryption password: ",
                     self.password.as_deref(),
                     self.askpass,
-                )?;
-                SigningPrivateKey::new_ssh(ssh_private_key.decrypt(password)?)
-                    .ur_string()
+                )?)
             } else {
-                SigningPrivateKey::new_ssh(ssh_private_key).ur_string()
-            }
+                None
+            };
+            import_private_key(&ssh_private_key, password.as_deref())?
+                .ur_string()
         } else if let Ok(ssh_public_key) = SSHPublicKey::from_openssh(&object) {
-            SigningPublicKey::from_ssh(ssh_public_key).ur_string()
+            import_public_key(&ssh_public_key).ur_string()
         } else if let Ok(ssh_signature) = SSHSignature::from_pem(&object) {
-            Signature::from_ssh(ssh_signature).ur_string()
+            import_signature(&ssh_signature).ur_string()
         } else {
             bail!(
                 "Invalid object for import. Supported types are SSH private key, public key, and signature."
@@ -64,3 +64,32 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Imports an SSH private key, decrypting it with `password` if it is
+/// encrypted.
+pub fn import_private_key(
+    ssh_private_key: &SSHPrivateKey,
+    password: Option<&str>,
+) -> Result<SigningPrivateKey> {
+    if !ssh_private_key.is_encrypted() {
+        return Ok(SigningPrivateKey::new_ssh(ssh_private_key.clone()));
+    }
+    let Some(password) = password else {
+        bail!(Error::Usage(
+            "a password is needed to decrypt the SSH private key".to_string()
+        ));
+    };
+    Ok(SigningPrivateKey::new_ssh(
+        ssh_private_key.decrypt(password)?,
+    ))
+}
+
+/// Imports an SSH public key.
+pub fn import_public_key(ssh_public_key: &SSHPublicKey) -> SigningPublicKey {
+    SigningPublicKey::from_ssh(ssh_public_key.clone())
+}
+
+/// Imports an SSH signature.
+pub fn import_signature(ssh_signature: &SSHSignature) -> Signature {
+    Signature::from_ssh(ssh_signature.clone())
+}
@@ -1,13 +1,18 @@
 use anyhow::{Result, bail};
 use bc_components::{
-    PrivateKeyBase, PublicKeys, Seed, Signature, SigningPrivateKey,
-    SigningPublicKey,
+    MLDSA, PrivateKeyBase, PrivateKeys, PublicKeys, Seed, Signature,
+    SigningPrivateKey, SigningPublicKey,
 };
 use bc_envelope::prelude::*;
 use clap::Args;
+use serde_json::{Map, Value};
 use ssh_key::{HashAlg, public::KeyData};
 
-use crate::{envelope_from_ur, read_argument};
+use crate::{
+    cmd::decrypt::{describe_locks, encapsulation_scheme_name, envelope_locks},
+    envelope_from_ur, is_json_output, json_string, read_command_argument,
+    tag_definition_for_name,
+};
 
 /// Provide type and other information about the object.
 #[derive(Debug, Args)]
@@ -20,169 +25,286 @@ pub struct CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let mut result = Vec::<String>::new();
+        let object = read_command_argument(self.object.as_deref())?;
+        render(&object_info(&object)?)
+    }
+}
 
-        fn add(
-            result: &mut Vec<String>,
-            field: impl Into<String>,
-            value: impl Into<String>,
-        ) {
-            result.push(format!("{}: {}", field.into(), value.into()));
-        }
+/// Describes `object`, a UR, as `(field, value)` pairs such as
+/// `("Format", "ur:envelope")`, in the order `info` prints them.
+pub fn object_info(object: &str) -> Result<Vec<(String, String)>> {
+    let mut result = Vec::<(String, String)>::new();
+    let object = object.trim();
+    if object.strip_prefix("ur:").is_some() {
+        let ur = UR::from_ur_string(object)?;
+        let ur_type = ur.ur_type_str();
+        let cbor_size = ur.cbor().to_cbor_data().len();
+        add(&mut result, "Format", format!("ur:{}", ur_type));
+        add(&mut result, "CBOR Size", cbor_size.to_string());
 
-        fn add_public_key_info(result: &mut Vec<String>, public_key: &KeyData) {
-            add(result, "Algorithm", format!("{}", public_key.algorithm()));
-            let fingerprint = public_key.fingerprint(HashAlg::default());
-            add(result, "Fingerprint", format!("{}", &fingerprint));
-            let algorithm_str = public_key.algorithm().to_string();
-            let algorithm_formatted =
-                algorithm_str.strip_prefix("ssh-").unwrap_or(&algorithm_str);
-            result.push(fingerprint.to_randomart(
-                &format!("[{}]", algorithm_formatted).to_uppercase(),
-            ));
+        if let Ok(envelope) = envelope_from_ur(&ur) {
+            add(&mut result, "Description", "Gordian Envelope");
+            let locks = envelope_locks(&envelope).unwrap_or_default();
+            if !locks.is_empty() {
+                add(&mut result, "Locks", describe_locks(&locks));
+            }
+            return Ok(result);
         }
 
-        let object = read_argument(self.object.as_deref())?;
-        let object = object.trim().to_string();
-        if object.strip_prefix("ur:").is_some() {
-            let ur = UR::from_ur_string(&object)?;
-            let ur_type = ur.ur_type_str();
-            let cbor_size = ur.cbor().to_cbor_data().len();
-            add(&mut result, "Format", format!("ur:{}", ur_type));
-            add(&mut result, "CBOR Size", cbor_size.to_string());
-
-            if envelope_from_ur(&ur).is_ok() {
-                add(&mut result, "Description", "Gordian Envelope");
-                return Ok(result.join("\n"));
+        match ur_type {
+            "seed" => {
+                let _seed = Seed::from_ur(&ur)?;
+                add(&mut result, "Description", "Cryptographic Seed");
             }
-
-            match ur_type {
-                "seed" => {
-                    let _seed = Seed::from_ur(&ur)?;
-                    add(&mut result, "Description", "Cryptographic Seed");
-                }
-                "prvkeys" => {
-                    let _private_key_base = PrivateKeyBase::from_ur(&ur)?;
-                    add(&mut result, "Description", "Private Key Base");
-                }
-                "pubkeys" => {
-                    let _public_keys = PublicKeys::from_ur(&ur)?;
-                    add(&mut result, "Description", "Public Keys");
-                }
-                "signing-private-key" => {
-                    let signing_private_key = SigningPrivateKey::from_ur(&ur)?;
-                    match signing_private_key {
-                        SigningPrivateKey::Schnorr(_) => add(
-                            &mut result,
-                            "Description",
-                            "Schnorr Signing Private Key",
-                        ),
-                        SigningPrivateKey::ECDSA(_) => add(
-                            &mut result,
-                            "Description",
-                            "ECDSA Signing Private Key",
-                        ),
-                        SigningPrivateKey::Ed25519(_) => add(
-                            &mut result,
-                            "Description",
-                            "Ed25519 Signing Private Key",
-                        ),
-                        SigningPrivateKey::MLDSA(mldsa_key) => add(
-                            &mut result,
-                            "Description",
-                            format!(
-                                "{:?} Signing Private Key",
-                                mldsa_key.level()
-                            ),
-                        ),
-                        SigningPrivateKey::SSH(ssh_key) => {
-                            add(
-                                &mut result,
-                                "Description",
-                                "SSH Signing Private Key",
-                            );
-                            add_public_key_info(
-                                &mut result,
-                                ssh_key.public_key().key_data(),
-                            );
-                        }
-                    };
-                }
-                "signing-public-key" => {
-                    let signing_public_key = SigningPublicKey::from_ur(&ur)?;
-                    match signing_public_key {
-                        SigningPublicKey::Schnorr(_) => add(
+            "prvkeys" => {
+                let _private_key_base = PrivateKeyBase::from_ur(&ur)?;
+                add(&mut result, "Description", "Private Key Base");
+            }
+            "pubkeys" => {
+                let _public_keys = PublicKeys::from_ur(&ur)?;
+                add(&mut result, "Description", "Public Keys");
+            }
+            "crypto-prvkeys" => {
+                let private_keys = PrivateKeys::from_ur(&ur)?;
+                add(&mut result, "Description", "Private Keys");
+                add(
+                    &mut result,
+                    "Signing",
+                    signing_private_key_scheme(
+                        private_keys.signing_private_key(),
+                    ),
+                );
+                add(
+                    &mut result,
+                    "Encapsulation",
+                    encapsulation_scheme_name(
+                        private_keys
+                            .enapsulation_private_key()
+                            .encapsulation_scheme(),
+                    ),
+                );
+            }
+            "crypto-pubkeys" => {
+                let public_keys = PublicKeys::from_ur(&ur)?;
+                add(&mut result, "Description", "Public Keys");
+                add(
+                    &mut result,
+                    "Signing",
+                    signing_public_key_scheme(public_keys.signing_public_key()),
+                );
+                add(
+                    &mut result,
+                    "Encapsulation",
+                    encapsulation_scheme_name(
+                        public_keys
+                            .enapsulation_public_key()
+                            .encapsulation_scheme(),
+                    ),
+                );
+            }
+            "signing-private-key" => {
+                let signing_private_key = SigningPrivateKey::from_ur(&ur)?;
+                match signing_private_key {
+                    SigningPrivateKey::Schnorr(_) => add(
+                        &mut result,
+                        "Description",
+                        "Schnorr Signing Private Key",
+                    ),
+                    SigningPrivateKey::ECDSA(_) => add(
+                        &mut result,
+                        "Description",
+                        "ECDSA Signing Private Key",
+                    ),
+                    SigningPrivateKey::Ed25519(_) => add(
+                        &mut result,
+                        "Description",
+                        "Ed25519 Signing Private Key",
+                    ),
+                    SigningPrivateKey::MLDSA(mldsa_key) => add(
+                        &mut result,
+                        "Description",
+                        format!("{:?} Signing Private Key", mldsa_key.level()),
+                    ),
+                    SigningPrivateKey::SSH(ssh_key) => {
+                        add(
                             &mut result,
                             "Description",
-                            "Schnorr Signing Public Key",
-                        ),
-                        SigningPublicKey::ECDSA(_) => add(
+                            "SSH Signing Private Key",
+                        );
+                        add_public_key_info(
                             &mut result,
-                            "Description",
-                            "ECDSA Signing Public Key",
-                        ),
-                        SigningPublicKey::Ed25519(_) => add(
+                            ssh_key.public_key().key_data(),
+                        );
+                    }
+                };
+            }
+            "signing-public-key" => {
+                let signing_public_key = SigningPublicKey::from_ur(&ur)?;
+                match signing_public_key {
+                    SigningPublicKey::Schnorr(_) => add(
+                        &mut result,
+                        "Description",
+                        "Schnorr Signing Public Key",
+                    ),
+                    SigningPublicKey::ECDSA(_) => add(
+                        &mut result,
+                        "Description",
+                        "ECDSA Signing Public Key",
+                    ),
+                    SigningPublicKey::Ed25519(_) => add(
+                        &mut result,
+                        "Description",
+                        "Ed25519 Signing Public Key",
+                    ),
+                    SigningPublicKey::MLDSA(mldsa_key) => add(
+                        &mut result,
+                        "Description",
+                        format!("{:?} Signing Public Key", mldsa_key.level()),
+                    ),
+                    SigningPublicKey::SSH(ssh_key) => {
+                        add(
                             &mut result,
                             "Description",
-                            "Ed25519 Signing Public Key",
-                        ),
-                        SigningPublicKey::MLDSA(mldsa_key) => add(
+                            "SSH Signing Public Key",
+                        );
+                        add_public_key_info(&mut result, ssh_key.key_data());
+                    }
+                };
+            }
+            "signature" => {
+                let signature = Signature::from_ur(&ur)?;
+                match signature {
+                    Signature::Schnorr { .. } => {
+                        add(&mut result, "Description", "Schnorr Signature")
+                    }
+                    Signature::ECDSA(_) => {
+                        add(&mut result, "Description", "ECDSA Signature")
+                    }
+                    Signature::Ed25519(_) => {
+                        add(&mut result, "Description", "Ed25519 Signature")
+                    }
+                    Signature::MLDSA(_) => {
+                        add(&mut result, "Description", "MLDSA Signature")
+                    }
+                    Signature::SSH(ssh_sig) => {
+                        add(&mut result, "Description", "SSH Signature");
+                        add(
                             &mut result,
-                            "Description",
-                            format!(
-                                "{:?} Signing Public Key",
-                                mldsa_key.level()
-                            ),
-                        ),
-                        SigningPublicKey::SSH(ssh_key) => {
-                            add(
-                                &mut result,
-                                "Description",
-                                "SSH Signing Public Key",
-                            );
-                            add_public_key_info(
-                                &mut result,
-                                ssh_key.key_data(),
-                            );
-                        }
-                    };
-                }
-                "signature" => {
-                    let signature = Signature::from_ur(&ur)?;
-                    match signature {
-                        Signature::Schnorr { .. } => {
-                            add(&mut result, "Description", "Schnorr Signature")
-                        }
-                        Signature::ECDSA(_) => {
-                            add(&mut result, "Description", "ECDSA Signature")
-                        }
-                        Signature::Ed25519(_) => {
-                            add(&mut result, "Description", "Ed25519 Signature")
-                        }
-                        Signature::MLDSA(_) => {
-                            add(&mut result, "Description", "MLDSA Signature")
-                        }
-                        Signature::SSH(ssh_sig) => {
-                            add(&mut result, "Description", "SSH Signature");
-                            add(
-                                &mut result,
-                                "Namespace",
-                                ssh_sig.namespace().to_string(),
-                            );
-                            add_public_key_info(
-                                &mut result,
-                                ssh_sig.public_key(),
-                            );
-                        }
-                    };
-                }
-                _ => {
+                            "Namespace",
+                            ssh_sig.namespace().to_string(),
+                        );
+                        add_public_key_info(&mut result, ssh_sig.public_key());
+                    }
+                };
+            }
+            _ => {
+                let Some(definition) = tag_definition_for_name(ur_type) else {
                     bail!("Unknown UR type: {}", ur_type);
-                }
+                };
+                add(
+                    &mut result,
+                    "Description",
+                    definition
+                        .description
+                        .unwrap_or_else(|| "Tagged CBOR".to_string()),
+                );
+                add(&mut result, "CBOR Tag", definition.value.to_string());
             }
-        } else {
-            bail!("Unknown object.");
         }
-        Ok(result.join("\n"))
+    } else {
+        bail!("Unknown object.");
     }
+    Ok(result)
+}
+
+fn add(
+    result: &mut Vec<(String, String)>,
+    field: impl Into<String>,
+    value: impl Into<String>,
+) {
+    result.push((field.into(), value.into()));
+}
+
+fn add_public_key_info(
+    result: &mut Vec<(String, String)>,
+    public_key: &KeyData,
+) {
+    add(result, "Algorithm", format!("{}", public_key.algorithm()));
+    let fingerprint = public_key.fingerprint(HashAlg::default());
+    add(result, "Fingerprint", format!("{}", &fingerprint));
+    let algorithm_str = public_key.algorithm().to_string();
+    let algorithm_formatted =
+        algorithm_str.strip_prefix("ssh-").unwrap_or(&algorithm_str);
+    add(
+        result,
+        RANDOMART,
+        fingerprint
+            .to_randomart(&format!("[{}]", algorithm_formatted).to_uppercase()),
+    );
+}
+
+/// The name of a signing private key's scheme, such as `Schnorr`.
+fn signing_private_key_scheme(key: &SigningPrivateKey) -> String {
+    match key {
+        SigningPrivateKey::Schnorr(_) => "Schnorr".to_string(),
+        SigningPrivateKey::ECDSA(_) => "ECDSA".to_string(),
+        SigningPrivateKey::Ed25519(_) => "Ed25519".to_string(),
+        SigningPrivateKey::MLDSA(key) => mldsa_name(key.level()).to_string(),
+        SigningPrivateKey::SSH(key) => key.algorithm().to_string(),
+    }
+}
+
+/// The name of a signing public key's scheme, such as `Schnorr`.
+fn signing_public_key_scheme(key: &SigningPublicKey) -> String {
+    match key {
+        SigningPublicKey::Schnorr(_) => "Schnorr".to_string(),
+        SigningPublicKey::ECDSA(_) => "ECDSA".to_string(),
+        SigningPublicKey::Ed25519(_) => "Ed25519".to_string(),
+        SigningPublicKey::MLDSA(key) => mldsa_name(key.level()).to_string(),
+        SigningPublicKey::SSH(key) => key.algorithm().to_string(),
+    }
+}
+
+/// The name of an ML-DSA security level, such as `MLDSA65`.
+fn mldsa_name(level: MLDSA) -> &'static str {
+    match level {
+        MLDSA::MLDSA44 => "MLDSA44",
+        MLDSA::MLDSA65 => "MLDSA65",
+        MLDSA::MLDSA87 => "MLDSA87",
+    }
+}
+
+/// The field holding an SSH key's randomart, which is shown without a label.
+const RANDOMART: &str = "Randomart";
+
+/// Renders the collected fields as `Field: value` lines, or with `--json` as
+/// an object whose keys are the field names in snake case.
+fn render(fields: &[(String, String)]) -> Result<String> {
+    if is_json_output() {
+        let object = fields
+            .iter()
+            .map(|(field, value)| {
+                let key = field.to_lowercase().replace(' ', "_");
+                let value = match value.parse::<u64>() {
+                    Ok(n) if key == "cbor_size" || key == "cbor_tag" => {
+                        Value::from(n)
+                    }
+                    _ => Value::from(value.as_str()),
+                };
+                (key, value)
+            })
+            .collect::<Map<_, _>>();
+        return json_string(&Value::Object(object));
+    }
+    Ok(fields
+        .iter()
+        .map(|(field, value)| {
+            if field == RANDOMART {
+                value.clone()
+            } else {
+                format!("{}: {}", field, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
 }
@@ -0,0 +1,161 @@
+use anyhow::{Result, bail};
+use bc_components::{KeyDerivationMethod, PublicKeys};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, Error,
+    cmd::{
+        decrypt::{UnlockArgs, UnlockMethod, unlock_content_key_with_any},
+        encrypt::PasswordDerivationType,
+    },
+    read_password,
+};
+
+/// Add locks to the content key.
+///
+/// The content key is first unlocked with any of the existing credentials,
+/// then locked again with each new recipient, password, or SSH identity.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    unlock_args: UnlockArgs,
+
+    /// A recipient to add. (ur:crypto-pubkeys) May be repeated.
+    #[arg(long)]
+    new_recipient: Vec<String>,
+
+    /// A password to add.
+    ///
+    /// If not provided, will be prompted.
+    #[arg(long, num_args(0..=1))]
+    new_password: Option<Option<String>>,
+
+    /// The password-based key derivation algorithm to use with the
+    /// `--new-password` option.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = PasswordDerivationType::Argon2id,
+        requires = "new_password"
+    )]
+    new_password_derivation: PasswordDerivationType,
+
+    /// An SSH agent key identity to add.
+    ///
+    /// If provided but empty, the only Ed25519 key in the SSH agent is used.
+    #[arg(long)]
+    new_ssh_id: Option<String>,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        if self.new_recipient.is_empty()
+            && self.new_password.is_none()
+            && self.new_ssh_id.is_none()
+        {
+            bail!(no_new_lock());
+        }
+        let recipients = self
+            .new_recipient
+            .iter()
+            .map(|s| PublicKeys::from_ur_string(s).map_err(anyhow::Error::from))
+            .collect::<Result<Vec<_>>>()?;
+
+        let envelope = self.read_envelope()?;
+        let methods = self.unlock_args.methods(&envelope)?;
+        let password = self
+            .new_password
+            .as_ref()
+            .map(|password| {
+                read_password("New password:", password.as_deref(), false)
+            })
+            .transpose()?;
+        let options = LocksAddOptions {
+            recipients,
+            password,
+            password_derivation: self.new_password_derivation.into(),
+            ssh_id: self.new_ssh_id.clone(),
+        };
+        Ok(locks_add(&envelope, &methods, &options)?.ur_string())
+    }
+}
+
+/// Options for [`locks_add`].
+#[derive(Debug, Clone)]
+pub struct LocksAddOptions {
+    /// Public keys to lock the content key to.
+    pub recipients: Vec<PublicKeys>,
+    /// A password to lock the content key with.
+    pub password: Option<String>,
+    /// The key derivation method used with `password`.
+    pub password_derivation: KeyDerivationMethod,
+    /// An SSH agent identity to lock the content key with. An empty string
+    /// selects the only Ed25519 key in the agent.
+    pub ssh_id: Option<String>,
+}
+
+impl Default for LocksAddOptions {
+    fn default() -> Self {
+        Self {
+            recipients: Vec::new(),
+            password: None,
+            password_derivation: KeyDerivationMethod::Argon2id,
+            ssh_id: None,
+        }
+    }
+}
+
+/// Unlocks the content key of `envelope` with any of `methods`, then locks it
+/// again with each of the new locks given in `options`.
+///
+/// Fails with [`Error::Usage`] if `options` has no new lock.
+pub fn locks_add(
+    envelope: &Envelope,
+    methods: &[UnlockMethod],
+    options: &LocksAddOptions,
+) -> Result<Envelope> {
+    if options.recipients.is_empty()
+        && options.password.is_none()
+        && options.ssh_id.is_none()
+    {
+        bail!(no_new_lock());
+    }
+    let content_key = unlock_content_key_with_any(envelope, methods)?;
+
+    let mut envelope = envelope.clone();
+    for recipient in &options.recipients {
+        envelope = envelope.add_recipient(recipient, &content_key);
+    }
+    if let Some(password) = &options.password {
+        envelope = envelope.add_secret(
+            options.password_derivation,
+            password.as_bytes(),
+            &content_key,
+        )?;
+    }
+    if let Some(ssh_id) = &options.ssh_id {
+        envelope = envelope.add_secret(
+            KeyDerivationMethod::SSHAgent,
+            ssh_id.as_bytes(),
+            &content_key,
+        )?;
+    }
+    Ok(envelope)
+}
+
+fn no_new_lock() -> Error {
+    Error::Usage(
+        "no new lock: provide --new-recipient, --new-password, or \
+         --new-ssh-id"
+            .to_string(),
+    )
+}
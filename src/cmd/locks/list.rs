@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::Args;
+use serde_json::Value;
+
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, cmd::decrypt::envelope_locks,
+    is_json_output, json_string,
+};
+
+/// List the locks on the content key, one per line with its index.
+///
+/// The index of a lock is used by `locks remove --index`. Recipients are
+/// shown with their encapsulation scheme, and secrets with their key
+/// derivation method.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let envelope = self.read_envelope()?;
+        let locks = envelope_locks(&envelope)?;
+        if is_json_output() {
+            let locks: Vec<Value> = locks
+                .iter()
+                .enumerate()
+                .map(|(index, lock)| {
+                    let mut json = lock.kind.to_json();
+                    json["index"] = index.into();
+                    json
+                })
+                .collect();
+            return json_string(&Value::Array(locks));
+        }
+        Ok(locks
+            .iter()
+            .enumerate()
+            .map(|(index, lock)| format!("{} {}", index, lock.kind))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
@@ -0,0 +1,34 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+/// List, add, or remove the locks on an encrypted envelope's content key.
+///
+/// Each lock is a `hasRecipient` or `hasSecret` assertion. Adding or removing
+/// a lock leaves the encrypted subject untouched.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: SubCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommands {
+    List(list::CommandArgs),
+    Add(add::CommandArgs),
+    Remove(remove::CommandArgs),
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        match &self.command {
+            SubCommands::List(args) => args.exec(),
+            SubCommands::Add(args) => args.exec(),
+            SubCommands::Remove(args) => args.exec(),
+        }
+    }
+}
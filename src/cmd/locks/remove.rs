@@ -0,0 +1,116 @@
+use anyhow::{Result, bail};
+use bc_components::{Decrypter, SealedMessage};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, Error,
+    cmd::decrypt::{Lock, LockKind, UnlockMethod, envelope_locks},
+};
+
+/// Remove a lock from the content key.
+///
+/// A recipient's lock can't be found from their public key, since the
+/// content key is sealed with an ephemeral key. Give the index shown by
+/// `locks list`, or the recipient's private key. The last lock can't be
+/// removed.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The index of the lock to remove, as shown by `locks list`.
+    #[arg(
+        long,
+        conflicts_with = "recipient",
+        required_unless_present = "recipient"
+    )]
+    index: Option<usize>,
+
+    /// The private key of the recipient whose lock to remove.
+    /// (ur:crypto-prvkey-base or ur:crypto-prvkeys)
+    #[arg(long, short)]
+    recipient: Option<String>,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let envelope = self.read_envelope()?;
+        let selector = if let Some(index) = self.index {
+            LockSelector::Index(index)
+        } else {
+            LockSelector::Recipient(UnlockMethod::parse_recipient(
+                self.recipient.as_deref().unwrap_or_default(),
+            )?)
+        };
+        Ok(locks_remove(&envelope, &selector)?.ur_string())
+    }
+}
+
+/// Which lock [`locks_remove`] removes.
+#[derive(Debug, Clone)]
+pub enum LockSelector {
+    /// The lock at this index in [`envelope_locks`].
+    Index(usize),
+    /// The lock that this recipient's private keys open.
+    Recipient(UnlockMethod),
+}
+
+/// Removes the lock chosen by `selector` from the content key of `envelope`.
+///
+/// The last lock can't be removed.
+pub fn locks_remove(
+    envelope: &Envelope,
+    selector: &LockSelector,
+) -> Result<Envelope> {
+    if !envelope.subject().is_encrypted() {
+        bail!(Error::Crypto("envelope subject is not encrypted".into()));
+    }
+    let locks = envelope_locks(envelope)?;
+    let lock = match selector {
+        LockSelector::Index(index) => locks.get(*index).ok_or_else(|| {
+            Error::NotFound(format!(
+                "no lock at index {}: the envelope has {} locks",
+                index,
+                locks.len()
+            ))
+        })?,
+        LockSelector::Recipient(recipient) => locks
+            .iter()
+            .find(|lock| opens(lock, recipient))
+            .ok_or_else(|| {
+                Error::Crypto(
+                    "the private key is not a recipient of this envelope"
+                        .to_string(),
+                )
+            })?,
+    };
+    if locks.len() == 1 {
+        bail!(Error::Usage(
+            "cannot remove the only lock on the content key".to_string()
+        ));
+    }
+    Ok(envelope.remove_assertion(lock.assertion.clone()))
+}
+
+/// Returns whether `recipient` can open `lock`.
+fn opens(lock: &Lock, recipient: &UnlockMethod) -> bool {
+    let decrypter: &dyn Decrypter = match recipient {
+        UnlockMethod::Recipient(keys) => keys,
+        UnlockMethod::RecipientBase(keys) => keys,
+        _ => return false,
+    };
+    matches!(lock.kind, LockKind::Recipient(_))
+        && lock
+            .assertion
+            .try_object()
+            .and_then(|object| object.extract_subject::<SealedMessage>())
+            .is_ok_and(|sealed_message| {
+                sealed_message.decrypt(decrypter).is_ok()
+            })
+}
@@ -1,8 +1,10 @@
 pub mod assertion;
 pub mod attachment;
 pub mod compress;
+pub mod convert;
 pub mod decompress;
 pub mod decrypt;
+pub mod diff;
 pub mod digest;
 pub mod elide;
 pub mod encrypt;
@@ -12,16 +14,28 @@ pub mod format;
 pub mod generate;
 pub mod import;
 pub mod info;
+pub mod locks;
+pub mod parse;
+pub mod patch;
 pub mod pattern;
 pub mod proof;
+pub mod run;
 pub mod salt;
+pub mod seal;
+pub mod shell;
 pub mod sign;
 pub mod sskr;
 pub mod subject;
+pub mod unseal;
 pub mod verify;
 pub mod walk;
 pub mod xid;
 
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::Exec;
+
 pub const ASKPASS_HELP: &str =
     "Prompt for the password using an external program.";
 pub const ASKPASS_LONG_HELP: &str = "If set, the password will be obtained by executing the program \
@@ -29,3 +43,79 @@ referenced by the SSH_ASKPASS environment variable. Attempts to fall \
 back to common locations for askpass helpers if the variable is not set. \
 If the program is not found, the password will be read from the \
 terminal.";
+
+#[derive(Debug, Subcommand)]
+#[doc(hidden)]
+pub enum MainCommands {
+    Assertion(assertion::CommandArgs),
+    Attachment(attachment::CommandArgs),
+    Compress(compress::CommandArgs),
+    Convert(convert::CommandArgs),
+    Decrypt(decrypt::CommandArgs),
+    Diff(diff::CommandArgs),
+    Digest(digest::CommandArgs),
+    Elide(elide::CommandArgs),
+    Encrypt(encrypt::CommandArgs),
+    Export(export::CommandArgs),
+    Extract(extract::CommandArgs),
+    Format(format::CommandArgs),
+    Generate(generate::CommandArgs),
+    Import(import::CommandArgs),
+    Info(info::CommandArgs),
+    Locks(locks::CommandArgs),
+    Match(pattern::CommandArgs),
+    Parse(parse::CommandArgs),
+    Patch(patch::CommandArgs),
+    Proof(proof::CommandArgs),
+    Run(run::CommandArgs),
+    Salt(salt::CommandArgs),
+    Seal(seal::CommandArgs),
+    Shell(shell::CommandArgs),
+    Sign(sign::CommandArgs),
+    Sskr(sskr::CommandArgs),
+    Subject(subject::CommandArgs),
+    Decompress(decompress::CommandArgs),
+    Unseal(unseal::CommandArgs),
+    Verify(verify::CommandArgs),
+    Walk(walk::CommandArgs),
+    Xid(xid::CommandArgs),
+}
+
+impl Exec for MainCommands {
+    fn exec(&self) -> Result<String> {
+        match self {
+            Self::Assertion(args) => args.exec(),
+            Self::Attachment(args) => args.exec(),
+            Self::Compress(args) => args.exec(),
+            Self::Convert(args) => args.exec(),
+            Self::Decrypt(args) => args.exec(),
+            Self::Diff(args) => args.exec(),
+            Self::Digest(args) => args.exec(),
+            Self::Elide(args) => args.exec(),
+            Self::Encrypt(args) => args.exec(),
+            Self::Export(args) => args.exec(),
+            Self::Extract(args) => args.exec(),
+            Self::Format(args) => args.exec(),
+            Self::Generate(args) => args.exec(),
+            Self::Import(args) => args.exec(),
+            Self::Info(args) => args.exec(),
+            Self::Locks(args) => args.exec(),
+            Self::Match(args) => args.exec(),
+            Self::Parse(args) => args.exec(),
+            Self::Patch(args) => args.exec(),
+            Self::Proof(args) => args.exec(),
+            Self::Run(args) => args.exec(),
+            Self::Salt(args) => args.exec(),
+            Self::Seal(args) => args.exec(),
+            Self::Shell(args) => args.exec(),
+            Self::Sign(args) => args.exec(),
+            Self::Sskr(args) => args.exec(),
+            Self::Subject(args) => args.exec(),
+            Self::Decompress(args) => args.exec(),
+            Self::Unseal(args) => args.exec(),
+            Self::Verify(args) => args.exec(),
+            Self::Walk(args) => args.exec(),
+            Self::Xid(args) => args.exec(),
+        }
+    }
+}
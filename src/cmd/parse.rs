@@ -0,0 +1,360 @@
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use crate::{
+    DataType, Error, parse_data_type_to_envelope, read_command_argument,
+};
+
+/// Create an envelope from envelope notation.
+///
+/// Reads the notation written by `format`, such as
+/// `"Alice" [ "knows": "Bob" ]`, and prints the envelope it describes.
+/// Braces wrap an envelope, and square brackets hold the assertions of a
+/// subject. Assertions may be separated by newlines or commas.
+///
+/// Leaves may be strings, numbers, `true`, `false`, `null`, known values in
+/// single quotes, dates (`2024-01-02` or `Date(2024-01-02)`), `ARID(HEX)`,
+/// `Digest(HEX)`, `URI(...)`, `UUID(...)`, `bignum(...)`, byte strings as
+/// `h'HEX'`, CBOR arrays, and `ur:` literals. A `ur:envelope` literal stands
+/// for the envelope itself. Braces that close on the same line and hold a
+/// CBOR map, such as `{1: 2, 3: 10}`, are read as a map, as `format` writes
+/// one.
+///
+/// `format --lossless` writes notation that reads back as the same envelope
+/// unless it has elided, encrypted, or compressed elements. The default
+/// `format` abbreviates some values, such as byte strings and ARIDs, and
+/// doesn't escape quotes in strings; a `"` that isn't escaped ends a string
+/// only when followed by whitespace, by one of `[]{}:,`, or by the end of
+/// the input.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The envelope notation. If not provided, it is read from stdin.
+    notation: Option<String>,
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let notation = read_command_argument(self.notation.as_deref())?;
+        Ok(parse_envelope_notation(&notation)?.ur_string())
+    }
+}
+
+/// Parses envelope notation, as written by `format`, into an envelope.
+pub fn parse_envelope_notation(notation: &str) -> Result<Envelope> {
+    let mut parser = Parser { source: notation, position: 0 };
+    let envelope = parser.parse_content()?;
+    parser.skip_whitespace();
+    if parser.position < notation.len() {
+        bail!(parser.error("unexpected text after the envelope"));
+    }
+    Ok(envelope)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str { &self.source[self.position..] }
+
+    fn peek(&self) -> Option<char> { self.rest().chars().next() }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `c` if it is the next character after any whitespace.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if !self.eat(c) {
+            bail!(self.error(&format!("expected '{}'", c)));
+        }
+        Ok(())
+    }
+
+    fn error(&self, message: &str) -> Error {
+        self.error_at(self.position, message)
+    }
+
+    /// Returns a parse error that gives the line and column of `position`.
+    fn error_at(&self, position: usize, message: &str) -> Error {
+        let before = &self.source[..position];
+        let line = before.matches('\n').count() + 1;
+        let column =
+            before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        Error::Parse(format!("line {}, column {}: {}", line, column, message))
+    }
+
+    /// Parses an envelope, or an assertion written as `predicate: object`.
+    fn parse_content(&mut self) -> Result<Envelope> {
+        let envelope = self.parse_envelope()?;
+        if self.eat(':') {
+            let object = self.parse_envelope()?;
+            return Ok(Envelope::new_assertion(envelope, object));
+        }
+        Ok(envelope)
+    }
+
+    /// Parses an element followed by an optional list of assertions.
+    fn parse_envelope(&mut self) -> Result<Envelope> {
+        let subject = self.parse_element()?;
+        if !self.eat('[') {
+            return Ok(subject);
+        }
+        let mut assertions = Vec::new();
+        while !self.eat(']') {
+            if self.peek().is_none() {
+                bail!(self.error("expected ']'"));
+            }
+            let predicate = self.parse_envelope()?;
+            self.expect(':')?;
+            let object = self.parse_envelope()?;
+            assertions.push(Envelope::new_assertion(predicate, object));
+            self.eat(',');
+        }
+        Ok(subject.add_assertion_envelopes(&assertions)?)
+    }
+
+    fn parse_element(&mut self) -> Result<Envelope> {
+        self.skip_whitespace();
+        let start = self.position;
+        let Some(c) = self.peek() else {
+            bail!(self.error("expected an envelope"));
+        };
+        match c {
+            '{' => {
+                if let Some(map) = self.parse_map() {
+                    return Ok(map);
+                }
+                self.position += 1;
+                let content = self.parse_content()?;
+                self.expect('}')?;
+                // `format` braces an assertion that is the subject of a node,
+                // so that its assertions aren't read as the object's.
+                self.skip_whitespace();
+                if content.is_assertion() && self.peek() == Some('[') {
+                    Ok(content)
+                } else {
+                    Ok(content.wrap())
+                }
+            }
+            '"' => self.parse_string(),
+            '\'' => {
+                let name = self.take_until('\'')?;
+                if name.is_empty() {
+                    return Ok(Envelope::unit());
+                }
+                self.leaf(start, DataType::Known, &name)
+            }
+            '[' => {
+                let (cbor, length) =
+                    dcbor_parse::parse_dcbor_item_partial(self.rest())
+                        .map_err(|e| self.error(&e.to_string()))?;
+                self.position += length;
+                Ok(Envelope::new(cbor))
+            }
+            _ => self.parse_word(start),
+        }
+    }
+
+    /// Parses braces that hold a CBOR map on a single line, such as
+    /// `{1: 2, 3: 10}`. Wrapped envelopes are written over several lines.
+    fn parse_map(&mut self) -> Option<Envelope> {
+        let (cbor, length) =
+            dcbor_parse::parse_dcbor_item_partial(self.rest()).ok()?;
+        if !matches!(cbor.as_case(), CBORCase::Map(_))
+            || self.rest()[..length].contains('\n')
+        {
+            return None;
+        }
+        self.position += length;
+        Some(Envelope::new(cbor))
+    }
+
+    /// Parses a string in double quotes. `format` writes newlines as `\n`;
+    /// `\"` and `\\` are also accepted. As `format` doesn't escape quotes,
+    /// a `"` ends the string only when followed by whitespace, punctuation, or
+    /// the end of the input.
+    fn parse_string(&mut self) -> Result<Envelope> {
+        self.position += 1;
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' if chars.peek().is_none_or(|&(_, next)| {
+                    next.is_whitespace() || "[]{}:,".contains(next)
+                }) =>
+                {
+                    self.position += index + 1;
+                    return Ok(Envelope::new(string));
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, c @ ('"' | '\\'))) => string.push(c),
+                    Some((_, c)) => {
+                        string.push('\\');
+                        string.push(c);
+                    }
+                    None => break,
+                },
+                c => string.push(c),
+            }
+        }
+        bail!(self.error("unterminated string"))
+    }
+
+    /// Consumes the opening delimiter and the text up to `end`, returning the
+    /// text.
+    fn take_until(&mut self, end: char) -> Result<String> {
+        let Some(length) = self.rest()[1..].find(end) else {
+            bail!(self.error(&format!("expected '{}'", end)));
+        };
+        let text = self.rest()[1..=length].to_string();
+        self.position += length + 1 + end.len_utf8();
+        Ok(text)
+    }
+
+    /// Parses a bare word: a number, a date, a `ur:` literal, a keyword, or a
+    /// `Name(...)` form.
+    fn parse_word(&mut self, start: usize) -> Result<Envelope> {
+        let length = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || "[]{}(),\"'".contains(c))
+            .unwrap_or(self.rest().len());
+        let mut word = &self.rest()[..length];
+        // A colon ends a word unless it is part of a time or a `ur:` prefix.
+        if is_date(word) && word.contains('T') {
+            word = word.strip_suffix(':').unwrap_or(word);
+        } else {
+            let prefix = if word.starts_with("ur:") { 3 } else { 0 };
+            if let Some(colon) = word[prefix..].find(':') {
+                word = &word[..prefix + colon];
+            }
+        }
+        if word.is_empty() {
+            bail!(self.error("expected an envelope"));
+        }
+        let word = word.to_string();
+        self.position += word.len();
+        if self.rest().starts_with('(') {
+            let argument = self.take_until(')')?;
+            return self.parse_named(start, &word, &argument);
+        }
+        match word.as_str() {
+            "true" | "false" => self.leaf(start, DataType::Bool, &word),
+            "null" => Ok(Envelope::null()),
+            "h" if self.rest().starts_with('\'') => {
+                let hex = self.take_until('\'')?;
+                let data = hex::decode(hex).map_err(|e| {
+                    self.error_at(start, &format!("invalid value: {}", e))
+                })?;
+                Ok(Envelope::new(CBOR::to_byte_string(data)))
+            }
+            "ELIDED" | "ENCRYPTED" | "COMPRESSED" => bail!(
+                self.error_at(start, "obscured elements cannot be parsed")
+            ),
+            _ if word.starts_with("ur:envelope/") => {
+                self.leaf(start, DataType::Envelope, &word)
+            }
+            _ if word.starts_with("ur:") => {
+                self.leaf(start, DataType::Ur, &word)
+            }
+            _ if is_date(&word) => self.leaf(start, DataType::Date, &word),
+            _ => self.parse_number(start, &word),
+        }
+    }
+
+    /// Parses a number, keeping integers exact.
+    fn parse_number(&self, start: usize, word: &str) -> Result<Envelope> {
+        if let Ok(n) = word.parse::<u64>() {
+            Ok(Envelope::new(n))
+        } else if let Ok(n) = word.parse::<i64>() {
+            Ok(Envelope::new(n))
+        } else if let Ok(n) = word.parse::<f64>() {
+            Ok(Envelope::new(n))
+        } else {
+            bail!(self.error_at(start, &format!("unknown value `{}`", word)))
+        }
+    }
+
+    /// Parses a `Name(argument)` form.
+    fn parse_named(
+        &self,
+        start: usize,
+        name: &str,
+        argument: &str,
+    ) -> Result<Envelope> {
+        match name {
+            "Date" => self.leaf(start, DataType::Date, argument),
+            "ARID" | "Digest" if argument.len() != 64 => {
+                bail!(self.error_at(
+                    start,
+                    &format!(
+                        "{}(...) needs all 64 hex digits; use `format \
+                         --lossless`",
+                        name
+                    )
+                ))
+            }
+            "ARID" => self.leaf(start, DataType::Arid, argument),
+            "Digest" => {
+                let data = hex::decode(argument).map_err(|e| {
+                    self.error_at(start, &format!("invalid value: {}", e))
+                })?;
+                Ok(Envelope::new(Digest::from_data_ref(data)?))
+            }
+            "URI" => self.leaf(start, DataType::Uri, argument),
+            "UUID" => self.leaf(start, DataType::Uuid, argument),
+            "bignum" => self.leaf(start, DataType::Bignum, argument),
+            "Bytes" => bail!(self.error_at(
+                start,
+                "Bytes(...) only gives the length of a byte string; use \
+                 `format --lossless`"
+            )),
+            _ => bail!(self.error_at(
+                start,
+                &format!(
+                    "cannot parse {}(...); use `format --lossless`",
+                    name
+                )
+            )),
+        }
+    }
+
+    /// Parses a leaf as `subject type` does, reporting errors at `start`.
+    fn leaf(
+        &self,
+        start: usize,
+        data_type: DataType,
+        value: &str,
+    ) -> Result<Envelope> {
+        parse_data_type_to_envelope(data_type, Some(value), None).map_err(|e| {
+            self.error_at(start, &format!("invalid value: {}", e)).into()
+        })
+    }
+}
+
+/// Returns whether `word` looks like an ISO 8601 date, as `format` writes
+/// dates without a `Date(...)` wrapper.
+fn is_date(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    bytes.len() >= 10
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[5..7].iter().all(u8::is_ascii_digit)
+        && bytes[7] == b'-'
+        && bytes[8..10].iter().all(u8::is_ascii_digit)
+}
@@ -0,0 +1,165 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use super::{Patch, PatchOperation};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
+
+/// Apply a patch envelope made by `diff --patch`.
+///
+/// The envelope must have the digest the patch was made from, and the
+/// patched envelope must have the digest the patch records, or the command
+/// fails without printing anything.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The patch envelope.
+    patch: String,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let envelope = self.read_envelope()?;
+        let patch = Patch::from_envelope(&Envelope::from_ur_string(
+            &self.patch,
+        )?)?;
+        Ok(apply_patch(&envelope, &patch)?.ur_string())
+    }
+}
+
+/// Applies `patch` to `envelope`, checking the digests of the envelope and
+/// of the result.
+///
+/// Every operation is applied to every element of the envelope with the
+/// operation's target digest. It is an error for a target to be missing.
+pub fn apply_patch(envelope: &Envelope, patch: &Patch) -> Result<Envelope> {
+    if envelope.digest() != patch.from {
+        bail!(Error::Verification(format!(
+            "the patch applies to {}, not to this envelope ({})",
+            patch.from.short_description(),
+            envelope.digest().short_description()
+        )));
+    }
+    let mut targets: HashMap<Digest, Vec<usize>> = HashMap::new();
+    for (index, operation) in patch.operations.iter().enumerate() {
+        targets.entry(operation.target()).or_default().push(index);
+    }
+    let mut patcher = Patcher {
+        operations: &patch.operations,
+        targets,
+        applied: HashSet::new(),
+    };
+    let result = patcher.apply(envelope)?;
+    if let Some(index) = (0..patch.operations.len())
+        .find(|index| !patcher.applied.contains(index))
+    {
+        let operation = &patch.operations[index];
+        bail!(Error::NotFound(format!(
+            "{} target {} not found",
+            operation.name(),
+            operation.target().short_description()
+        )));
+    }
+    if result.digest() != patch.to {
+        bail!(Error::Verification(format!(
+            "the patched envelope has digest {}, but the patch expects {}",
+            result.digest().short_description(),
+            patch.to.short_description()
+        )));
+    }
+    Ok(result)
+}
+
+struct Patcher<'a> {
+    operations: &'a [PatchOperation],
+    targets: HashMap<Digest, Vec<usize>>,
+    applied: HashSet<usize>,
+}
+
+impl Patcher<'_> {
+    /// Returns the patched form of `envelope`, patching its elements first.
+    fn apply(&mut self, envelope: &Envelope) -> Result<Envelope> {
+        let indexes =
+            self.targets.get(&envelope.digest()).cloned().unwrap_or_default();
+        let operations: Vec<(usize, &PatchOperation)> = indexes
+            .iter()
+            .map(|&index| (index, &self.operations[index]))
+            .collect();
+        self.applied.extend(indexes.iter().copied());
+
+        for (_, operation) in &operations {
+            match operation {
+                PatchOperation::Replace { envelope, .. }
+                | PatchOperation::Reveal { envelope, .. } => {
+                    return Ok(envelope.clone());
+                }
+                PatchOperation::Elide { .. } => return Ok(envelope.elide()),
+                _ => {}
+            }
+        }
+
+        let removed: HashSet<Digest> = operations
+            .iter()
+            .filter_map(|(_, operation)| match operation {
+                PatchOperation::RemoveAssertion { assertion, .. } => {
+                    Some(*assertion)
+                }
+                _ => None,
+            })
+            .collect();
+        let replaced_subject =
+            operations.iter().find_map(|(_, operation)| match operation {
+                PatchOperation::ReplaceSubject { subject, .. } => Some(subject),
+                _ => None,
+            });
+        let added = operations.iter().filter_map(|(_, operation)| {
+            match operation {
+                PatchOperation::AddAssertion { assertion, .. } => {
+                    Some(assertion.clone())
+                }
+                _ => None,
+            }
+        });
+
+        let case = (envelope.case(), replaced_subject);
+        let (subject, mut assertions) = match case {
+            (EnvelopeCase::Node { subject, assertions, .. }, replaced) => {
+                let subject = match replaced {
+                    Some(subject) => subject.clone(),
+                    None => self.apply(subject)?,
+                };
+                let mut patched = Vec::new();
+                for assertion in assertions {
+                    if removed.contains(&assertion.digest()) {
+                        continue;
+                    }
+                    patched.push(self.apply(assertion)?);
+                }
+                (subject, patched)
+            }
+            (_, Some(subject)) => (subject.clone(), Vec::new()),
+            (EnvelopeCase::Wrapped { envelope, .. }, None) => {
+                (self.apply(envelope)?.wrap(), Vec::new())
+            }
+            (EnvelopeCase::Assertion(assertion), None) => (
+                Envelope::new_assertion(
+                    self.apply(&assertion.predicate())?,
+                    self.apply(&assertion.object())?,
+                ),
+                Vec::new(),
+            ),
+            (_, None) => (envelope.clone(), Vec::new()),
+        };
+        assertions.extend(added);
+        Ok(subject.add_assertion_envelopes(&assertions)?)
+    }
+}
@@ -0,0 +1,215 @@
+pub mod apply;
+
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::{Args, Subcommand};
+
+use crate::Error;
+
+/// Work with patch envelopes made by `diff --patch`.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(subcommand)]
+    command: SubCommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum SubCommands {
+    Apply(apply::CommandArgs),
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        match &self.command {
+            SubCommands::Apply(args) => args.exec(),
+        }
+    }
+}
+
+/// One digest-targeted change to an envelope.
+///
+/// Each target is the digest of an element of the original envelope.
+#[derive(Debug, Clone)]
+pub enum PatchOperation {
+    /// Adds an assertion to the target.
+    AddAssertion { target: Digest, assertion: Envelope },
+    /// Removes the assertion with the given digest from the target.
+    RemoveAssertion { target: Digest, assertion: Digest },
+    /// Replaces the subject of the target, keeping its assertions.
+    ReplaceSubject { target: Digest, subject: Envelope },
+    /// Replaces the target with a different envelope.
+    Replace { target: Digest, envelope: Envelope },
+    /// Elides the target.
+    Elide { target: Digest },
+    /// Replaces the elided target with its revealed form.
+    Reveal { target: Digest, envelope: Envelope },
+}
+
+impl PatchOperation {
+    /// The name of the operation in a patch envelope.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AddAssertion { .. } => "addAssertion",
+            Self::RemoveAssertion { .. } => "removeAssertion",
+            Self::ReplaceSubject { .. } => "replaceSubject",
+            Self::Replace { .. } => "replace",
+            Self::Elide { .. } => "elide",
+            Self::Reveal { .. } => "reveal",
+        }
+    }
+
+    /// The digest of the element the operation applies to.
+    pub fn target(&self) -> Digest {
+        match self {
+            Self::AddAssertion { target, .. }
+            | Self::RemoveAssertion { target, .. }
+            | Self::ReplaceSubject { target, .. }
+            | Self::Replace { target, .. }
+            | Self::Elide { target }
+            | Self::Reveal { target, .. } => *target,
+        }
+    }
+
+    /// The envelope the operation inserts, if any.
+    pub fn envelope(&self) -> Option<&Envelope> {
+        match self {
+            Self::AddAssertion { assertion: envelope, .. }
+            | Self::ReplaceSubject { subject: envelope, .. }
+            | Self::Replace { envelope, .. }
+            | Self::Reveal { envelope, .. } => Some(envelope),
+            Self::RemoveAssertion { .. } | Self::Elide { .. } => None,
+        }
+    }
+
+    /// The operation as an element of a patch envelope.
+    ///
+    /// The subject is the operation's name. Inserted envelopes are wrapped,
+    /// so that assertions can be carried as objects.
+    pub fn to_envelope(&self) -> Envelope {
+        let mut envelope = Envelope::new(self.name())
+            .add_assertion(PATCH_TARGET, self.target());
+        if let Self::RemoveAssertion { assertion, .. } = self {
+            envelope = envelope.add_assertion(PATCH_ASSERTION, *assertion);
+        }
+        if let Some(inserted) = self.envelope() {
+            envelope = envelope.add_assertion(PATCH_ENVELOPE, inserted.wrap());
+        }
+        envelope
+    }
+}
+
+pub const PATCH_SUBJECT: &str = "patch";
+pub const PATCH_FROM: &str = "from";
+pub const PATCH_TO: &str = "to";
+pub const PATCH_OPERATION: &str = "operation";
+pub const PATCH_TARGET: &str = "target";
+pub const PATCH_ASSERTION: &str = "assertion";
+pub const PATCH_ENVELOPE: &str = "envelope";
+
+/// A set of operations that transforms the envelope with digest `from` into
+/// the envelope with digest `to`.
+#[derive(Debug, Clone)]
+pub struct Patch {
+    pub from: Digest,
+    pub to: Digest,
+    pub operations: Vec<PatchOperation>,
+}
+
+impl Patch {
+    /// The patch as an envelope:
+    ///
+    /// ```text
+    /// "patch" [
+    ///     "from": Digest
+    ///     "to": Digest
+    ///     "operation": "addAssertion" [
+    ///         "target": Digest
+    ///         "envelope": { ... }
+    ///     ]
+    /// ]
+    /// ```
+    pub fn into_envelope(self) -> Envelope {
+        self.operations.iter().fold(
+            Envelope::new(PATCH_SUBJECT)
+                .add_assertion(PATCH_FROM, self.from)
+                .add_assertion(PATCH_TO, self.to),
+            |envelope, operation| {
+                envelope.add_assertion(PATCH_OPERATION, operation.to_envelope())
+            },
+        )
+    }
+
+    /// Reads a patch envelope written by [`Patch::into_envelope`].
+    pub fn from_envelope(envelope: &Envelope) -> Result<Self> {
+        let invalid = |message: &str| {
+            Error::Parse(format!("invalid patch envelope: {}", message))
+        };
+        if envelope.extract_subject::<String>().ok().as_deref()
+            != Some(PATCH_SUBJECT)
+        {
+            bail!(invalid("the subject is not \"patch\""));
+        }
+        let digest = |envelope: &Envelope, predicate: &str| {
+            envelope
+                .extract_object_for_predicate::<Digest>(predicate)
+                .map_err(|_| {
+                    invalid(&format!("missing or invalid \"{}\"", predicate))
+                })
+        };
+        let operations = envelope
+            .objects_for_predicate(PATCH_OPERATION)
+            .iter()
+            .map(|operation| {
+                let name = operation
+                    .extract_subject::<String>()
+                    .map_err(|_| invalid("an operation has no name"))?;
+                let target = digest(operation, PATCH_TARGET)?;
+                let inserted = || -> Result<Envelope> {
+                    operation
+                        .object_for_predicate(PATCH_ENVELOPE)
+                        .and_then(|e| e.try_unwrap())
+                        .map_err(|_| {
+                            invalid(&format!(
+                                "{} has no wrapped \"{}\"",
+                                name, PATCH_ENVELOPE
+                            ))
+                            .into()
+                        })
+                };
+                Ok(match name.as_str() {
+                    "addAssertion" => PatchOperation::AddAssertion {
+                        target,
+                        assertion: inserted()?,
+                    },
+                    "removeAssertion" => PatchOperation::RemoveAssertion {
+                        target,
+                        assertion: digest(operation, PATCH_ASSERTION)?,
+                    },
+                    "replaceSubject" => PatchOperation::ReplaceSubject {
+                        target,
+                        subject: inserted()?,
+                    },
+                    "replace" => PatchOperation::Replace {
+                        target,
+                        envelope: inserted()?,
+                    },
+                    "elide" => PatchOperation::Elide { target },
+                    "reveal" => PatchOperation::Reveal {
+                        target,
+                        envelope: inserted()?,
+                    },
+                    _ => bail!(invalid(&format!(
+                        "unknown operation \"{}\"",
+                        name
+                    ))),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            from: digest(envelope, PATCH_FROM)?,
+            to: digest(envelope, PATCH_TO)?,
+            operations,
+        })
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
 use bc_envelope_pattern::{
-    FormatPathsOpts, Matcher, PathElementFormat, Pattern, format_paths_opt,
+    FormatPathsOpts, Matcher, Path, PathElementFormat, Pattern,
+    format_path_opt, format_paths_opt,
 };
 use clap::Args;
+use serde_json::{Value, json};
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, is_json_output, json_string};
 
 /// Match the envelope subject against a pattern.
 #[derive(Debug, Args)]
@@ -42,50 +45,14 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        let pattern = Pattern::parse(&self.pattern)
-            .map_err(|e| {
-                match e {
-                    bc_envelope_pattern::Error::UnrecognizedToken(span) => {
-                        let input = &self.pattern;
-                        let start = span.start.min(input.len());
-                        let end = span.end.min(input.len());
-                        let error_text = if start < input.len() {
-                            &input[start..end]
-                        } else {
-                            "<end of input>"
-                        };
-                        anyhow::anyhow!(
-                            "Failed to parse pattern at position {}..{}: unrecognized token '{}'\nPattern: {}\n         {}^",
-                            start, end, error_text, input,
-                            " ".repeat(start)
-                        )
-                    }
-                    bc_envelope_pattern::Error::ExtraData(span) => {
-                        let input = &self.pattern;
-                        let start = span.start.min(input.len());
-                        anyhow::anyhow!(
-                            "Failed to parse pattern: extra data at position {}\nPattern: {}\n         {}^",
-                            start, input, " ".repeat(start)
-                        )
-                    }
-                    bc_envelope_pattern::Error::UnexpectedToken(token, span) => {
-                        let input = &self.pattern;
-                        let start = span.start.min(input.len());
-                        anyhow::anyhow!(
-                            "Failed to parse pattern at position {}: unexpected token {:?}\nPattern: {}\n         {}^",
-                            start, token, input, " ".repeat(start)
-                        )
-                    }
-                    _ => anyhow::anyhow!("Failed to parse pattern: {}", e),
-                }
-            })?;
-        let (paths, _captures) = pattern.paths_with_captures(&envelope);
+        let pattern = parse_pattern(&self.pattern)?;
+        let paths = match_paths(&envelope, &pattern)?;
 
         // Build format options from command line arguments
         let element_format = if self.envelopes {
@@ -103,10 +70,97 @@ impl crate::Exec for CommandArgs {
             .element_format(element_format)
             .last_element_only(self.last_only);
 
-        if !paths.is_empty() {
-            Ok(format_paths_opt(&paths, format_options))
-        } else {
-            bail!("No match")
+        if is_json_output() {
+            let max_length = self.max_length;
+            let paths = paths
+                .iter()
+                .map(|path| {
+                    let elements = if self.last_only {
+                        &path[path.len().saturating_sub(1)..]
+                    } else {
+                        &path[..]
+                    };
+                    elements
+                        .iter()
+                        .map(|e| path_element_json(e, max_length))
+                        .collect()
+                })
+                .collect();
+            return json_string(&Value::Array(paths));
         }
+        Ok(format_paths_opt(&paths, format_options))
     }
 }
+
+/// Parses a pattern, reporting where a syntax error occurred.
+pub fn parse_pattern(pattern: &str) -> Result<Pattern> {
+    let pattern = Pattern::parse(pattern)
+        .map_err(|e| {
+            match e {
+                bc_envelope_pattern::Error::UnrecognizedToken(span) => {
+                    let input = pattern;
+                    let start = span.start.min(input.len());
+                    let end = span.end.min(input.len());
+                    let error_text = if start < input.len() {
+                        &input[start..end]
+                    } else {
+                        "<end of input>"
+                    };
+                    Error::Parse(format!(
+                        "Failed to parse pattern at position {}..{}: unrecognized token '{}'\nPattern: {}\n         {}^",
+                        start, end, error_text, input,
+                        " ".repeat(start)
+                    ))
+                }
+                bc_envelope_pattern::Error::ExtraData(span) => {
+                    let input = pattern;
+                    let start = span.start.min(input.len());
+                    Error::Parse(format!(
+                        "Failed to parse pattern: extra data at position {}\nPattern: {}\n         {}^",
+                        start, input, " ".repeat(start)
+                    ))
+                }
+                bc_envelope_pattern::Error::UnexpectedToken(token, span) => {
+                    let input = pattern;
+                    let start = span.start.min(input.len());
+                    Error::Parse(format!(
+                        "Failed to parse pattern at position {}: unexpected token {:?}\nPattern: {}\n         {}^",
+                        start, token, input, " ".repeat(start)
+                    ))
+                }
+                _ => {
+                    Error::Parse(format!("Failed to parse pattern: {}", e))
+                }
+            }
+        })?;
+    Ok(pattern)
+}
+
+/// Returns the paths in `envelope` that match `pattern`, failing with
+/// [`Error::NotFound`] if there are none.
+pub fn match_paths(
+    envelope: &Envelope,
+    pattern: &Pattern,
+) -> Result<Vec<Path>> {
+    let (paths, _captures) = pattern.paths_with_captures(envelope);
+    if paths.is_empty() {
+        bail!(Error::NotFound("No match".to_string()))
+    }
+    Ok(paths)
+}
+
+/// The JSON form of a path element: its digest and the same summary shown by
+/// the default text output.
+fn path_element_json(element: &Envelope, max_length: Option<usize>) -> Value {
+    let path: Path = vec![element.clone()];
+    let summary = format_path_opt(
+        &path,
+        FormatPathsOpts::new()
+            .element_format(PathElementFormat::Summary(max_length))
+            .last_element_only(true),
+    );
+    json!({
+        "digest": element.digest().ur_string(),
+        "summary": summary,
+    })
+}
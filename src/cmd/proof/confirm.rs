@@ -1,8 +1,10 @@
+use std::collections::HashSet;
+
 use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, parse_digests};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, parse_digests};
 
 /// Confirm that an elided envelope contains a target digest using a proof.
 ///
@@ -27,7 +29,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
@@ -35,9 +37,7 @@ impl crate::Exec for CommandArgs {
         let envelope = self.read_envelope()?;
         let proof = Envelope::from_ur_string(&self.proof)?;
         let digests = parse_digests(&self.target)?;
-        if !envelope.clone().confirm_contains_set(&digests, &proof) {
-            bail!("Proof does not confirm target");
-        }
+        proof_confirm(&envelope, &proof, &digests)?;
         Ok(if self.silent {
             "".to_string()
         } else {
@@ -45,3 +45,18 @@ impl crate::Exec for CommandArgs {
         })
     }
 }
+
+/// Confirms that `proof` shows `envelope` contains all of `target`, failing
+/// with [`Error::Verification`] if it doesn't.
+pub fn proof_confirm(
+    envelope: &Envelope,
+    proof: &Envelope,
+    target: &HashSet<Digest>,
+) -> Result<()> {
+    if !envelope.clone().confirm_contains_set(target, proof) {
+        bail!(Error::Verification(
+            "Proof does not confirm target".to_string()
+        ));
+    }
+    Ok(())
+}
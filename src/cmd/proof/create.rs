@@ -1,8 +1,10 @@
+use std::collections::HashSet;
+
 use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, parse_digests};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, parse_digests};
 
 /// Retrieve the assertion at the given index.
 #[derive(Debug, Args)]
@@ -18,18 +20,24 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
         let digests = parse_digests(&self.target)?;
-        let proof: Option<Envelope> = envelope.proof_contains_set(&digests);
-        if let Some(proof) = proof {
-            Ok(proof.ur_string())
-        } else {
-            bail!("No proof found for target set");
-        }
+        Ok(proof_create(&envelope, &digests)?.ur_string())
     }
 }
+
+/// Returns a proof that `envelope` contains all of `target`.
+pub fn proof_create(
+    envelope: &Envelope,
+    target: &HashSet<Digest>,
+) -> Result<Envelope> {
+    let Some(proof) = envelope.proof_contains_set(target) else {
+        bail!(Error::NotFound("No proof found for target set".to_string()));
+    };
+    Ok(proof)
+}
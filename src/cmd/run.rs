@@ -0,0 +1,301 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use super::shell::{Session, envelope_notation};
+use crate::{Error, read_envelope};
+
+/// Run the steps of a recipe file.
+///
+/// A recipe is a TOML file listing `envelope` commands as `[[step]]` tables.
+/// Each step's output can be named and used by later steps as `$NAME`, and
+/// can be checked against an expected output or digest. The run stops at the
+/// first step that fails.
+///
+/// With `--record`, the output of every step is written to a file next to the
+/// recipe. With `--check`, the recipe is run again and its outputs are
+/// compared with the recorded ones. See `docs/Recipes.md` for the format.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The recipe file.
+    recipe: PathBuf,
+
+    /// Record the output of each step.
+    #[arg(long, conflicts_with = "check")]
+    record: bool,
+
+    /// Compare the output of each step with the recorded output.
+    #[arg(long)]
+    check: bool,
+
+    /// The file of recorded outputs.
+    ///
+    /// Defaults to the recipe's path with the extension `.out.toml`.
+    #[arg(long, value_name = "PATH")]
+    outputs: Option<PathBuf>,
+}
+
+thread_local! {
+    /// The recipes being run, so that a recipe that runs itself, directly or
+    /// through other recipes, is an error rather than endless recursion.
+    static RUNNING: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let path = fs::canonicalize(&self.recipe)
+            .unwrap_or_else(|_| self.recipe.clone());
+        if RUNNING.with_borrow(|running| running.contains(&path)) {
+            bail!(Error::Usage(format!(
+                "the recipe {} runs itself",
+                self.recipe.display()
+            )));
+        }
+        RUNNING.with_borrow_mut(|running| running.push(path));
+        let result = self.run_file();
+        RUNNING.with_borrow_mut(|running| running.pop());
+        result
+    }
+}
+
+impl CommandArgs {
+    fn run_file(&self) -> Result<String> {
+        let text = fs::read_to_string(&self.recipe).with_context(|| {
+            format!("could not read {}", self.recipe.display())
+        })?;
+        let recipe: Recipe = toml::from_str(&text)
+            .map_err(|e| Error::Parse(format!("invalid recipe: {}", e)))?;
+        let outputs_path = self
+            .outputs
+            .clone()
+            .unwrap_or_else(|| self.recipe.with_extension("out.toml"));
+
+        let recorded = if self.check {
+            Some(read_recorded(&outputs_path)?)
+        } else {
+            None
+        };
+
+        let outputs = run_recipe(&recipe)?;
+        let mut report: Vec<String> = recipe
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| format!("{}: ok", step_label(i, step)))
+            .collect();
+
+        if let Some(recorded) = recorded {
+            check_outputs(&recipe, &outputs, &recorded)?;
+            report.push(format!(
+                "all outputs match {}",
+                outputs_path.display()
+            ));
+        }
+        if self.record {
+            let recorded = RecordedOutputs {
+                steps: recipe
+                    .steps
+                    .iter()
+                    .zip(&outputs)
+                    .map(|(step, output)| RecordedStep {
+                        name: step.name.clone(),
+                        output: output.clone(),
+                    })
+                    .collect(),
+            };
+            let text = toml::to_string(&recorded)?;
+            fs::write(&outputs_path, text).with_context(|| {
+                format!("could not write {}", outputs_path.display())
+            })?;
+            report.push(format!(
+                "recorded {} outputs to {}",
+                outputs.len(),
+                outputs_path.display()
+            ));
+        }
+        Ok(report.join("\n"))
+    }
+}
+
+/// A recipe: a list of `envelope` commands run in order.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Recipe {
+    /// Variables available to every step, such as keys.
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// The steps, from `[[step]]` tables.
+    #[serde(default, rename = "step")]
+    pub steps: Vec<RecipeStep>,
+}
+
+/// One step of a [`Recipe`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecipeStep {
+    /// Binds the output of the step to `$NAME`.
+    pub name: Option<String>,
+    /// The command to run, without the leading `envelope`.
+    pub run: String,
+    /// The exact output the step must produce.
+    pub expect: Option<String>,
+    /// The digest the step's output envelope must have.
+    pub expect_digest: Option<String>,
+    /// The envelope notation the step's output envelope must have.
+    pub expect_format: Option<String>,
+    /// If `true`, the step must fail.
+    #[serde(default)]
+    pub fails: bool,
+    /// If `false`, the step is skipped by `--check`. Use this for steps whose
+    /// output is random, such as a digest of a signed envelope.
+    #[serde(default = "default_check")]
+    pub check: bool,
+}
+
+fn default_check() -> bool { true }
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedOutputs {
+    #[serde(rename = "step")]
+    steps: Vec<RecordedStep>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedStep {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    output: String,
+}
+
+/// Runs the steps of `recipe` in order and returns the output of each.
+///
+/// The error of a failed step is prefixed with the step's number and name.
+pub fn run_recipe(recipe: &Recipe) -> Result<Vec<String>> {
+    let mut session = Session::default();
+    for (name, value) in &recipe.vars {
+        session.set_variable(name, value);
+    }
+    recipe
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            run_step(&mut session, step).with_context(|| step_label(i, step))
+        })
+        .collect()
+}
+
+fn run_step(session: &mut Session, step: &RecipeStep) -> Result<String> {
+    let result = session.run_line(step.name.as_deref(), &step.run);
+    if step.fails {
+        return match result {
+            Ok(_) => bail!(Error::Verification(
+                "the step was expected to fail, but succeeded".to_string()
+            )),
+            Err(_) => Ok(String::new()),
+        };
+    }
+    let output = result?;
+    if let Some(expected) = &step.expect
+        && output != expected.trim()
+    {
+        bail!(mismatch("output", expected.trim(), &output));
+    }
+    if let Some(expected) = &step.expect_digest {
+        let digest = read_envelope(Some(&output))?.digest().ur_string();
+        if digest != expected.trim() {
+            bail!(mismatch("digest", expected.trim(), &digest));
+        }
+    }
+    if let Some(expected) = &step.expect_format {
+        let notation = read_envelope(Some(&output))?.format();
+        if notation != expected.trim() {
+            bail!(mismatch("envelope notation", expected.trim(), &notation));
+        }
+    }
+    Ok(output)
+}
+
+fn read_recorded(path: &Path) -> Result<RecordedOutputs> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("could not read {}", path.display()))?;
+    toml::from_str(&text).map_err(|e| {
+        Error::Parse(format!("invalid recorded outputs: {}", e)).into()
+    })
+}
+
+/// Compares outputs with the recorded ones, reporting every step that
+/// differs.
+///
+/// Envelopes are compared by their envelope notation, so that signatures and
+/// ciphertext, which differ from run to run, don't count as differences.
+fn check_outputs(
+    recipe: &Recipe,
+    outputs: &[String],
+    recorded: &RecordedOutputs,
+) -> Result<()> {
+    if recorded.steps.len() != outputs.len() {
+        bail!(Error::Verification(format!(
+            "the recipe has {} steps, but {} outputs were recorded",
+            outputs.len(),
+            recorded.steps.len()
+        )));
+    }
+    let comparable = |output: &str| {
+        envelope_notation(output).unwrap_or_else(|| output.to_string())
+    };
+    let differences: Vec<String> = recipe
+        .steps
+        .iter()
+        .zip(outputs.iter().zip(&recorded.steps))
+        .enumerate()
+        .filter(|(_, (step, _))| step.check)
+        .filter_map(|(i, (step, (output, recorded)))| {
+            let expected = comparable(&recorded.output);
+            let actual = comparable(output);
+            (expected != actual).then(|| {
+                format!(
+                    "{} differs from the recorded output:\n{}",
+                    step_label(i, step),
+                    diff_lines(&expected, &actual)
+                )
+            })
+        })
+        .collect();
+    if !differences.is_empty() {
+        bail!(Error::Verification(differences.join("\n")));
+    }
+    Ok(())
+}
+
+fn mismatch(what: &str, expected: &str, actual: &str) -> Error {
+    Error::Verification(format!(
+        "unexpected {}:\n{}",
+        what,
+        diff_lines(expected, actual)
+    ))
+}
+
+/// Lists the expected lines prefixed with `-` and the actual lines prefixed
+/// with `+`.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let removed = expected.lines().map(|line| format!("- {}", line));
+    let added = actual.lines().map(|line| format!("+ {}", line));
+    removed.chain(added).collect::<Vec<_>>().join("\n")
+}
+
+fn step_label(index: usize, step: &RecipeStep) -> String {
+    match &step.name {
+        Some(name) => format!("step {} ({})", index + 1, name),
+        None => format!("step {}", index + 1),
+    }
+}
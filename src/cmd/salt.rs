@@ -22,17 +22,22 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        Ok((if let Some(size) = self.size {
-            envelope.add_salt_with_len(size)?
-        } else {
-            envelope.add_salt()
-        })
-        .ur_string())
+        Ok(salt(&envelope, self.size)?.ur_string())
+    }
+}
+
+/// Adds salt to `envelope`: `size` bytes of it, or a random amount if `size`
+/// is `None`.
+pub fn salt(envelope: &Envelope, size: Option<usize>) -> Result<Envelope> {
+    if let Some(size) = size {
+        Ok(envelope.add_salt_with_len(size)?)
+    } else {
+        Ok(envelope.add_salt())
     }
 }
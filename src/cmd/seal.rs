@@ -0,0 +1,79 @@
+use anyhow::{Result, bail};
+use bc_components::PublicKeys;
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use super::{
+    encrypt::{EncryptOptions, encrypt},
+    sign::{SignOptions, SignerKey, sign},
+};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
+
+/// Sign and encrypt the envelope in one step.
+///
+/// The envelope is wrapped and signed, then wrapped again and encrypted to
+/// the recipients, so that both the envelope's assertions and the signatures
+/// are hidden. Use `unseal` to reverse it.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    /// The signer to sign the envelope with. May be a private key base
+    /// (ur:crypto-prvkey-base), private keys (ur:crypto-prvkeys), or a signing
+    /// private key (ur:signing-private-key).
+    ///
+    /// Multiple signers may be provided.
+    #[arg(long, short)]
+    signer: Vec<String>,
+
+    /// The recipients to whom the envelope should be encrypted.
+    /// (ur:crypto-pubkeys)
+    ///
+    /// May be provided multiple times.
+    #[arg(long, short)]
+    recipient: Vec<String>,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let signers = self
+            .signer
+            .iter()
+            .map(|s| SignerKey::from_ur_string(s))
+            .collect::<Result<Vec<_>>>()?;
+        let recipients = self
+            .recipient
+            .iter()
+            .map(|s| PublicKeys::from_ur_string(s).map_err(anyhow::Error::from))
+            .collect::<Result<Vec<_>>>()?;
+        let envelope = self.read_envelope()?;
+        Ok(seal(&envelope, &signers, &recipients)?.ur_string())
+    }
+}
+
+/// Wraps and signs `envelope` with each of `signers`, then wraps the signed
+/// envelope and encrypts it to each of `recipients`.
+pub fn seal(
+    envelope: &Envelope,
+    signers: &[SignerKey],
+    recipients: &[PublicKeys],
+) -> Result<Envelope> {
+    if recipients.is_empty() {
+        bail!(Error::MissingInput(
+            "at least one recipient must be provided".to_string()
+        ));
+    }
+    let signed = sign(&envelope.wrap(), signers, &SignOptions::default())?;
+    let options = EncryptOptions {
+        recipients: recipients.to_vec(),
+        whole: true,
+        ..Default::default()
+    };
+    encrypt(&signed, &options)
+}
@@ -0,0 +1,309 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Parser};
+use regex::Regex;
+use serde_json::json;
+
+use crate::{
+    Error, Exec, MainCommands, read_envelope, report_error, with_envelope_input,
+};
+
+/// Start an interactive shell with named envelope variables.
+///
+/// Each line is an `envelope` command without the leading `envelope`. The
+/// result of the last command is available as `$_`, and the last envelope
+/// result is the input of the next command if it doesn't name an envelope of
+/// its own. Results can be bound to a name with `let NAME = COMMAND` and used
+/// later as `$NAME`.
+///
+/// After each command, its output is shown along with the envelope notation of
+/// envelope results. Lines beginning with `:` are shell commands; type `:help`
+/// for a list.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let mut session = Session::default();
+        let stdin = io::stdin();
+        let interactive = stdin.is_terminal();
+        let mut line = String::new();
+        loop {
+            if interactive {
+                print!("envelope> ");
+                io::stdout().flush()?;
+            }
+            line.clear();
+            if stdin.lock().read_line(&mut line)? == 0 {
+                break;
+            }
+            match session.eval(&line) {
+                Ok(Step::Output(output)) => {
+                    if !output.is_empty() {
+                        writeln!(io::stdout(), "{}", output)?;
+                    }
+                }
+                Ok(Step::Quit) => break,
+                Err(error) => report_error(&error),
+            }
+        }
+        Ok(String::new())
+    }
+}
+
+/// The outcome of a line evaluated by a [`Session`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Text to show the user, possibly empty.
+    Output(String),
+    /// The user asked to leave the shell.
+    Quit,
+}
+
+/// The state of a shell: its variables, history, and last results.
+#[derive(Debug, Default)]
+pub struct Session {
+    variables: BTreeMap<String, String>,
+    history: Vec<String>,
+    /// The last non-empty output, available as `$_`.
+    last: Option<String>,
+    /// The last output that was an envelope, used as the implicit input.
+    last_envelope: Option<String>,
+}
+
+/// Parses a shell line as the arguments of `envelope`.
+#[derive(Debug, Parser)]
+#[command(no_binary_name = true, infer_subcommands = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: MainCommands,
+}
+
+const HELP: &str = "\
+COMMAND...           Run an envelope command, such as `subject type string Alice`
+let NAME = COMMAND   Run a command and bind its result to $NAME
+:vars                List the variables and their values
+:history             List the lines entered so far
+:save FILE           Save the variables and history to a session file
+:load FILE           Load the variables and history from a session file
+:help                Show this help
+:quit                Leave the shell";
+
+impl Session {
+    /// Returns the value bound to `name`, if any.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// Returns the lines evaluated so far, oldest first.
+    pub fn history(&self) -> &[String] { &self.history }
+
+    /// Evaluates one line of input.
+    pub fn eval(&mut self, line: &str) -> Result<Step> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(Step::Output(String::new()));
+        }
+        self.history.push(line.to_string());
+        if let Some(command) = line.strip_prefix(':') {
+            return self.eval_shell_command(command);
+        }
+
+        let let_regex =
+            Regex::new(r"^let\s+([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.*)$")?;
+        let (name, command) = match let_regex.captures(line) {
+            Some(captures) => (
+                Some(captures[1].to_string()),
+                captures.get(2).unwrap().as_str(),
+            ),
+            None => (None, line),
+        };
+
+        let output = self.run_line(name.as_deref(), command)?;
+        let notation = envelope_notation(&output);
+        Ok(Step::Output(match (name, notation) {
+            (Some(_), Some(notation)) => notation,
+            (None, Some(notation)) => format!("{}\n{}", output, notation),
+            (_, None) => output,
+        }))
+    }
+
+    /// Binds `value` to `name`.
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.insert(name.to_string(), value.to_string());
+    }
+
+    /// Runs an `envelope` command line, without the leading `envelope`, and
+    /// returns its output. If `name` is given, the output is bound to it.
+    pub fn run_line(
+        &mut self,
+        name: Option<&str>,
+        command: &str,
+    ) -> Result<String> {
+        let output = self.run(command)?;
+        if let Some(name) = name {
+            if output.is_empty() {
+                bail!(Error::MissingInput(format!(
+                    "the command produced no result to bind to ${}",
+                    name
+                )));
+            }
+            self.set_variable(name, &output);
+        }
+        if !output.is_empty() {
+            self.last = Some(output.clone());
+        }
+        if envelope_notation(&output).is_some() {
+            self.last_envelope = Some(output.clone());
+        }
+        Ok(output)
+    }
+
+    /// Runs an `envelope` command line, returning its output.
+    fn run(&self, command: &str) -> Result<String> {
+        let words = shlex::split(command).ok_or_else(|| {
+            Error::Parse("unterminated quote in command".to_string())
+        })?;
+        let mut args = Vec::new();
+        for word in words {
+            args.extend(self.expand(&word)?);
+        }
+        let line = match ShellLine::try_parse_from(&args) {
+            Ok(line) => line,
+            // Help is reported as an error by clap, but is what the user
+            // asked for.
+            Err(error) if !error.use_stderr() => return Ok(error.to_string()),
+            Err(error) => {
+                let text = error.to_string();
+                let message = text.lines().next().unwrap_or_default();
+                bail!(Error::Usage(
+                    message.trim_start_matches("error: ").to_string()
+                ));
+            }
+        };
+        if matches!(line.command, MainCommands::Shell(_)) {
+            bail!(Error::Usage("the shell can't be nested".to_string()));
+        }
+        // Commands without an envelope argument read the last envelope
+        // instead of the shell's own input.
+        let input = self.last_envelope.as_deref().unwrap_or_default();
+        with_envelope_input(input, || line.command.exec())
+    }
+
+    /// Replaces `$NAME` references in a word. A word that is just a reference
+    /// to a value with several words, such as a set of SSKR shares, becomes
+    /// several arguments.
+    fn expand(&self, word: &str) -> Result<Vec<String>> {
+        let regex = Regex::new(r"\$([A-Za-z_][A-Za-z0-9_]*)")?;
+        let mut unknown = None;
+        let expanded = regex.replace_all(word, |captures: &regex::Captures| {
+            let name = &captures[1];
+            let value = if name == "_" {
+                self.last.as_deref()
+            } else {
+                self.variable(name)
+            };
+            value.map(str::to_string).unwrap_or_else(|| {
+                unknown.get_or_insert_with(|| name.to_string());
+                String::new()
+            })
+        });
+        if let Some(name) = unknown {
+            bail!(Error::NotFound(format!("unknown variable ${}", name)));
+        }
+        let is_reference = regex
+            .find(word)
+            .is_some_and(|m| m.start() == 0 && m.end() == word.len());
+        Ok(if is_reference {
+            expanded.split_whitespace().map(str::to_string).collect()
+        } else {
+            vec![expanded.into_owned()]
+        })
+    }
+
+    fn eval_shell_command(&mut self, command: &str) -> Result<Step> {
+        let (name, argument) = match command.split_once(char::is_whitespace)
+        {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (command, ""),
+        };
+        let output = match name {
+            "vars" => self
+                .variables
+                .iter()
+                .map(|(name, value)| format!("${} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "history" => self
+                .history
+                .iter()
+                .enumerate()
+                .map(|(i, line)| format!("{:>4}  {}", i + 1, line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            "save" => {
+                self.save(argument)?;
+                String::new()
+            }
+            "load" => {
+                self.load(argument)?;
+                String::new()
+            }
+            "help" => HELP.to_string(),
+            "quit" | "exit" => return Ok(Step::Quit),
+            _ => bail!(Error::Usage(format!(
+                "unknown shell command :{}; type :help for a list",
+                name
+            ))),
+        };
+        Ok(Step::Output(output))
+    }
+
+    /// Writes the variables and history to `path` as JSON.
+    pub fn save(&self, path: &str) -> Result<()> {
+        if path.is_empty() {
+            bail!(Error::MissingInput("no session file given".to_string()));
+        }
+        let session = json!({
+            "variables": self.variables,
+            "history": self.history,
+        });
+        fs::write(path, serde_json::to_string_pretty(&session)? + "\n")
+            .with_context(|| format!("could not write {}", path))
+    }
+
+    /// Reads a session file written by [`Session::save`], adding its
+    /// variables and placing its history before the current history.
+    pub fn load(&mut self, path: &str) -> Result<()> {
+        if path.is_empty() {
+            bail!(Error::MissingInput("no session file given".to_string()));
+        }
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path))?;
+        let session: serde_json::Value =
+            serde_json::from_str(&text).context("invalid session file")?;
+        let variables: BTreeMap<String, String> =
+            serde_json::from_value(session["variables"].clone())
+                .context("invalid session file")?;
+        let history: Vec<String> =
+            serde_json::from_value(session["history"].clone())
+                .context("invalid session file")?;
+        self.variables.extend(variables);
+        self.history.splice(0..0, history);
+        Ok(())
+    }
+}
+
+/// The envelope notation of `output`, if it is a single envelope.
+pub fn envelope_notation(output: &str) -> Option<String> {
+    if output.is_empty() || output.contains(char::is_whitespace) {
+        return None;
+    }
+    read_envelope(Some(output)).ok().map(|e| e.format())
+}
@@ -7,7 +7,7 @@ use clap::Args;
 use known_values::NOTE;
 
 use super::generate::HashType;
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Sign the envelope subject with the provided signer(s).
 #[derive(Debug, Args)]
@@ -38,7 +38,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
@@ -51,75 +51,124 @@ impl crate::Exec for CommandArgs {
                     "signer keys must be passed with --signer/-s; did you mean: envelope sign --signer <key> [ENVELOPE]?"
                 );
             }
-            bail!("at least one signer must be provided");
+            bail!(Error::MissingInput(
+                "at least one signer must be provided".to_string()
+            ));
         }
         let envelope = self.read_envelope()?;
-        let mut private_key_bases: Vec<PrivateKeyBase> = Vec::new();
-        let mut private_keys: Vec<PrivateKeys> = Vec::new();
-        let mut signing_private_keys: Vec<SigningPrivateKey> = Vec::new();
-        let mut signing_options: Vec<Option<SigningOptions>> = Vec::new();
-        for s in &self.signer {
-            if let Ok(key) = PrivateKeyBase::from_ur_string(s) {
-                private_key_bases.push(key);
-            } else if let Ok(key) = PrivateKeys::from_ur_string(s) {
-                private_keys.push(key);
-            } else if let Ok(key) = SigningPrivateKey::from_ur_string(s) {
-                if key.is_ssh() {
-                    let namespace = self.namespace.clone();
-                    let hash_alg = self.hash_type.to_ssh_hash_alg();
-                    signing_options.push(Some(SigningOptions::Ssh {
-                        namespace,
-                        hash_alg,
-                    }));
-                } else {
-                    signing_options.push(None);
-                }
-                signing_private_keys.push(key);
-            } else {
-                bail!("invalid signer: {}", s);
-            }
+        let signers = self
+            .signer
+            .iter()
+            .map(|s| SignerKey::from_ur_string(s))
+            .collect::<Result<Vec<_>>>()?;
+        let options = SignOptions {
+            note: self.note.clone(),
+            namespace: self.namespace.clone(),
+            hash_type: self.hash_type,
+        };
+        Ok(sign(&envelope, &signers, &options)?.ur_string())
+    }
+}
+
+/// A key that can sign an envelope.
+#[derive(Debug, Clone)]
+pub enum SignerKey {
+    PrivateKeyBase(PrivateKeyBase),
+    PrivateKeys(PrivateKeys),
+    SigningPrivateKey(SigningPrivateKey),
+}
+
+impl SignerKey {
+    /// Parses a `ur:crypto-prvkey-base`, `ur:crypto-prvkeys`, or
+    /// `ur:signing-private-key`.
+    pub fn from_ur_string(s: &str) -> Result<Self> {
+        if let Ok(key) = PrivateKeyBase::from_ur_string(s) {
+            Ok(Self::PrivateKeyBase(key))
+        } else if let Ok(key) = PrivateKeys::from_ur_string(s) {
+            Ok(Self::PrivateKeys(key))
+        } else if let Ok(key) = SigningPrivateKey::from_ur_string(s) {
+            Ok(Self::SigningPrivateKey(key))
+        } else {
+            bail!(Error::Parse(format!("invalid signer: {}", s)));
         }
-        let mut signers: Vec<(
-            &dyn Signer,
-            Option<SigningOptions>,
-            Option<SignatureMetadata>,
-        )> = Vec::new();
-        for key in private_key_bases.iter() {
-            signers.push((key as &dyn Signer, None, None));
+    }
+
+    fn as_signer(&self) -> &dyn Signer {
+        match self {
+            Self::PrivateKeyBase(key) => key,
+            Self::PrivateKeys(key) => key,
+            Self::SigningPrivateKey(key) => key,
         }
-        for key in private_keys.iter() {
-            let options = if key.signing_private_key().is_ssh() {
-                let namespace = self.namespace.clone();
-                let hash_alg = self.hash_type.to_ssh_hash_alg();
-                Some(SigningOptions::Ssh { namespace, hash_alg })
-            } else {
-                None
-            };
-            signers.push((key as &dyn Signer, options, None));
+    }
+
+    fn is_ssh(&self) -> bool {
+        match self {
+            Self::PrivateKeyBase(_) => false,
+            Self::PrivateKeys(key) => key.signing_private_key().is_ssh(),
+            Self::SigningPrivateKey(key) => key.is_ssh(),
         }
-        for i in 0..signing_private_keys.len() {
-            signers.push((
-                &signing_private_keys[i] as &dyn Signer,
-                signing_options[i].clone(),
-                None,
-            ));
+    }
+}
+
+/// Options for [`sign`].
+#[derive(Debug, Clone)]
+pub struct SignOptions {
+    /// A note to add to the signature. Only allowed with a single signer.
+    pub note: Option<String>,
+    /// The namespace for SSH signatures.
+    pub namespace: String,
+    /// The hash algorithm for SSH signatures.
+    pub hash_type: HashType,
+}
+
+impl Default for SignOptions {
+    fn default() -> Self {
+        Self {
+            note: None,
+            namespace: "envelope".to_string(),
+            hash_type: HashType::Sha256,
         }
-        if let Some(note) = &self.note {
-            if signers.len() != 1 {
-                bail!("can only add a note on a single signature");
-            }
-            let metadata =
-                SignatureMetadata::new().with_assertion(NOTE, note.clone());
-            Ok(envelope
-                .add_signature_opt(
-                    signers[0].0,
-                    signers[0].1.clone(),
-                    Some(metadata),
-                )
-                .ur_string())
-        } else {
-            Ok(envelope.add_signatures_opt(&signers).ur_string())
+    }
+}
+
+/// Signs the subject of `envelope` with each of `signers`.
+pub fn sign(
+    envelope: &Envelope,
+    signers: &[SignerKey],
+    options: &SignOptions,
+) -> Result<Envelope> {
+    if signers.is_empty() {
+        bail!(Error::MissingInput(
+            "at least one signer must be provided".to_string()
+        ));
+    }
+    let signers: Vec<(
+        &dyn Signer,
+        Option<SigningOptions>,
+        Option<SignatureMetadata>,
+    )> = signers
+        .iter()
+        .map(|key| {
+            let signing_options = key.is_ssh().then(|| SigningOptions::Ssh {
+                namespace: options.namespace.clone(),
+                hash_alg: options.hash_type.to_ssh_hash_alg(),
+            });
+            (key.as_signer(), signing_options, None)
+        })
+        .collect();
+    if let Some(note) = &options.note {
+        if signers.len() != 1 {
+            bail!("can only add a note on a single signature");
         }
+        let metadata =
+            SignatureMetadata::new().with_assertion(NOTE, note.clone());
+        Ok(envelope.add_signature_opt(
+            signers[0].0,
+            signers[0].1.clone(),
+            Some(metadata),
+        ))
+    } else {
+        Ok(envelope.add_signatures_opt(&signers))
     }
 }
 
@@ -2,6 +2,8 @@ use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
 use clap::Args;
 
+use crate::{Error, has_envelope_input, read_command_line};
+
 /// Join a set of SSKR shares back into the original envelope.
 #[derive(Debug, Args)]
 #[group(skip)]
@@ -13,8 +15,11 @@ pub struct CommandArgs {
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         // If envelopes is empty, read them from stdin, one per line.
+        // The shell or `--each` supplies a single share.
         let mut shares = self.shares.clone();
-        if shares.is_empty() {
+        if shares.is_empty() && has_envelope_input() {
+            shares.push(read_command_line()?.trim().to_string());
+        } else if shares.is_empty() {
             let mut line = String::new();
             while std::io::stdin().read_line(&mut line)? > 0 {
                 shares.push(line.trim().to_string());
@@ -24,17 +29,24 @@ impl crate::Exec for CommandArgs {
 
         let shares: Vec<Envelope> = shares
             .iter()
-            .map(|s| Envelope::from_ur_string(s).unwrap())
-            .collect();
+            .map(Envelope::from_ur_string)
+            .collect::<Result<_, _>>()?;
 
-        // Make sure we have at least one.
-        if shares.is_empty() {
-            bail!("No share envelopes provided");
-        }
+        Ok(sskr_join(&shares)?.ur_string())
+    }
+}
 
-        let shares_refs: Vec<&Envelope> = shares.iter().collect();
-        let wrapped = bc_envelope::Envelope::sskr_join(&shares_refs)?;
-        let result = wrapped.try_unwrap()?;
-        Ok(result.ur_string())
+/// Joins SSKR shares produced by [`super::split::sskr_split`] and returns the
+/// original envelope.
+pub fn sskr_join(shares: &[Envelope]) -> Result<Envelope> {
+    // Make sure we have at least one.
+    if shares.is_empty() {
+        bail!(Error::MissingInput(
+            "No share envelopes provided".to_string()
+        ));
     }
+
+    let shares_refs: Vec<&Envelope> = shares.iter().collect();
+    let wrapped = bc_envelope::Envelope::sskr_join(&shares_refs)?;
+    Ok(wrapped.try_unwrap()?)
 }
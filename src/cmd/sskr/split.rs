@@ -4,7 +4,7 @@ use bc_components::{PublicKeys, SSKRGroupSpec, SSKRSpec, SymmetricKey};
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Split an envelope into several shares using SSKR.
 #[derive(Debug, Args)]
@@ -41,72 +41,33 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-
-        if self.group_threshold > self.groups.len() {
-            bail!(
-                "Group threshold must be less than or equal to the number of groups"
-            );
-        }
-
-        let regex = regex::Regex::new(r"(\d{1,2})-of-(\d{1,2})")?;
-        let groups: Vec<(usize, usize)> = self
-            .groups
-            .iter()
-            .map(|group| {
-                let matches = regex.captures(group).ok_or_else(|| {
-                    anyhow::anyhow!("Invalid group specifier: {}", group)
-                })?;
-                let m = matches[1].parse()?;
-                let n = matches[2].parse()?;
-                Ok((m, n))
-            })
-            .collect::<Result<_>>()?;
-
-        let content_key = match &self.key {
-            Some(key) => SymmetricKey::from_ur_string(key)?,
-            None => SymmetricKey::new(),
-        };
-
-        let wrapped = envelope.wrap();
-        let encrypted = wrapped.encrypt_subject(&content_key)?;
-        let group_specs: Vec<SSKRGroupSpec> = groups
-            .iter()
-            .map(|(m, n)| SSKRGroupSpec::new(*m, *n))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(anyhow::Error::from)?;
-        let spec = SSKRSpec::new(self.group_threshold, group_specs)?;
-        let grouped_shares = encrypted.sskr_split(&spec, &content_key)?;
-        let flattened_shares =
-            grouped_shares.into_iter().flatten().collect::<Vec<_>>();
-        let flattened_shares = if self.recipients.is_empty() {
-            flattened_shares
-        } else {
-            let recipients: Vec<PublicKeys> = self
+        let options = SskrSplitOptions {
+            group_threshold: self.group_threshold,
+            groups: self
+                .groups
+                .iter()
+                .map(|group| parse_group_spec(group))
+                .collect::<Result<_>>()?,
+            key: self
+                .key
+                .as_ref()
+                .map(SymmetricKey::from_ur_string)
+                .transpose()?,
+            recipients: self
                 .recipients
                 .iter()
                 .map(|r| {
                     PublicKeys::from_ur_string(r).map_err(anyhow::Error::from)
                 })
-                .collect::<Result<_>>()?;
-            flattened_shares
-                .into_iter()
-                .map(|share| {
-                    let mut share = share;
-                    for recipient in &recipients {
-                        share = share.add_recipient(recipient, &content_key);
-                    }
-                    Ok(share)
-                })
-                .collect::<Result<Vec<_>>>()?
+                .collect::<Result<_>>()?,
         };
-
-        let output_shares = flattened_shares
+        let output_shares = sskr_split(&envelope, &options)?
             .iter()
             .map(|share| share.ur_string())
             .collect::<Vec<_>>()
@@ -115,3 +76,80 @@ impl crate::Exec for CommandArgs {
         Ok(output_shares)
     }
 }
+
+/// Options for [`sskr_split`].
+#[derive(Debug, Clone)]
+pub struct SskrSplitOptions {
+    /// The number of groups that must meet their threshold.
+    pub group_threshold: usize,
+    /// The `(threshold, count)` of each group.
+    pub groups: Vec<(usize, usize)>,
+    /// The content key. If `None`, an ephemeral key is generated.
+    pub key: Option<SymmetricKey>,
+    /// Public keys to also encrypt the content key to.
+    pub recipients: Vec<PublicKeys>,
+}
+
+impl Default for SskrSplitOptions {
+    /// A single `1-of-1` group, with an ephemeral key and no recipients.
+    fn default() -> Self {
+        Self {
+            group_threshold: 1,
+            groups: vec![(1, 1)],
+            key: None,
+            recipients: Vec::new(),
+        }
+    }
+}
+
+/// Parses a group specification such as `2-of-3` into `(2, 3)`.
+pub fn parse_group_spec(group: &str) -> Result<(usize, usize)> {
+    let regex = regex::Regex::new(r"(\d{1,2})-of-(\d{1,2})")?;
+    let matches = regex.captures(group).ok_or_else(|| {
+        Error::Parse(format!("Invalid group specifier: {}", group))
+    })?;
+    let m = matches[1].parse()?;
+    let n = matches[2].parse()?;
+    Ok((m, n))
+}
+
+/// Wraps and encrypts `envelope`, then splits it into SSKR shares.
+///
+/// The shares are returned in group order. If recipients are given, each
+/// share also has the content key sealed to every recipient.
+pub fn sskr_split(
+    envelope: &Envelope,
+    options: &SskrSplitOptions,
+) -> Result<Vec<Envelope>> {
+    if options.group_threshold > options.groups.len() {
+        bail!(
+            "Group threshold must be less than or equal to the number of groups"
+        );
+    }
+
+    let content_key = match &options.key {
+        Some(key) => key.clone(),
+        None => SymmetricKey::new(),
+    };
+
+    let wrapped = envelope.wrap();
+    let encrypted = wrapped.encrypt_subject(&content_key)?;
+    let group_specs: Vec<SSKRGroupSpec> = options
+        .groups
+        .iter()
+        .map(|(m, n)| SSKRGroupSpec::new(*m, *n))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(anyhow::Error::from)?;
+    let spec = SSKRSpec::new(options.group_threshold, group_specs)?;
+    let grouped_shares = encrypted.sskr_split(&spec, &content_key)?;
+    let shares = grouped_shares
+        .into_iter()
+        .flatten()
+        .map(|share| {
+            options.recipients.iter().fold(share, |share, recipient| {
+                share.add_recipient(recipient, &content_key)
+            })
+        })
+        .collect();
+    Ok(shares)
+}
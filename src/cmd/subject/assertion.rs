@@ -2,7 +2,9 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{DataType, PredObjArgs, PredObjArgsLike};
+use crate::{
+    DataType, PredObjArgs, PredObjArgsLike, assertion::create::assertion_create,
+};
 
 /// Create an envelope with the given assertion (predicate and object).
 #[derive(Debug, Args)]
@@ -23,6 +25,7 @@ impl PredObjArgsLike for CommandArgs {
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        Ok(self.assertion_envelope()?.ur_string())
+        let (predicate, object) = self.predicate_and_object()?;
+        Ok(assertion_create(&predicate, &object, false).ur_string())
     }
 }
@@ -32,7 +32,7 @@ impl crate::Exec for CommandArgs {
         } else {
             Some(self.read_subject_value()?)
         };
-        Ok(parse_data_type_to_envelope(
+        Ok(subject_envelope(
             self.subject_type(),
             value.as_deref(),
             self.ur_tag(),
@@ -40,3 +40,15 @@ impl crate::Exec for CommandArgs {
         .ur_string())
     }
 }
+
+/// Returns an envelope whose subject is `value` parsed as `data_type`.
+///
+/// `value` is ignored for [`DataType::Unit`]. `ur_tag` is the CBOR tag to use
+/// for an unknown UR type.
+pub fn subject_envelope(
+    data_type: DataType,
+    value: Option<&str>,
+    ur_tag: Option<u64>,
+) -> Result<Envelope> {
+    parse_data_type_to_envelope(data_type, value, ur_tag)
+}
@@ -0,0 +1,81 @@
+use anyhow::{Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+
+use super::{
+    decrypt::{UnlockArgs, UnlockMethod, decrypt_with_any},
+    verify::{VerifierKey, verify},
+};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
+
+/// Decrypt and verify an envelope made by `seal`.
+///
+/// On success, print the envelope that was sealed. It is an error if the
+/// envelope can't be decrypted, or if it isn't signed by the verifiers.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct CommandArgs {
+    #[command(flatten)]
+    unlock_args: UnlockArgs,
+
+    /// The verifier(s). May be a private key base (ur:prvkeys), `PublicKeys`
+    /// (ur:crypto-pubkeys), signing private key (ur:signing-private-key), or a
+    /// signing public key (ur:signing-public-key).
+    ///
+    /// Multiple verifiers may be provided.
+    #[arg(long, short)]
+    verifier: Vec<String>,
+
+    /// The minimum number of required valid signatures.
+    #[arg(long, short, default_value = "1")]
+    threshold: usize,
+
+    #[command(flatten)]
+    envelope_args: EnvelopeArgs,
+}
+
+impl EnvelopeArgsLike for CommandArgs {
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
+}
+
+impl crate::Exec for CommandArgs {
+    fn exec(&self) -> Result<String> {
+        let verifiers = self
+            .verifier
+            .iter()
+            .map(|v| VerifierKey::from_ur_string(v))
+            .collect::<Result<Vec<_>>>()?;
+        let envelope = self.read_envelope()?;
+        let methods = self.unlock_args.methods(&envelope)?;
+        Ok(unseal(&envelope, &methods, &verifiers, self.threshold)?
+            .ur_string())
+    }
+}
+
+/// Reverses [`seal`](super::seal::seal): decrypts `envelope` with the first
+/// of `methods` that unlocks it, checks that at least `threshold` of
+/// `verifiers` signed it, and returns the envelope that was sealed.
+pub fn unseal(
+    envelope: &Envelope,
+    methods: &[UnlockMethod],
+    verifiers: &[VerifierKey],
+    threshold: usize,
+) -> Result<Envelope> {
+    if verifiers.is_empty() {
+        bail!(Error::MissingInput(
+            "at least one verifier must be provided".to_string()
+        ));
+    }
+    let not_sealed = || {
+        Error::Parse(
+            "not a sealed envelope: the decrypted subject is not a wrapped, \
+             signed envelope"
+                .to_string(),
+        )
+    };
+    let signed = decrypt_with_any(envelope, methods)?
+        .try_unwrap()
+        .map_err(|_| not_sealed())?;
+    verify(&signed, verifiers, threshold)?;
+    Ok(signed.try_unwrap().map_err(|_| not_sealed())?)
+}
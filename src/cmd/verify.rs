@@ -5,7 +5,7 @@ use bc_components::{
 use bc_envelope::prelude::*;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Verify a signature on the envelope using the provided verifiers.
 ///
@@ -37,75 +37,18 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        if self.verifier.is_empty() {
-            bail!("at least one verifier must be provided");
-        }
-        let mut private_key_bases: Vec<PrivateKeyBase> = Vec::new();
-        let mut public_keys_vec: Vec<PublicKeys> = Vec::new();
-        let mut signing_private_keys: Vec<SigningPrivateKey> = Vec::new();
-        let mut signing_public_keys: Vec<SigningPublicKey> = Vec::new();
-        for v in &self.verifier {
-            if let Ok(key) = PrivateKeyBase::from_ur_string(v) {
-                private_key_bases.push(key);
-            } else if let Ok(key) = PublicKeys::from_ur_string(v) {
-                public_keys_vec.push(key);
-            } else if let Ok(key) = SigningPrivateKey::from_ur_string(v) {
-                signing_private_keys.push(key);
-            } else if let Ok(key) = SigningPublicKey::from_ur_string(v) {
-                signing_public_keys.push(key);
-            } else if v.starts_with("ur:envelope") {
-                // Handle envelope-wrapped keys (e.g., from `xid key at`)
-                // by extracting the key from the envelope's subject
-                let key_envelope = Envelope::from_ur_string(v)?;
-                if let Ok(key) =
-                    key_envelope.extract_subject::<PrivateKeyBase>()
-                {
-                    private_key_bases.push(key);
-                } else if let Ok(key) =
-                    key_envelope.extract_subject::<PublicKeys>()
-                {
-                    public_keys_vec.push(key);
-                } else if let Ok(key) =
-                    key_envelope.extract_subject::<SigningPrivateKey>()
-                {
-                    signing_private_keys.push(key);
-                } else if let Ok(key) =
-                    key_envelope.extract_subject::<SigningPublicKey>()
-                {
-                    signing_public_keys.push(key);
-                } else {
-                    bail!(
-                        "envelope does not contain a valid verifier key: {}",
-                        v
-                    );
-                }
-            } else {
-                bail!("invalid verifier: {}", v);
-            }
-        }
-        let mut verifiers: Vec<&dyn Verifier> = Vec::new();
-        for key in private_key_bases.iter() {
-            verifiers.push(key as &dyn Verifier);
-        }
-        for key in public_keys_vec.iter() {
-            verifiers.push(key as &dyn Verifier);
-        }
-        for key in signing_private_keys.iter() {
-            verifiers.push(key as &dyn Verifier);
-        }
-        for key in signing_public_keys.iter() {
-            verifiers.push(key as &dyn Verifier);
-        }
-        envelope.clone().verify_signatures_from_threshold(
-            &verifiers,
-            Some(self.threshold),
-        )?;
+        let verifiers = self
+            .verifier
+            .iter()
+            .map(|v| VerifierKey::from_ur_string(v))
+            .collect::<Result<Vec<_>>>()?;
+        verify(&envelope, &verifiers, self.threshold)?;
         Ok(if self.silent {
             "".to_string()
         } else {
@@ -113,3 +56,84 @@ impl crate::Exec for CommandArgs {
         })
     }
 }
+
+/// A key that can verify a signature on an envelope.
+#[derive(Debug, Clone)]
+pub enum VerifierKey {
+    PrivateKeyBase(PrivateKeyBase),
+    PublicKeys(PublicKeys),
+    SigningPrivateKey(SigningPrivateKey),
+    SigningPublicKey(SigningPublicKey),
+}
+
+impl VerifierKey {
+    /// Parses a `ur:crypto-prvkey-base`, `ur:crypto-pubkeys`,
+    /// `ur:signing-private-key`, or `ur:signing-public-key`, or a
+    /// `ur:envelope` whose subject is one of these.
+    pub fn from_ur_string(v: &str) -> Result<Self> {
+        if let Ok(key) = PrivateKeyBase::from_ur_string(v) {
+            Ok(Self::PrivateKeyBase(key))
+        } else if let Ok(key) = PublicKeys::from_ur_string(v) {
+            Ok(Self::PublicKeys(key))
+        } else if let Ok(key) = SigningPrivateKey::from_ur_string(v) {
+            Ok(Self::SigningPrivateKey(key))
+        } else if let Ok(key) = SigningPublicKey::from_ur_string(v) {
+            Ok(Self::SigningPublicKey(key))
+        } else if v.starts_with("ur:envelope") {
+            // Handle envelope-wrapped keys (e.g., from `xid key at`)
+            // by extracting the key from the envelope's subject
+            let key_envelope = Envelope::from_ur_string(v)?;
+            if let Ok(key) = key_envelope.extract_subject::<PrivateKeyBase>() {
+                Ok(Self::PrivateKeyBase(key))
+            } else if let Ok(key) =
+                key_envelope.extract_subject::<PublicKeys>()
+            {
+                Ok(Self::PublicKeys(key))
+            } else if let Ok(key) =
+                key_envelope.extract_subject::<SigningPrivateKey>()
+            {
+                Ok(Self::SigningPrivateKey(key))
+            } else if let Ok(key) =
+                key_envelope.extract_subject::<SigningPublicKey>()
+            {
+                Ok(Self::SigningPublicKey(key))
+            } else {
+                bail!(Error::Parse(format!(
+                    "envelope does not contain a valid verifier key: {}",
+                    v
+                )));
+            }
+        } else {
+            bail!(Error::Parse(format!("invalid verifier: {}", v)));
+        }
+    }
+
+    fn as_verifier(&self) -> &dyn Verifier {
+        match self {
+            Self::PrivateKeyBase(key) => key,
+            Self::PublicKeys(key) => key,
+            Self::SigningPrivateKey(key) => key,
+            Self::SigningPublicKey(key) => key,
+        }
+    }
+}
+
+/// Verifies that at least `threshold` of `verifiers` have signed the subject
+/// of `envelope`.
+pub fn verify(
+    envelope: &Envelope,
+    verifiers: &[VerifierKey],
+    threshold: usize,
+) -> Result<()> {
+    if verifiers.is_empty() {
+        bail!(Error::MissingInput(
+            "at least one verifier must be provided".to_string()
+        ));
+    }
+    let verifiers: Vec<&dyn Verifier> =
+        verifiers.iter().map(VerifierKey::as_verifier).collect();
+    envelope
+        .clone()
+        .verify_signatures_from_threshold(&verifiers, Some(threshold))?;
+    Ok(())
+}
@@ -15,7 +15,16 @@ impl CommandArgs {
         envelope: Envelope,
         target: Option<HashSet<Digest>>,
     ) -> Result<String> {
-        let result = envelope.walk_decompress(target.as_ref());
+        let result = walk_decompress(&envelope, target.as_ref());
         Ok(result.ur_string())
     }
 }
+
+/// Decompresses the compressed nodes of `envelope`, or only those in
+/// `target` if it is given.
+pub fn walk_decompress(
+    envelope: &Envelope,
+    target: Option<&HashSet<Digest>>,
+) -> Envelope {
+    envelope.walk_decompress(target)
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bc_components::SymmetricKey;
 use bc_envelope::prelude::*;
 use clap::Args;
 
@@ -13,15 +14,18 @@ pub struct CommandArgs {
 
 impl CommandArgs {
     pub fn exec_with_envelope(&self, envelope: Envelope) -> Result<String> {
-        use bc_components::SymmetricKey;
-
         let mut symmetric_keys = Vec::new();
         for ur_string in &self.keys {
             let key = SymmetricKey::from_ur_string(ur_string)?;
             symmetric_keys.push(key);
         }
 
-        let result = envelope.walk_decrypt(&symmetric_keys);
+        let result = walk_decrypt(&envelope, &symmetric_keys);
         Ok(result.ur_string())
     }
 }
+
+/// Decrypts the encrypted nodes of `envelope` that one of `keys` unlocks.
+pub fn walk_decrypt(envelope: &Envelope, keys: &[SymmetricKey]) -> Envelope {
+    envelope.walk_decrypt(keys)
+}
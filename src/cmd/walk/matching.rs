@@ -41,7 +41,17 @@ impl CommandArgs {
             obscure_types.push(ObscureType::Compressed);
         }
 
-        let digests = envelope.nodes_matching(target.as_ref(), &obscure_types);
+        let digests = walk_matching(&envelope, target.as_ref(), &obscure_types);
         super::output_digests(digests)
     }
 }
+
+/// Returns the digests of the nodes of `envelope` that are in `target`, if
+/// given, and are obscured in one of `obscure_types`, if any are given.
+pub fn walk_matching(
+    envelope: &Envelope,
+    target: Option<&HashSet<Digest>>,
+    obscure_types: &[ObscureType],
+) -> HashSet<Digest> {
+    envelope.nodes_matching(target, obscure_types)
+}
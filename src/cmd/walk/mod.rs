@@ -10,7 +10,9 @@ use anyhow::Result;
 use bc_envelope::prelude::*;
 use clap::{Args, Subcommand};
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, digests_json, is_json_output, json_string,
+};
 
 /// Walk an envelope's nodes.
 #[derive(Debug, Args)]
@@ -37,7 +39,7 @@ enum WalkCommands {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
@@ -68,7 +70,8 @@ impl crate::Exec for CommandArgs {
                 // Default: output all digests
                 let envelope = self.read_envelope()?;
                 let target = parse_target_digests(&self.target)?;
-                let digests = envelope.nodes_matching(target.as_ref(), &[]);
+                let digests =
+                    matching::walk_matching(&envelope, target.as_ref(), &[]);
                 output_digests(digests)
             }
         }
@@ -89,6 +92,9 @@ fn parse_target_digests(target: &[String]) -> Result<Option<HashSet<Digest>>> {
 }
 
 fn output_digests(digests: HashSet<Digest>) -> Result<String> {
+    if is_json_output() {
+        return json_string(&digests_json(digests));
+    }
     let mut ordered_digests = digests.iter().cloned().collect::<Vec<_>>();
     ordered_digests.sort();
     let output = ordered_digests
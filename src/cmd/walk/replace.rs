@@ -26,7 +26,17 @@ impl CommandArgs {
         })?;
 
         let replacement = Envelope::from_ur_string(&self.replacement)?;
-        let result = envelope.walk_replace(&target_digests, &replacement)?;
+        let result = walk_replace(&envelope, &target_digests, &replacement)?;
         Ok(result.ur_string())
     }
 }
+
+/// Replaces the nodes of `envelope` whose digests are in `target` with
+/// `replacement`.
+pub fn walk_replace(
+    envelope: &Envelope,
+    target: &HashSet<Digest>,
+    replacement: &Envelope,
+) -> Result<Envelope> {
+    Ok(envelope.walk_replace(target, replacement)?)
+}
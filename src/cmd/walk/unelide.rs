@@ -19,7 +19,12 @@ impl CommandArgs {
             unelide_envelopes.push(env);
         }
 
-        let result = envelope.walk_unelide(&unelide_envelopes);
+        let result = walk_unelide(&envelope, &unelide_envelopes);
         Ok(result.ur_string())
     }
 }
+
+/// Restores the elided nodes of `envelope` that match one of `envelopes`.
+pub fn walk_unelide(envelope: &Envelope, envelopes: &[Envelope]) -> Envelope {
+    envelope.walk_unelide(envelopes)
+}
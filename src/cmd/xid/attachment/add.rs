@@ -1,9 +1,12 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike, read_envelope,
+    EnvelopeArgs, EnvelopeArgsLike,
+    attachment::create::attachment_create,
+    read_command_envelope, read_envelope,
     xid::{
         OutputOptions, ReadWritePasswordArgs, SigningArgs, VerifyArgs,
         XIDDocumentReadable, xid_document_to_ur_string,
@@ -51,7 +54,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -64,34 +67,17 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        // Add the attachment using the Attachable trait
-        if let Some(attachment_str) = &self.attachment {
-            // Use pre-made attachment - need to add it via its components
-            let attachment_envelope = read_envelope(Some(attachment_str))?;
-            attachment_envelope.validate_attachment()?;
-
-            // Extract components and add
-            let payload = attachment_envelope.attachment_payload()?;
-            let vendor = attachment_envelope.attachment_vendor()?;
-            let conforms_to =
-                attachment_envelope.attachment_conforms_to().ok().flatten();
-
-            xid_document.add_attachment(
-                payload,
-                &vendor,
-                conforms_to.as_deref(),
-            );
+        let attachment = if let Some(attachment_str) = &self.attachment {
+            read_envelope(Some(attachment_str))?
         } else {
-            // Build from components
-            let vendor = self.vendor.as_ref().unwrap();
-            let payload_envelope = read_envelope(self.payload.as_deref())?;
-
-            xid_document.add_attachment(
-                payload_envelope,
-                vendor,
+            let payload = read_command_envelope(self.payload.as_deref())?;
+            attachment_create(
+                &payload,
+                self.vendor.as_ref().unwrap(),
                 self.conforms_to.as_deref(),
-            );
-        }
+            )
+        };
+        xid_attachment_add(&mut xid_document, &attachment)?;
 
         let signing_options = self
             .signing_args
@@ -106,3 +92,17 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Adds `attachment` to `xid_document` after checking that it is a
+/// well-formed attachment.
+pub fn xid_attachment_add(
+    xid_document: &mut XIDDocument,
+    attachment: &Envelope,
+) -> Result<()> {
+    attachment.validate_attachment()?;
+    let payload = attachment.attachment_payload()?;
+    let vendor = attachment.attachment_vendor()?;
+    let conforms_to = attachment.attachment_conforms_to().ok().flatten();
+    xid_document.add_attachment(payload, &vendor, conforms_to.as_deref());
+    Ok(())
+}
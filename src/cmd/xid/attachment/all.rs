@@ -1,8 +1,12 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, attachment::all::attachment_all,
+    xid::XIDDocumentReadable,
+};
 
 /// Get all attachments from a XID document.
 #[derive(Debug, Args)]
@@ -13,7 +17,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -21,9 +25,7 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        let attachments = envelope.attachments()?;
+        let attachments = xid_attachment_all(&xid_document)?;
         let result = attachments
             .iter()
             .map(|a| a.ur_string())
@@ -32,3 +34,8 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the attachments of `xid_document`.
+pub fn xid_attachment_all(xid_document: &XIDDocument) -> Result<Vec<Envelope>> {
+    attachment_all(&xid_document.clone().into())
+}
@@ -1,8 +1,10 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use super::all::xid_attachment_all;
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, xid::XIDDocumentReadable};
 
 /// Get the attachment at the specified index from a XID document.
 #[derive(Debug, Args)]
@@ -16,7 +18,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -24,12 +26,19 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        let attachments = envelope.attachments()?;
-        let attachment = attachments.get(self.index).ok_or_else(|| {
-            anyhow::anyhow!("Index {} out of range", self.index)
-        })?;
-        Ok(attachment.ur_string())
+        Ok(xid_attachment_at(&xid_document, self.index)?.ur_string())
     }
 }
+
+/// Returns the attachment of `xid_document` at `index`, failing with
+/// [`Error::NotFound`] if there is none.
+pub fn xid_attachment_at(
+    xid_document: &XIDDocument,
+    index: usize,
+) -> Result<Envelope> {
+    let attachments = xid_attachment_all(xid_document)?;
+    let attachment = attachments.get(index).ok_or_else(|| {
+        Error::NotFound(format!("Index {} out of range", index))
+    })?;
+    Ok(attachment.clone())
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
-use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, attachment::count::attachment_count,
+    xid::XIDDocumentReadable,
+};
 
 /// Get the count of attachments in a XID document.
 #[derive(Debug, Args)]
@@ -13,7 +16,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -21,8 +24,11 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        Ok(envelope.attachments()?.len().to_string())
+        Ok(xid_attachment_count(&xid_document)?.to_string())
     }
 }
+
+/// Returns the number of attachments on `xid_document`.
+pub fn xid_attachment_count(xid_document: &XIDDocument) -> Result<usize> {
+    attachment_count(&xid_document.clone().into())
+}
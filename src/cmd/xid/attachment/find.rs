@@ -1,8 +1,12 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, attachment::find::attachment_find,
+    xid::XIDDocumentReadable,
+};
 
 /// Find attachments in a XID document by vendor and/or conforms-to.
 #[derive(Debug, Args)]
@@ -21,7 +25,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -29,13 +33,11 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        let attachments = envelope.attachments_with_vendor_and_conforms_to(
+        let attachments = xid_attachment_find(
+            &xid_document,
             self.vendor.as_deref(),
             self.conforms_to.as_deref(),
         )?;
-
         let result = attachments
             .iter()
             .map(|a| a.ur_string())
@@ -44,3 +46,14 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the attachments of `xid_document` matching `vendor` and
+/// `conforms_to` where given, failing with [`crate::Error::NotFound`] if there
+/// are none.
+pub fn xid_attachment_find(
+    xid_document: &XIDDocument,
+    vendor: Option<&str>,
+    conforms_to: Option<&str>,
+) -> Result<Vec<Envelope>> {
+    attachment_find(&xid_document.clone().into(), vendor, conforms_to)
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike, read_envelope,
+    EnvelopeArgs, EnvelopeArgsLike, Error, read_envelope,
     xid::{
         OutputOptions, ReadWritePasswordArgs, SigningArgs, VerifyArgs,
         XIDDocumentReadable, xid_document_to_ur_string,
@@ -37,7 +38,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -50,15 +51,8 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        // Read the attachment to remove and get its digest
-        let attachment_to_remove = read_envelope(Some(&self.attachment))?;
-        attachment_to_remove.validate_attachment()?;
-        let digest = attachment_to_remove.digest();
-
-        // Remove from XID document using the Attachable trait
-        xid_document
-            .remove_attachment(digest)
-            .ok_or_else(|| anyhow::anyhow!("Attachment not found"))?;
+        let attachment = read_envelope(Some(&self.attachment))?;
+        xid_attachment_remove(&mut xid_document, &attachment)?;
 
         let signing_options = self
             .signing_args
@@ -73,3 +67,16 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Removes `attachment` from `xid_document`, failing with
+/// [`Error::NotFound`] if the document doesn't have it.
+pub fn xid_attachment_remove(
+    xid_document: &mut XIDDocument,
+    attachment: &Envelope,
+) -> Result<()> {
+    attachment.validate_attachment()?;
+    xid_document
+        .remove_attachment(attachment.digest())
+        .ok_or_else(|| Error::NotFound("Attachment not found".to_string()))?;
+    Ok(())
+}
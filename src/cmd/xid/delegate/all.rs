@@ -1,5 +1,5 @@
 use anyhow::Result;
-use bc_envelope::known_values;
+use bc_envelope::{Envelope, known_values};
 use bc_ur::prelude::*;
 use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
@@ -15,7 +15,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -23,14 +23,22 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let delegate_assertions =
-            envelope.assertions_with_predicate(known_values::DELEGATE);
-        let delegates = delegate_assertions
+        let delegates = xid_delegate_all(&envelope)?
             .iter()
-            .map(|delegate| delegate.try_object().unwrap().ur_string())
+            .map(|delegate| delegate.ur_string())
             .collect::<Vec<_>>()
             .join("\n");
         Ok(delegates)
     }
 }
+
+/// Returns the delegates of the XID document in `envelope`, in the order
+/// they appear in the envelope.
+pub fn xid_delegate_all(envelope: &Envelope) -> Result<Vec<Envelope>> {
+    XIDDocument::from_envelope(envelope, None, XIDVerifySignature::None)?; // Validation only
+    envelope
+        .assertions_with_predicate(known_values::DELEGATE)
+        .iter()
+        .map(|delegate| Ok(delegate.try_object()?))
+        .collect()
+}
@@ -1,10 +1,10 @@
-use anyhow::{Result, anyhow};
-use bc_envelope::known_values;
+use anyhow::Result;
+use bc_envelope::Envelope;
 use bc_ur::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use super::all::xid_delegate_all;
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, xid::XIDDocumentReadable};
 
 /// Retrieve the XID document's delegate at the specified index.
 #[derive(Debug, Args)]
@@ -18,7 +18,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -26,13 +26,16 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let delegate_assertions =
-            envelope.assertions_with_predicate(known_values::DELEGATE);
-        let delegate_assertion = delegate_assertions
-            .get(self.index)
-            .ok_or_else(|| anyhow!("Index out of bounds"))?;
-        let delegate = delegate_assertion.try_object()?;
-        Ok(delegate.ur_string())
+        Ok(xid_delegate_at(&envelope, self.index)?.ur_string())
     }
 }
+
+/// Returns the delegate of the XID document in `envelope` at `index`,
+/// failing with [`Error::NotFound`] if there is none.
+pub fn xid_delegate_at(envelope: &Envelope, index: usize) -> Result<Envelope> {
+    let delegates = xid_delegate_all(envelope)?;
+    let delegate = delegates
+        .get(index)
+        .ok_or_else(|| Error::NotFound("Index out of bounds".to_string()))?;
+    Ok(delegate.clone())
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
@@ -12,7 +13,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -20,6 +21,11 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        Ok(xid_document.delegates().len().to_string())
+        Ok(xid_delegate_count(&xid_document).to_string())
     }
 }
+
+/// Returns the number of delegates in `xid_document`.
+pub fn xid_delegate_count(xid_document: &XIDDocument) -> usize {
+    xid_document.delegates().len()
+}
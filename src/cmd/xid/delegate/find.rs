@@ -1,10 +1,10 @@
-use anyhow::Result;
-use bc_envelope::EnvelopeEncodable;
+use anyhow::{Result, bail};
+use bc_envelope::{Envelope, EnvelopeEncodable};
 use bc_ur::prelude::*;
 use bc_xid::XIDDocument;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, xid::XIDDocumentReadable};
 
 /// Find a delegate in the XID document.
 #[derive(Debug, Args)]
@@ -18,22 +18,27 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let target_xid_document =
-            XIDDocument::from_ur_string(self.delegate.as_str())?;
+        let delegate = XIDDocument::from_ur_string(self.delegate.as_str())?;
         let xid_document = self.read_xid_document()?;
-        if let Some(delegate) =
-            xid_document.find_delegate_by_xid(&target_xid_document)
-        {
-            Ok(delegate.to_envelope().ur_string())
-        } else {
-            Ok("".to_string())
-        }
+        Ok(xid_delegate_find(&xid_document, &delegate)?.ur_string())
     }
 }
+
+/// Returns the envelope of `delegate` in `xid_document`, failing with
+/// [`Error::NotFound`] if the document doesn't have it.
+pub fn xid_delegate_find(
+    xid_document: &XIDDocument,
+    delegate: &XIDDocument,
+) -> Result<Envelope> {
+    let Some(delegate) = xid_document.find_delegate_by_xid(delegate) else {
+        bail!(Error::NotFound("No such delegate".to_string()));
+    };
+    Ok(delegate.to_envelope())
+}
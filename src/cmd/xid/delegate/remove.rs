@@ -24,20 +24,28 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let target_xid_document =
-            XIDDocument::from_ur_string(self.delegate.as_str())?;
+        let delegate = XIDDocument::from_ur_string(self.delegate.as_str())?;
         let mut xid_document = self.read_xid_document()?;
-        xid_document.remove_delegate(&target_xid_document)?;
+        xid_delegate_remove(&mut xid_document, &delegate)?;
         xid_document_to_unsigned_envelope_ur_string(
             xid_document,
             &self.output_opts,
         )
     }
 }
+
+/// Removes `delegate` from `xid_document`.
+pub fn xid_delegate_remove(
+    xid_document: &mut XIDDocument,
+    delegate: &XIDDocument,
+) -> Result<()> {
+    xid_document.remove_delegate(delegate)?;
+    Ok(())
+}
@@ -35,29 +35,36 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        let replacement_xid_document =
-            XIDDocument::from_ur_string(self.delegate.as_str())?;
-        let mut delegate = Delegate::new(&replacement_xid_document);
-
+        let delegate = XIDDocument::from_ur_string(self.delegate.as_str())?;
         let mut xid_document = self.read_xid_document()?;
-        xid_document.take_delegate(&delegate);
-        if self.permissions.is_empty() {
-            bail!("At least one permission must be granted to the delegate.");
-        }
-
-        add_delegate_permissions(&mut delegate, &self.permissions);
-        xid_document.add_delegate(delegate)?;
-
+        xid_delegate_update(&mut xid_document, &delegate, &self.permissions)?;
         xid_document_to_unsigned_envelope_ur_string(
             xid_document,
             &self.output_opts,
         )
     }
 }
+
+/// Replaces `delegate` in `xid_document`, allowing it `permissions`, which
+/// must not be empty. The delegate is added if it isn't already present.
+pub fn xid_delegate_update(
+    xid_document: &mut XIDDocument,
+    delegate: &XIDDocument,
+    permissions: &[XIDPrivilege],
+) -> Result<()> {
+    if permissions.is_empty() {
+        bail!("At least one permission must be granted to the delegate.");
+    }
+    let mut delegate = Delegate::new(delegate);
+    xid_document.take_delegate(&delegate);
+    add_delegate_permissions(&mut delegate, permissions);
+    xid_document.add_delegate(delegate)?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
@@ -37,7 +38,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -50,15 +51,8 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        let edge_envelope = read_envelope(Some(&self.edge))?;
-
-        // Validate edge structure
-        edge_envelope
-            .validate_edge()
-            .map_err(|e| anyhow::anyhow!("Invalid edge envelope: {}", e))?;
-
-        // Add edge using the Edgeable trait
-        xid_document.add_edge(edge_envelope);
+        let edge = read_envelope(Some(&self.edge))?;
+        xid_edge_add(&mut xid_document, &edge)?;
 
         let signing_options = self
             .signing_args
@@ -73,3 +67,15 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Adds `edge` to `xid_document` after checking that it is a well-formed
+/// edge.
+pub fn xid_edge_add(
+    xid_document: &mut XIDDocument,
+    edge: &Envelope,
+) -> Result<()> {
+    edge.validate_edge()
+        .map_err(|e| anyhow::anyhow!("Invalid edge envelope: {}", e))?;
+    xid_document.add_edge(edge.clone());
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
@@ -13,7 +14,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -21,9 +22,7 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        let edges = envelope.edges()?;
+        let edges = xid_edge_all(&xid_document)?;
         let result = edges
             .iter()
             .map(|e| e.ur_string())
@@ -32,3 +31,9 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Returns the edges of `xid_document`.
+pub fn xid_edge_all(xid_document: &XIDDocument) -> Result<Vec<Envelope>> {
+    let envelope: Envelope = xid_document.clone().into();
+    Ok(envelope.edges()?)
+}
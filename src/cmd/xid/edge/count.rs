@@ -1,7 +1,8 @@
 use anyhow::Result;
-use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
+use super::all::xid_edge_all;
 use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
 
 /// Get the count of edges in a XID document.
@@ -13,7 +14,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -21,8 +22,11 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        Ok(envelope.edges()?.len().to_string())
+        Ok(xid_edge_count(&xid_document)?.to_string())
     }
 }
+
+/// Returns the number of edges on `xid_document`.
+pub fn xid_edge_count(xid_document: &XIDDocument) -> Result<usize> {
+    Ok(xid_edge_all(xid_document)?.len())
+}
@@ -1,9 +1,11 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike, read_envelope, xid::XIDDocumentReadable,
+    EnvelopeArgs, EnvelopeArgsLike, Error, read_envelope,
+    xid::XIDDocumentReadable,
 };
 
 /// Find edges in a XID document by criteria.
@@ -34,7 +36,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -42,36 +44,16 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        let envelope: Envelope = xid_document.into();
-
-        let is_a_env = self
-            .is_a
-            .as_deref()
-            .map(|s| read_envelope(Some(s)))
-            .transpose()?;
-        let source_env = self
-            .source
-            .as_deref()
-            .map(|s| read_envelope(Some(s)))
-            .transpose()?;
-        let target_env = self
-            .target
-            .as_deref()
-            .map(|s| read_envelope(Some(s)))
-            .transpose()?;
-        let subject_env = self
-            .subject
-            .as_deref()
-            .map(|s| read_envelope(Some(s)))
-            .transpose()?;
-
-        let matching = envelope.edges_matching(
-            is_a_env.as_ref(),
-            source_env.as_ref(),
-            target_env.as_ref(),
-            subject_env.as_ref(),
-        )?;
-
+        let read = |s: &Option<String>| {
+            s.as_deref().map(|s| read_envelope(Some(s))).transpose()
+        };
+        let options = EdgeFindOptions {
+            is_a: read(&self.is_a)?,
+            source: read(&self.source)?,
+            target: read(&self.target)?,
+            subject: read(&self.subject)?,
+        };
+        let matching = xid_edge_find(&xid_document, &options)?;
         let result = matching
             .iter()
             .map(|e| e.ur_string())
@@ -80,3 +62,35 @@ impl crate::Exec for CommandArgs {
         Ok(result)
     }
 }
+
+/// Options for [`xid_edge_find`]. Each field that is set must match.
+#[derive(Debug, Clone, Default)]
+pub struct EdgeFindOptions {
+    /// The edge's `isA` value.
+    pub is_a: Option<Envelope>,
+    /// The edge's source.
+    pub source: Option<Envelope>,
+    /// The edge's target.
+    pub target: Option<Envelope>,
+    /// The edge's subject.
+    pub subject: Option<Envelope>,
+}
+
+/// Returns the edges of `xid_document` matching `options`, failing with
+/// [`Error::NotFound`] if there are none.
+pub fn xid_edge_find(
+    xid_document: &XIDDocument,
+    options: &EdgeFindOptions,
+) -> Result<Vec<Envelope>> {
+    let envelope: Envelope = xid_document.clone().into();
+    let matching = envelope.edges_matching(
+        options.is_a.as_ref(),
+        options.source.as_ref(),
+        options.target.as_ref(),
+        options.subject.as_ref(),
+    )?;
+    if matching.is_empty() {
+        bail!(Error::NotFound("No matching edge".to_string()));
+    }
+    Ok(matching)
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use bc_envelope::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike, read_envelope,
+    EnvelopeArgs, EnvelopeArgsLike, Error, read_envelope,
     xid::{
         OutputOptions, ReadWritePasswordArgs, SigningArgs, VerifyArgs,
         XIDDocumentReadable, xid_document_to_ur_string,
@@ -36,7 +37,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -49,14 +50,8 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        // Read the edge to remove and get its digest
-        let edge_to_remove = read_envelope(Some(&self.edge))?;
-        let digest = edge_to_remove.digest();
-
-        // Remove from XID document using the Edgeable trait
-        xid_document
-            .remove_edge(digest)
-            .ok_or_else(|| anyhow::anyhow!("Edge not found"))?;
+        let edge = read_envelope(Some(&self.edge))?;
+        xid_edge_remove(&mut xid_document, &edge)?;
 
         let signing_options = self
             .signing_args
@@ -71,3 +66,15 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Removes `edge` from `xid_document`, failing with [`Error::NotFound`] if
+/// the document doesn't have it.
+pub fn xid_edge_remove(
+    xid_document: &mut XIDDocument,
+    edge: &Envelope,
+) -> Result<()> {
+    xid_document
+        .remove_edge(edge.digest())
+        .ok_or_else(|| Error::NotFound("Edge not found".to_string()))?;
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_components::{Digest, DigestProvider};
 use bc_envelope::{
     Envelope,
@@ -65,7 +65,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -108,45 +108,12 @@ impl CommandArgs {
         // Read the original envelope
         let envelope = self.read_envelope()?;
 
-        // Verify signature if requested
-        if self.verify_args.verify_signature() == XIDVerifySignature::Inception
-        {
-            // Parse just to verify
-            let _ = XIDDocument::from_envelope(
-                &envelope,
-                None,
-                XIDVerifySignature::Inception,
-            )?;
-        }
-
-        // Collect digests to elide
-        let mut digests_to_elide: HashSet<Digest> = HashSet::new();
-
-        // Get the inner XID document
-        // A signed envelope has its subject wrapped: { XID [...] } [ 'signed':
-        // Signature ]
-        let inner = if envelope.subject().is_wrapped() {
-            envelope.subject().try_unwrap()?
-        } else {
-            envelope.clone()
-        };
-
-        // Find privateKey assertions to elide
-        if private_opts == PrivateOptions::Elide {
-            collect_private_key_digests(&inner, &mut digests_to_elide)?;
-        }
-
-        // Find provenanceGenerator assertions to elide
-        if generator_opts == GeneratorOptions::Elide {
-            collect_generator_digests(&inner, &mut digests_to_elide)?;
-        }
-
-        // Elide the collected digests from the original envelope
-        let elided = if digests_to_elide.is_empty() {
-            envelope
-        } else {
-            envelope.elide_removing_set(&digests_to_elide)
-        };
+        let elided = xid_export_elided(
+            &envelope,
+            private_opts,
+            generator_opts,
+            self.verify_args.verify_signature(),
+        )?;
 
         // Return as XID UR
         Ok(UR::new("xid", elided.to_cbor())?.string())
@@ -176,6 +143,67 @@ impl CommandArgs {
     }
 }
 
+/// Elides the secrets of a XID document without reconstructing it, so any
+/// signature on the document remains valid.
+///
+/// Only [`PrivateOptions::Include`] and [`PrivateOptions::Elide`], and the
+/// matching [`GeneratorOptions`], can be applied this way; the other options
+/// change the document's content and require it to be re-signed.
+pub fn xid_export_elided(
+    envelope: &Envelope,
+    private_opts: PrivateOptions,
+    generator_opts: GeneratorOptions,
+    verify: XIDVerifySignature,
+) -> Result<Envelope> {
+    if !matches!(private_opts, PrivateOptions::Include | PrivateOptions::Elide)
+        || !matches!(
+            generator_opts,
+            GeneratorOptions::Include | GeneratorOptions::Elide
+        )
+    {
+        bail!("only include and elide can be applied without re-signing");
+    }
+
+    // Verify signature if requested
+    if verify == XIDVerifySignature::Inception {
+        // Parse just to verify
+        let _ = XIDDocument::from_envelope(
+            envelope,
+            None,
+            XIDVerifySignature::Inception,
+        )?;
+    }
+
+    // Collect digests to elide
+    let mut digests_to_elide: HashSet<Digest> = HashSet::new();
+
+    // Get the inner XID document
+    // A signed envelope has its subject wrapped: { XID [...] } [ 'signed':
+    // Signature ]
+    let inner = if envelope.subject().is_wrapped() {
+        envelope.subject().try_unwrap()?
+    } else {
+        envelope.clone()
+    };
+
+    // Find privateKey assertions to elide
+    if private_opts == PrivateOptions::Elide {
+        collect_private_key_digests(&inner, &mut digests_to_elide)?;
+    }
+
+    // Find provenanceGenerator assertions to elide
+    if generator_opts == GeneratorOptions::Elide {
+        collect_generator_digests(&inner, &mut digests_to_elide)?;
+    }
+
+    // Elide the collected digests from the original envelope
+    Ok(if digests_to_elide.is_empty() {
+        envelope.clone()
+    } else {
+        envelope.elide_removing_set(&digests_to_elide)
+    })
+}
+
 /// Collect digests of privateKey assertions from all keys in the XID document.
 fn collect_private_key_digests(
     envelope: &Envelope,
@@ -1,11 +1,13 @@
 use anyhow::Result;
 use bc_components::{XID, XIDProvider};
+use bc_envelope::Envelope;
 use bc_ur::prelude::*;
-use bc_xid::XIDVerifySignature;
+use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::{Args, ValueEnum};
+use serde_json::json;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, is_json_output, json_string,
     xid::{VerifyArgs, XIDDocumentReadable, xid_from_document_envelope},
 };
 
@@ -30,7 +32,7 @@ pub enum IDFormat {
 #[group(skip)]
 pub struct CommandArgs {
     /// Output format of the XID identifier. May be repeated to output multiple
-    /// formats.
+    /// formats. Ignored with `--json`, which outputs every format.
     #[arg(long, default_value = "ur", num_args = 1)]
     format: Vec<IDFormat>,
 
@@ -42,23 +44,34 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        if self.verify_args.verify_signature() == XIDVerifySignature::None {
-            let envelope = self.read_envelope()?;
-            let xid = xid_from_document_envelope(&envelope)?;
-            Ok(format_xid(&xid, &self.format))
-        } else {
-            let xid_document = self.read_xid_document_with_verify(
-                self.verify_args.verify_signature(),
-            )?;
-            Ok(format_xid(&xid_document.xid(), &self.format))
+        let envelope = self.read_envelope()?;
+        let xid = xid_id(&envelope, self.verify_args.verify_signature())?;
+        if is_json_output() {
+            return json_string(&json!({
+                "ur": xid.ur_string(),
+                "hex": xid.to_string(),
+                "bytewords": xid.bytewords_identifier(true),
+                "bytemoji": xid.bytemoji_identifier(true),
+            }));
         }
+        Ok(format_xid(&xid, &self.format))
+    }
+}
+
+/// Returns the XID of the XID document in `envelope`, after verifying the
+/// document's signature as `verify` requests.
+pub fn xid_id(envelope: &Envelope, verify: XIDVerifySignature) -> Result<XID> {
+    if verify == XIDVerifySignature::None {
+        xid_from_document_envelope(envelope)
+    } else {
+        Ok(XIDDocument::from_envelope(envelope, None, verify)?.xid())
     }
 }
 
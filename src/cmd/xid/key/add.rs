@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bc_components::URI;
-use bc_xid::Key;
+use bc_xid::{Key, XIDDocument};
 use clap::Args;
 
 use crate::{
@@ -49,7 +49,7 @@ impl KeyArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -57,38 +57,19 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let keys = self.read_key()?;
-
         let mut xid_document = self
             .read_xid_document_with_password_and_verify(
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
 
-        let mut key = match &keys {
-            InputKey::PrivateBase(private_key_base) => {
-                Key::new_with_private_key_base(private_key_base.clone())
-            }
-            InputKey::Public(public_keys) => Key::new(public_keys.clone()),
-            InputKey::PrivateKeys(private_keys) => {
-                let public_keys = private_keys.public_keys()?;
-                Key::new_with_private_keys(private_keys.clone(), public_keys)
-            }
-            InputKey::PrivateAndPublicKeys(private_keys, public_keys) => {
-                Key::new_with_private_keys(
-                    private_keys.clone(),
-                    public_keys.clone(),
-                )
-            }
-        };
-
-        update_key(
-            &mut key,
+        xid_key_add(
+            &mut xid_document,
+            &keys,
             self.nickname(),
             self.endpoints(),
             self.permissions(),
-        );
-
-        xid_document.add_key(key)?;
+        )?;
 
         let signing_options = self
             .signing_args
@@ -108,3 +89,33 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Adds `key` to `xid_document` with the given nickname, endpoints, and
+/// permissions, each of which is left at its default if empty.
+pub fn xid_key_add(
+    xid_document: &mut XIDDocument,
+    key: &InputKey,
+    nickname: &str,
+    endpoints: &[URI],
+    permissions: &[XIDPrivilege],
+) -> Result<()> {
+    let mut key = match key {
+        InputKey::PrivateBase(private_key_base) => {
+            Key::new_with_private_key_base(private_key_base.clone())
+        }
+        InputKey::Public(public_keys) => Key::new(public_keys.clone()),
+        InputKey::PrivateKeys(private_keys) => {
+            let public_keys = private_keys.public_keys()?;
+            Key::new_with_private_keys(private_keys.clone(), public_keys)
+        }
+        InputKey::PrivateAndPublicKeys(private_keys, public_keys) => {
+            Key::new_with_private_keys(
+                private_keys.clone(),
+                public_keys.clone(),
+            )
+        }
+    };
+    update_key(&mut key, nickname, endpoints, permissions);
+    xid_document.add_key(key)?;
+    Ok(())
+}
@@ -1,11 +1,16 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
-use bc_envelope::known_values;
-use bc_ur::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use bc_envelope::{known_values, prelude::*};
+use bc_xid::{
+    HasNickname, HasPermissions, Key, Privilege, XIDDocument,
+    XIDVerifySignature,
+};
 use clap::Args;
+use serde_json::{Value, json};
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, is_json_output, json_string,
     xid::{
         ReadPasswordArgs, VerifyArgs, XIDDocumentReadable, get_private_key_ur,
         xid_document_envelope, xid_from_document_envelope,
@@ -22,6 +27,8 @@ pub struct CommandArgs {
     /// For encrypted keys without --password, returns the encrypted envelope
     /// UR. For encrypted keys with --password, returns the decrypted
     /// PrivateKeys UR.
+    ///
+    /// With `--json`, the private key is added to each key object instead.
     #[arg(long)]
     private: bool,
 
@@ -36,13 +43,67 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
+impl CommandArgs {
+    /// Returns the key assertion objects of the XID document, verifying the
+    /// document's signature as requested.
+    fn read_key_envelopes(&self) -> Result<Vec<Envelope>> {
+        let envelope = self.read_envelope()?;
+        xid_key_all(&envelope, self.verify_args.verify_signature())
+    }
+
+    fn key_json(&self, key: &Key) -> Result<Value> {
+        let nickname = key.nickname();
+        let mut endpoints =
+            key.endpoints().iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        endpoints.sort();
+        let mut value = json!({
+            "public_keys": key.public_keys().ur_string(),
+            "nickname": (!nickname.is_empty()).then_some(nickname),
+            "permissions": {
+                "allow": privileges_json(key.allow()),
+                "deny": privileges_json(key.deny()),
+            },
+            "endpoints": endpoints,
+            "has_private_key": key.has_private_keys()
+                || key.has_encrypted_private_keys(),
+            "private_key_encrypted": key.has_encrypted_private_keys(),
+        });
+        if self.private {
+            value["private_key"] =
+                get_private_key_ur(key, &self.password_args)?.into();
+        }
+        Ok(value)
+    }
+}
+
+/// Privileges as their lowercase names, in registry order.
+fn privileges_json(privileges: &HashSet<Privilege>) -> Value {
+    let mut privileges =
+        privileges.iter().map(KnownValue::from).collect::<Vec<_>>();
+    privileges.sort_by_key(|p| p.value());
+    Value::Array(
+        privileges
+            .iter()
+            .map(|p| p.name().to_lowercase().into())
+            .collect(),
+    )
+}
+
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
+        if is_json_output() {
+            let keys = self
+                .read_key_envelopes()?
+                .iter()
+                .map(|envelope| self.key_json(&Key::try_from(envelope)?))
+                .collect::<Result<Vec<_>>>()?;
+            return json_string(&Value::Array(keys));
+        }
         if self.private {
             // Return private keys
             let xid_document = self.read_xid_document_with_verify(
@@ -56,28 +117,32 @@ impl crate::Exec for CommandArgs {
                 .join("\n");
             Ok(keys)
         } else {
-            let envelope = self.read_envelope()?;
-            let inner_envelope = if self.verify_args.verify_signature()
-                == XIDVerifySignature::None
-            {
-                xid_from_document_envelope(&envelope)?;
-                xid_document_envelope(&envelope)?
-            } else {
-                XIDDocument::from_envelope(
-                    &envelope,
-                    None,
-                    self.verify_args.verify_signature(),
-                )?;
-                xid_document_envelope(&envelope)?
-            };
-            let key_assertions =
-                inner_envelope.assertions_with_predicate(known_values::KEY);
-            let keys = key_assertions
+            let keys = self
+                .read_key_envelopes()?
                 .iter()
-                .map(|key| Ok(key.try_object()?.ur_string()))
-                .collect::<Result<Vec<String>>>()?
+                .map(|key| key.ur_string())
+                .collect::<Vec<String>>()
                 .join("\n");
             Ok(keys)
         }
     }
 }
+
+/// Returns the keys of the XID document in `envelope`, in the order they
+/// appear in the envelope, after verifying the document's signature as
+/// `verify` requests.
+pub fn xid_key_all(
+    envelope: &Envelope,
+    verify: XIDVerifySignature,
+) -> Result<Vec<Envelope>> {
+    if verify == XIDVerifySignature::None {
+        xid_from_document_envelope(envelope)?;
+    } else {
+        XIDDocument::from_envelope(envelope, None, verify)?;
+    }
+    xid_document_envelope(envelope)?
+        .assertions_with_predicate(known_values::KEY)
+        .iter()
+        .map(|key| Ok(key.try_object()?))
+        .collect()
+}
@@ -1,14 +1,14 @@
-use anyhow::{Result, anyhow};
-use bc_envelope::known_values;
+use anyhow::Result;
+use bc_envelope::Envelope;
 use bc_ur::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use bc_xid::XIDVerifySignature;
 use clap::Args;
 
+use super::all::xid_key_all;
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, Error,
     xid::{
         ReadPasswordArgs, VerifyArgs, XIDDocumentReadable, get_private_key_ur,
-        xid_document_envelope, xid_from_document_envelope,
     },
 };
 
@@ -39,7 +39,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -55,30 +55,34 @@ impl crate::Exec for CommandArgs {
                 .keys()
                 .iter()
                 .nth(self.index)
-                .ok_or_else(|| anyhow!("Index out of bounds"))?;
+                .ok_or_else(|| {
+                    Error::NotFound("Index out of bounds".to_string())
+                })?;
             get_private_key_ur(key, &self.password_args)
         } else {
             let envelope = self.read_envelope()?;
-            let inner_envelope = if self.verify_args.verify_signature()
-                == XIDVerifySignature::None
-            {
-                xid_from_document_envelope(&envelope)?;
-                xid_document_envelope(&envelope)?
-            } else {
-                XIDDocument::from_envelope(
-                    &envelope,
-                    None,
-                    self.verify_args.verify_signature(),
-                )?;
-                xid_document_envelope(&envelope)?
-            };
-            let key_assertions =
-                inner_envelope.assertions_with_predicate(known_values::KEY);
-            let key_assertion = key_assertions
-                .get(self.index)
-                .ok_or_else(|| anyhow!("Index out of bounds"))?;
-            let key = key_assertion.try_object()?;
+            let key = xid_key_at(
+                &envelope,
+                self.index,
+                self.verify_args.verify_signature(),
+            )?;
             Ok(key.ur_string())
         }
     }
 }
+
+/// Returns the key of the XID document in `envelope` at `index`, after
+/// verifying the document's signature as `verify` requests.
+///
+/// Fails with [`Error::NotFound`] if there is no key at `index`.
+pub fn xid_key_at(
+    envelope: &Envelope,
+    index: usize,
+    verify: XIDVerifySignature,
+) -> Result<Envelope> {
+    let keys = xid_key_all(envelope, verify)?;
+    let key = keys
+        .get(index)
+        .ok_or_else(|| Error::NotFound("Index out of bounds".to_string()))?;
+    Ok(key.clone())
+}
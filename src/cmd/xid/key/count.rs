@@ -1,12 +1,12 @@
 use anyhow::Result;
+use bc_envelope::Envelope;
+use bc_xid::XIDVerifySignature;
 use clap::Args;
 
+use super::all::xid_key_all;
 use crate::{
     EnvelopeArgs, EnvelopeArgsLike,
-    xid::{
-        VerifyArgs, XIDDocumentReadable, xid_document_envelope,
-        xid_from_document_envelope,
-    },
+    xid::{VerifyArgs, XIDDocumentReadable},
 };
 
 /// Print the count of the XID document's keys.
@@ -21,28 +21,25 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        if self.verify_args.verify_signature()
-            == bc_xid::XIDVerifySignature::None
-        {
-            let envelope = self.read_envelope()?;
-            xid_from_document_envelope(&envelope)?;
-            let envelope = xid_document_envelope(&envelope)?;
-            return Ok(envelope
-                .assertions_with_predicate(bc_envelope::known_values::KEY)
-                .len()
-                .to_string());
-        }
-
-        let xid_document = self.read_xid_document_with_verify(
-            self.verify_args.verify_signature(),
-        )?;
-        Ok(xid_document.keys().len().to_string())
+        let envelope = self.read_envelope()?;
+        let count =
+            xid_key_count(&envelope, self.verify_args.verify_signature())?;
+        Ok(count.to_string())
     }
 }
+
+/// Returns the number of keys in the XID document in `envelope`, after
+/// verifying the document's signature as `verify` requests.
+pub fn xid_key_count(
+    envelope: &Envelope,
+    verify: XIDVerifySignature,
+) -> Result<usize> {
+    Ok(xid_key_all(envelope, verify)?.len())
+}
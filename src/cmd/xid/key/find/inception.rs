@@ -1,10 +1,11 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::EnvelopeEncodable;
 use bc_ur::prelude::*;
+use bc_xid::{Key, XIDDocument};
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, Error,
     xid::{
         ReadPasswordArgs, VerifyArgs, XIDDocumentReadable, get_private_key_ur,
     },
@@ -34,7 +35,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -44,15 +45,21 @@ impl crate::Exec for CommandArgs {
         let xid_document = self.read_xid_document_with_verify(
             self.verify_args.verify_signature(),
         )?;
-        let result = if let Some(inception_key) = xid_document.inception_key() {
-            if self.private {
-                get_private_key_ur(inception_key, &self.password_args)?
-            } else {
-                inception_key.to_envelope().ur_string()
-            }
+        let inception_key = xid_key_find_inception(&xid_document)?;
+        let result = if self.private {
+            get_private_key_ur(&inception_key, &self.password_args)?
         } else {
-            "".to_string()
+            inception_key.to_envelope().ur_string()
         };
         Ok(result)
     }
 }
+
+/// Returns the inception key of `xid_document`, failing with
+/// [`Error::NotFound`] if it has none.
+pub fn xid_key_find_inception(xid_document: &XIDDocument) -> Result<Key> {
+    let Some(inception_key) = xid_document.inception_key() else {
+        bail!(Error::NotFound("No inception key".to_string()));
+    };
+    Ok(inception_key.clone())
+}
@@ -1,11 +1,11 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::EnvelopeEncodable;
 use bc_ur::prelude::*;
-use bc_xid::HasNickname;
+use bc_xid::{HasNickname, Key, XIDDocument};
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, Error,
     xid::{
         ReadPasswordArgs, VerifyArgs, XIDDocumentReadable, get_private_key_ur,
     },
@@ -37,7 +37,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -47,31 +47,38 @@ impl crate::Exec for CommandArgs {
         let xid_document = self.read_xid_document_with_verify(
             self.verify_args.verify_signature(),
         )?;
-
-        let keys = xid_document.keys();
-        if self.private {
+        let keys = xid_key_find_name(&xid_document, &self.name)?;
+        let result = if self.private {
             // Return private keys
-            let result = keys
-                .iter()
-                .filter(|key| key.nickname() == self.name)
+            keys.iter()
                 .map(|key| get_private_key_ur(key, &self.password_args))
                 .collect::<Result<Vec<String>>>()?
-                .join("\n");
-            Ok(result)
+                .join("\n")
         } else {
             // Return public keys (original behavior)
-            let result = keys
-                .iter()
-                .filter_map(|key| {
-                    if key.nickname() == self.name {
-                        Some(key.to_envelope().ur_string())
-                    } else {
-                        None
-                    }
-                })
+            keys.iter()
+                .map(|key| key.to_envelope().ur_string())
                 .collect::<Vec<String>>()
-                .join("\n");
-            Ok(result)
-        }
+                .join("\n")
+        };
+        Ok(result)
+    }
+}
+
+/// Returns the keys of `xid_document` named `name`, failing with
+/// [`Error::NotFound`] if there are none.
+pub fn xid_key_find_name(
+    xid_document: &XIDDocument,
+    name: &str,
+) -> Result<Vec<Key>> {
+    let keys = xid_document
+        .keys()
+        .iter()
+        .filter(|key| key.nickname() == name)
+        .cloned()
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        bail!(Error::NotFound("No key with that name".to_string()));
     }
+    Ok(keys)
 }
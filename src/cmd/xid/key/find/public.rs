@@ -1,10 +1,12 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use bc_components::PublicKeys;
 use bc_envelope::EnvelopeEncodable;
 use bc_ur::prelude::*;
+use bc_xid::{Key, XIDDocument};
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, Error,
     xid::{
         ReadPasswordArgs, VerifyArgs, XIDDocumentReadable, get_private_key_ur,
         read_public_key,
@@ -39,7 +41,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -50,31 +52,38 @@ impl crate::Exec for CommandArgs {
         let xid_document = self.read_xid_document_with_verify(
             self.verify_args.verify_signature(),
         )?;
-
-        let keys = xid_document.keys();
-        if self.private {
+        let keys = xid_key_find_public(&xid_document, &public_keys)?;
+        let result = if self.private {
             // Return private keys
-            let result = keys
-                .iter()
-                .filter(|key| key.public_keys() == &public_keys)
+            keys.iter()
                 .map(|key| get_private_key_ur(key, &self.password_args))
                 .collect::<Result<Vec<String>>>()?
-                .join("\n");
-            Ok(result)
+                .join("\n")
         } else {
             // Return public keys (original behavior)
-            let result = keys
-                .iter()
-                .filter_map(|key| {
-                    if key.public_keys() == &public_keys {
-                        Some(key.to_envelope().ur_string())
-                    } else {
-                        None
-                    }
-                })
+            keys.iter()
+                .map(|key| key.to_envelope().ur_string())
                 .collect::<Vec<String>>()
-                .join("\n");
-            Ok(result)
-        }
+                .join("\n")
+        };
+        Ok(result)
+    }
+}
+
+/// Returns the keys of `xid_document` with `public_keys`, failing with
+/// [`Error::NotFound`] if there are none.
+pub fn xid_key_find_public(
+    xid_document: &XIDDocument,
+    public_keys: &PublicKeys,
+) -> Result<Vec<Key>> {
+    let keys = xid_document
+        .keys()
+        .iter()
+        .filter(|key| key.public_keys() == public_keys)
+        .cloned()
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        bail!(Error::NotFound("No key with that public keys".to_string()));
     }
+    Ok(keys)
 }
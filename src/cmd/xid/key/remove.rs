@@ -1,4 +1,6 @@
 use anyhow::Result;
+use bc_components::PublicKeys;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
@@ -39,7 +41,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -52,7 +54,8 @@ impl crate::Exec for CommandArgs {
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
-        xid_document.remove_key(&public_keys)?;
+
+        xid_key_remove(&mut xid_document, &public_keys)?;
 
         let signing_options = self
             .signing_args
@@ -72,3 +75,12 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Removes the key with `public_keys` from `xid_document`.
+pub fn xid_key_remove(
+    xid_document: &mut XIDDocument,
+    public_keys: &PublicKeys,
+) -> Result<()> {
+    xid_document.remove_key(public_keys)?;
+    Ok(())
+}
@@ -1,9 +1,10 @@
-use anyhow::{Result, anyhow};
-use bc_components::URI;
+use anyhow::Result;
+use bc_components::{PublicKeys, URI};
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
-    EnvelopeArgs, EnvelopeArgsLike,
+    EnvelopeArgs, EnvelopeArgsLike, Error,
     xid::{
         GeneratorOutputArgs, KeyArgs, KeyArgsLike, OutputOptions,
         PrivateOptions, ReadWritePasswordArgs, SigningArgs, VerifyArgs,
@@ -48,7 +49,7 @@ impl KeyArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -56,26 +57,19 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let public_keys = self.read_public_key()?;
-
         let mut xid_document = self
             .read_xid_document_with_password_and_verify(
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
 
-        let mut key = xid_document
-            .find_key_by_public_keys(&public_keys)
-            .cloned()
-            .ok_or_else(|| anyhow!("Key not found"))?;
-
-        xid_document.take_key(&key);
-        update_key(
-            &mut key,
+        xid_key_update(
+            &mut xid_document,
+            &public_keys,
             self.nickname(),
             self.endpoints(),
             self.permissions(),
-        );
-        xid_document.add_key(key)?;
+        )?;
 
         let signing_options = self
             .signing_args
@@ -95,3 +89,24 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Updates the key of `xid_document` with `public_keys`, setting whichever of
+/// the nickname, endpoints, and permissions are not empty.
+///
+/// Fails with [`Error::NotFound`] if the document has no such key.
+pub fn xid_key_update(
+    xid_document: &mut XIDDocument,
+    public_keys: &PublicKeys,
+    nickname: &str,
+    endpoints: &[URI],
+    permissions: &[XIDPrivilege],
+) -> Result<()> {
+    let mut key = xid_document
+        .find_key_by_public_keys(public_keys)
+        .cloned()
+        .ok_or_else(|| Error::NotFound("Key not found".to_string()))?;
+    xid_document.take_key(&key);
+    update_key(&mut key, nickname, endpoints, permissions);
+    xid_document.add_key(key)?;
+    Ok(())
+}
@@ -6,7 +6,8 @@ use crate::{
     EnvelopeArgs, EnvelopeArgsLike,
     xid::{
         OutputOptions, ReadWritePasswordArgs, SigningArgs, VerifyArgs,
-        XIDDocumentReadable, xid_document_to_ur_string,
+        XIDDocumentReadable, resolution::add::xid_resolution_add,
+        xid_document_to_ur_string,
     },
 };
 
@@ -35,7 +36,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -47,7 +48,7 @@ impl crate::Exec for CommandArgs {
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
-        xid_document.add_resolution_method(self.method.clone());
+        xid_resolution_add(&mut xid_document, self.method.clone());
 
         let signing_options = self
             .signing_args
@@ -1,10 +1,9 @@
 use anyhow::Result;
-use bc_components::URI;
-use bc_envelope::known_values;
-use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike, xid::resolution::all::xid_resolution_all,
+};
 
 /// Retrieve all the XID document's resolution methods.
 #[derive(Debug, Args)]
@@ -15,23 +14,17 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let method_assertions =
-            envelope.assertions_with_predicate(known_values::DEREFERENCE_VIA);
-        let methods: Result<Vec<String>> = method_assertions
+        let methods = xid_resolution_all(&envelope)?
             .iter()
-            .map(|method| {
-                let uri = URI::try_from(method.try_object()?.try_leaf()?)?;
-                Ok(uri.to_string())
-            })
-            .collect();
-        let methods = methods?.join("\n");
+            .map(|uri| uri.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
         Ok(methods)
     }
 }
@@ -1,10 +1,10 @@
-use anyhow::{Result, anyhow};
-use bc_components::URI;
-use bc_envelope::known_values;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use anyhow::Result;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike,
+    xid::{XIDDocumentReadable, resolution::at::xid_resolution_at},
+};
 
 /// Retrieve the resolution method at the given index
 #[derive(Debug, Args)]
@@ -18,7 +18,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -26,13 +26,6 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let method_assertions =
-            envelope.assertions_with_predicate(known_values::DEREFERENCE_VIA);
-        let method_assertion = method_assertions
-            .get(self.index)
-            .ok_or_else(|| anyhow!("Index out of bounds"))?;
-        let uri: URI = method_assertion.try_object()?.try_leaf()?.try_into()?;
-        Ok(uri.to_string())
+        Ok(xid_resolution_at(&envelope, self.index)?.to_string())
     }
 }
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{
+    EnvelopeArgs, EnvelopeArgsLike,
+    xid::{XIDDocumentReadable, resolution::count::xid_resolution_count},
+};
 
 /// Print the count of the XID document's resolution methods.
 #[derive(Debug, Args)]
@@ -12,7 +15,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -20,6 +23,6 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-        Ok(xid_document.resolution_methods().len().to_string())
+        Ok(xid_resolution_count(&xid_document).to_string())
     }
 }
@@ -1,6 +1,6 @@
-mod attachment;
-mod delegate;
-mod edge;
+pub mod attachment;
+pub mod delegate;
+pub mod edge;
 pub mod export;
 pub mod generator_options;
 pub use generator_options::*;
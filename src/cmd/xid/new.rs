@@ -2,7 +2,7 @@ use anyhow::Result;
 use bc_components::URI;
 use bc_xid::{XIDDocument, XIDGenesisMarkOptions, XIDInceptionKeyOptions};
 use clap::Args;
-use dcbor::Date;
+use dcbor::{CBOR, Date};
 
 use super::{
     InputKey, KeyArgs, KeyArgsLike, OutputOptions, PrivateOptions,
@@ -81,30 +81,9 @@ impl crate::Exec for CommandArgs {
             None
         };
 
-        // Determine genesis mark options based on generator_opts
-        let genesis_mark_opts = match self.generator_opts {
-            GeneratorOptions::Omit => XIDGenesisMarkOptions::None,
-            GeneratorOptions::Include | GeneratorOptions::Encrypt => {
-                // Parse optional date parameter
-                let date = if let Some(date_str) = &self.date {
-                    Some(Date::from_string(date_str)?)
-                } else {
-                    None
-                };
-
-                // Parse optional info parameter - convert any UR to CBOR
-                let info = if let Some(info_str) = &self.info {
-                    Some(parse_ur_to_cbor(info_str, self.ur_tag)?)
-                } else {
-                    None
-                };
-
-                // Use a random seed to initialize the provenance mark generator
-                let mut rng = bc_rand::SecureRandomNumberGenerator;
-                let random_seed =
-                    provenance_mark::ProvenanceSeed::new_using(&mut rng);
-                XIDGenesisMarkOptions::Seed(random_seed, None, date, info)
-            }
+        let provenance = match self.generator_opts {
+            GeneratorOptions::Omit => false,
+            GeneratorOptions::Include | GeneratorOptions::Encrypt => true,
             GeneratorOptions::Elide => {
                 anyhow::bail!(
                     "Elide is not allowed for 'xid new'. Use 'omit' (the default) to create without a provenance mark, or 'include'/'encrypt' to create with one."
@@ -112,47 +91,29 @@ impl crate::Exec for CommandArgs {
             }
         };
 
-        let mut xid_document = match &keys {
-            InputKey::PrivateBase(private_key_base) => XIDDocument::new(
-                XIDInceptionKeyOptions::PrivateKeyBase(
-                    private_key_base.clone(),
-                ),
-                genesis_mark_opts,
-            ),
-            InputKey::Public(public_keys) => XIDDocument::new(
-                XIDInceptionKeyOptions::PublicKeys(public_keys.clone()),
-                genesis_mark_opts,
-            ),
-            InputKey::PrivateKeys(private_keys) => {
-                let public_keys = private_keys.public_keys()?;
-                XIDDocument::new(
-                    XIDInceptionKeyOptions::PublicAndPrivateKeys(
-                        public_keys,
-                        private_keys.clone(),
-                    ),
-                    genesis_mark_opts,
-                )
-            }
-            InputKey::PrivateAndPublicKeys(private_keys, public_keys) => {
-                XIDDocument::new(
-                    XIDInceptionKeyOptions::PublicAndPrivateKeys(
-                        public_keys.clone(),
-                        private_keys.clone(),
-                    ),
-                    genesis_mark_opts,
-                )
-            }
+        // Parse optional date parameter
+        let date = if let Some(date_str) = &self.date {
+            Some(Date::from_string(date_str)?)
+        } else {
+            None
+        };
+
+        // Parse optional info parameter - convert any UR to CBOR
+        let info = if let Some(info_str) = &self.info {
+            Some(parse_ur_to_cbor(info_str, self.ur_tag)?)
+        } else {
+            None
         };
 
-        let mut key = xid_document.keys().iter().next().unwrap().clone();
-        xid_document.take_key(&key);
-        update_key(
-            &mut key,
-            self.nickname(),
-            self.endpoints(),
-            self.permissions(),
-        );
-        xid_document.add_key(key)?;
+        let options = XIDNewOptions {
+            nickname: self.nickname().to_string(),
+            endpoints: self.endpoints().to_vec(),
+            permissions: self.permissions().to_vec(),
+            provenance,
+            date,
+            info,
+        };
+        let xid_document = xid_new(&keys, &options)?;
 
         let signing_options = self
             .signing_args
@@ -170,3 +131,79 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Options for [`xid_new`].
+#[derive(Debug, Clone, Default)]
+pub struct XIDNewOptions {
+    /// A user-assigned name for the inception key.
+    pub nickname: String,
+    /// Endpoints of the inception key.
+    pub endpoints: Vec<URI>,
+    /// Permissions of the inception key. If empty, the key is allowed
+    /// everything.
+    pub permissions: Vec<XIDPrivilege>,
+    /// Whether to create a genesis provenance mark and its generator.
+    pub provenance: bool,
+    /// The date of the genesis mark. If `None`, the current date is used.
+    pub date: Option<Date>,
+    /// Additional info to attach to the genesis mark.
+    pub info: Option<CBOR>,
+}
+
+/// Returns a new XID document whose inception key is `key`.
+pub fn xid_new(key: &InputKey, options: &XIDNewOptions) -> Result<XIDDocument> {
+    let genesis_mark_opts = if options.provenance {
+        // Use a random seed to initialize the provenance mark generator
+        let mut rng = bc_rand::SecureRandomNumberGenerator;
+        let random_seed = provenance_mark::ProvenanceSeed::new_using(&mut rng);
+        XIDGenesisMarkOptions::Seed(
+            random_seed,
+            None,
+            options.date,
+            options.info.clone(),
+        )
+    } else {
+        XIDGenesisMarkOptions::None
+    };
+
+    let mut xid_document = match key {
+        InputKey::PrivateBase(private_key_base) => XIDDocument::new(
+            XIDInceptionKeyOptions::PrivateKeyBase(private_key_base.clone()),
+            genesis_mark_opts,
+        ),
+        InputKey::Public(public_keys) => XIDDocument::new(
+            XIDInceptionKeyOptions::PublicKeys(public_keys.clone()),
+            genesis_mark_opts,
+        ),
+        InputKey::PrivateKeys(private_keys) => {
+            let public_keys = private_keys.public_keys()?;
+            XIDDocument::new(
+                XIDInceptionKeyOptions::PublicAndPrivateKeys(
+                    public_keys,
+                    private_keys.clone(),
+                ),
+                genesis_mark_opts,
+            )
+        }
+        InputKey::PrivateAndPublicKeys(private_keys, public_keys) => {
+            XIDDocument::new(
+                XIDInceptionKeyOptions::PublicAndPrivateKeys(
+                    public_keys.clone(),
+                    private_keys.clone(),
+                ),
+                genesis_mark_opts,
+            )
+        }
+    };
+
+    let mut key = xid_document.keys().iter().next().unwrap().clone();
+    xid_document.take_key(&key);
+    update_key(
+        &mut key,
+        &options.nickname,
+        &options.endpoints,
+        &options.permissions,
+    );
+    xid_document.add_key(key)?;
+    Ok(xid_document)
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
-use bc_envelope::known_values;
+use bc_envelope::{Envelope, known_values};
 use bc_ur::prelude::*;
-use bc_xid::XIDVerifySignature;
+use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
 use provenance_mark::ProvenanceMark;
 
@@ -28,40 +28,55 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
-        if self.verify_args.verify_signature() == XIDVerifySignature::None {
-            let envelope = self.read_envelope()?;
-            xid_from_document_envelope(&envelope)?;
-            let envelope = xid_document_envelope(&envelope)?;
-            if let Some(provenance_assertion) = envelope
-                .optional_assertion_with_predicate(known_values::PROVENANCE)?
-            {
-                let provenance_envelope = provenance_assertion.try_object()?;
-                let provenance_mark: ProvenanceMark =
-                    provenance_envelope.extract_subject()?;
-                return Ok(provenance_mark.ur_string());
-            }
-            return Ok(String::new());
-        }
-
-        let xid_document = self.read_xid_document_with_password_and_verify(
-            &self.password_args.read,
-            self.verify_args.verify_signature(),
-        )?;
-
-        // Extract the provenance mark if it exists
-        if let Some(provenance_mark) = xid_document.provenance() {
-            // Return the ProvenanceMark as a UR string
-            Ok(provenance_mark.ur_string())
+        let envelope = self.read_envelope()?;
+        let verify = self.verify_args.verify_signature();
+        let password = if verify == XIDVerifySignature::None {
+            None
         } else {
-            // No provenance mark - return empty string
-            Ok(String::new())
-        }
+            self.password_args
+                .read
+                .read_password("Decryption password:")?
+        };
+        let provenance_mark =
+            xid_provenance_get(&envelope, verify, password.as_deref())?;
+        Ok(provenance_mark
+            .map(|provenance_mark| provenance_mark.ur_string())
+            .unwrap_or_default())
+    }
+}
+
+/// Returns the provenance mark of the XID document in `envelope`, if it has
+/// one, after verifying the document's signature as `verify` requests.
+///
+/// `password` decrypts the document's private keys when it is verified.
+pub fn xid_provenance_get(
+    envelope: &Envelope,
+    verify: XIDVerifySignature,
+    password: Option<&str>,
+) -> Result<Option<ProvenanceMark>> {
+    if verify == XIDVerifySignature::None {
+        xid_from_document_envelope(envelope)?;
+        let envelope = xid_document_envelope(envelope)?;
+        let Some(provenance_assertion) = envelope
+            .optional_assertion_with_predicate(known_values::PROVENANCE)?
+        else {
+            return Ok(None);
+        };
+        let provenance_envelope = provenance_assertion.try_object()?;
+        return Ok(Some(provenance_envelope.extract_subject()?));
     }
+
+    let xid_document = XIDDocument::from_envelope(
+        envelope,
+        password.map(|s| s.as_bytes()),
+        verify,
+    )?;
+    Ok(xid_document.provenance().cloned())
 }
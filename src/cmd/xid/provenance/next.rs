@@ -1,8 +1,9 @@
 use anyhow::Result;
 use bc_envelope::Envelope;
 use bc_ur::prelude::*;
+use bc_xid::XIDDocument;
 use clap::Args;
-use dcbor::Date;
+use dcbor::{CBOR, Date};
 use provenance_mark::ProvenanceMarkGenerator;
 
 use crate::{
@@ -57,7 +58,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -84,31 +85,33 @@ impl crate::Exec for CommandArgs {
             None
         };
 
-        // Determine if we should use embedded or provided generator
-        if let Some(generator_str) = &self.external_generator {
-            // User provided a generator - use
-            // next_provenance_mark_with_provided_generator
+        // Parse optional external generator
+        let mut external_generator = if let Some(generator_str) =
+            &self.external_generator
+        {
             let generator_envelope = Envelope::from_ur_string(generator_str)?;
-            let mut generator: ProvenanceMarkGenerator =
+            let generator: ProvenanceMarkGenerator =
                 generator_envelope.try_into()?;
-
-            xid_document.next_provenance_mark_with_provided_generator(
-                &mut generator,
-                date,
-                info,
-            )?;
+            Some(generator)
         } else {
-            // No generator provided - use
-            // next_provenance_mark_with_embedded_generator
-            let password = self
-                .password_args
+            None
+        };
+
+        // The embedded generator may be encrypted.
+        let password = if external_generator.is_none() {
+            self.password_args
                 .read
                 .read_password("Decryption password:")?
-                .map(|s| s.as_bytes().to_vec());
-            xid_document.next_provenance_mark_with_embedded_generator(
-                password, date, info,
-            )?;
-        }
+        } else {
+            None
+        };
+        xid_provenance_next(
+            &mut xid_document,
+            external_generator.as_mut(),
+            password.as_deref(),
+            date,
+            info,
+        )?;
 
         // Convert updated document to UR string
         let signing_options = self
@@ -131,3 +134,29 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Advances the provenance mark of `xid_document` to the next state, with an
+/// optional date and info for the new mark.
+///
+/// The mark is generated by `external_generator` if one is given, and
+/// otherwise by the document's embedded generator, which `password` decrypts
+/// if it is encrypted.
+pub fn xid_provenance_next(
+    xid_document: &mut XIDDocument,
+    external_generator: Option<&mut ProvenanceMarkGenerator>,
+    password: Option<&str>,
+    date: Option<Date>,
+    info: Option<CBOR>,
+) -> Result<()> {
+    if let Some(generator) = external_generator {
+        xid_document.next_provenance_mark_with_provided_generator(
+            generator, date, info,
+        )?;
+    } else {
+        let password = password.map(|s| s.as_bytes().to_vec());
+        xid_document.next_provenance_mark_with_embedded_generator(
+            password, date, info,
+        )?;
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bc_components::URI;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
@@ -35,7 +36,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -49,7 +50,7 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        xid_document.add_resolution_method(uri);
+        xid_resolution_add(&mut xid_document, uri);
 
         let signing_options = self
             .signing_args
@@ -64,3 +65,8 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Adds the resolution method `uri` to `xid_document`.
+pub fn xid_resolution_add(xid_document: &mut XIDDocument, uri: URI) {
+    xid_document.add_resolution_method(uri);
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bc_components::URI;
-use bc_envelope::known_values;
+use bc_envelope::{Envelope, known_values};
 use clap::Args;
 
 use crate::{
@@ -17,25 +17,29 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        xid_from_document_envelope(&envelope)?;
-        let envelope = xid_document_envelope(&envelope)?;
-        let resolution_assertions =
-            envelope.assertions_with_predicate(known_values::DEREFERENCE_VIA);
-        let methods = resolution_assertions
+        let methods = xid_resolution_all(&envelope)?
             .iter()
-            .map(|assertion| {
-                let uri: URI =
-                    assertion.try_object()?.try_leaf()?.try_into()?;
-                Ok(uri.to_string())
-            })
-            .collect::<Result<Vec<String>>>()?
+            .map(|uri| uri.to_string())
+            .collect::<Vec<_>>()
             .join("\n");
         Ok(methods)
     }
 }
+
+/// Returns the resolution methods of the XID document in `envelope`, in the
+/// order they appear in the envelope.
+pub fn xid_resolution_all(envelope: &Envelope) -> Result<Vec<URI>> {
+    xid_from_document_envelope(envelope)?;
+    let envelope = xid_document_envelope(envelope)?;
+    envelope
+        .assertions_with_predicate(known_values::DEREFERENCE_VIA)
+        .iter()
+        .map(|assertion| Ok(assertion.try_object()?.try_leaf()?.try_into()?))
+        .collect()
+}
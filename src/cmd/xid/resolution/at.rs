@@ -1,10 +1,10 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use bc_components::URI;
-use bc_envelope::known_values;
-use bc_xid::{XIDDocument, XIDVerifySignature};
+use bc_envelope::Envelope;
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use super::all::xid_resolution_all;
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Retrieve the XID Document's resolution method at the given index.
 #[derive(Debug, Args)]
@@ -18,19 +18,22 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let resolution_assertions =
-            envelope.assertions_with_predicate(known_values::DEREFERENCE_VIA);
-        let assertion = resolution_assertions
-            .get(self.index)
-            .ok_or_else(|| anyhow!("Index out of bounds"))?;
-        let uri: URI = assertion.try_object()?.try_leaf()?.try_into()?;
-        Ok(uri.to_string())
+        Ok(xid_resolution_at(&envelope, self.index)?.to_string())
     }
 }
+
+/// Returns the resolution method of the XID document in `envelope` at
+/// `index`, failing with [`Error::NotFound`] if there is none.
+pub fn xid_resolution_at(envelope: &Envelope, index: usize) -> Result<URI> {
+    let uris = xid_resolution_all(envelope)?;
+    let uri = uris
+        .get(index)
+        .ok_or_else(|| Error::NotFound("Index out of bounds".to_string()))?;
+    Ok(uri.clone())
+}
@@ -1,5 +1,6 @@
 use anyhow::{Result, bail};
 use bc_components::URI;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
@@ -35,7 +36,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -49,9 +50,7 @@ impl crate::Exec for CommandArgs {
                 self.verify_args.verify_signature(),
             )?;
 
-        if xid_document.remove_resolution_method(&uri).is_none() {
-            bail!("Resolution method not found: {}", uri);
-        }
+        xid_resolution_remove(&mut xid_document, &uri)?;
 
         let signing_options = self
             .signing_args
@@ -66,3 +65,15 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Removes the resolution method `uri` from `xid_document`, failing if it
+/// has no such method.
+pub fn xid_resolution_remove(
+    xid_document: &mut XIDDocument,
+    uri: &URI,
+) -> Result<()> {
+    if xid_document.remove_resolution_method(uri).is_none() {
+        bail!("Resolution method not found: {}", uri);
+    }
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use anyhow::Result;
 use bc_components::URI;
-use bc_xid::{HasPermissions, Service};
+use bc_xid::{HasPermissions, Service, XIDDocument};
 use clap::Args;
 
-use super::service_args::{ServiceArgs, ServiceArgsLike};
+use super::service_args::{ServiceArgs, ServiceArgsLike, ServiceOptions};
 use crate::{
     EnvelopeArgs, EnvelopeArgsLike,
     xid::{
@@ -52,7 +52,7 @@ impl ServiceArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -60,42 +60,13 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let uri = self.read_uri()?;
-
         let mut xid_document = self
             .read_xid_document_with_password_and_verify(
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
 
-        let mut service = Service::new(uri);
-
-        if let Some(name) = self.name()
-            && !name.is_empty()
-        {
-            service.set_name(name)?;
-        }
-
-        if let Some(capability) = self.capability()
-            && !capability.is_empty()
-        {
-            service.set_capability(capability);
-        }
-        for privilege in self.permissions() {
-            service.add_allow((*privilege).into());
-        }
-
-        for key in self.keys() {
-            xid_document.check_contains_key(key)?;
-            service.add_key(key)?;
-        }
-
-        for delegate in self.delegates() {
-            xid_document.check_contains_delegate(delegate)?;
-            service.add_delegate(delegate)?;
-        }
-
-        xid_document.check_service_consistency(&service)?;
-        xid_document.add_service(service)?;
+        xid_service_add(&mut xid_document, uri, &self.service_options())?;
 
         let signing_options = self
             .signing_args
@@ -110,3 +81,37 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Adds a service at `uri` to `xid_document` with the properties given in
+/// `options`. The service's keys and delegates must be in the document.
+pub fn xid_service_add(
+    xid_document: &mut XIDDocument,
+    uri: URI,
+    options: &ServiceOptions,
+) -> Result<()> {
+    let mut service = Service::new(uri);
+    if let Some(name) = &options.name
+        && !name.is_empty()
+    {
+        service.set_name(name)?;
+    }
+    if let Some(capability) = &options.capability
+        && !capability.is_empty()
+    {
+        service.set_capability(capability);
+    }
+    for privilege in &options.permissions {
+        service.add_allow((*privilege).into());
+    }
+    for key in &options.keys {
+        xid_document.check_contains_key(key)?;
+        service.add_key(key)?;
+    }
+    for delegate in &options.delegates {
+        xid_document.check_contains_delegate(delegate)?;
+        service.add_delegate(delegate)?;
+    }
+    xid_document.check_service_consistency(&service)?;
+    xid_document.add_service(service)?;
+    Ok(())
+}
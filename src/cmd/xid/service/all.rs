@@ -1,5 +1,5 @@
 use anyhow::Result;
-use bc_envelope::known_values;
+use bc_envelope::{Envelope, known_values};
 use bc_ur::prelude::*;
 use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
@@ -15,20 +15,28 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let service_assertions =
-            envelope.assertions_with_predicate(known_values::SERVICE);
-        let services = service_assertions
+        let services = xid_service_all(&envelope)?
             .iter()
-            .map(|key| key.try_object().unwrap().ur_string())
+            .map(|service| service.ur_string())
             .collect::<Vec<String>>()
             .join("\n");
         Ok(services)
     }
 }
+
+/// Returns the services of the XID document in `envelope`, in the order
+/// they appear in the envelope.
+pub fn xid_service_all(envelope: &Envelope) -> Result<Vec<Envelope>> {
+    XIDDocument::from_envelope(envelope, None, XIDVerifySignature::None)?; // Validation only
+    envelope
+        .assertions_with_predicate(known_values::SERVICE)
+        .iter()
+        .map(|service| Ok(service.try_object()?))
+        .collect()
+}
@@ -1,10 +1,10 @@
-use anyhow::{Result, anyhow};
-use bc_envelope::known_values;
+use anyhow::Result;
+use bc_envelope::Envelope;
 use bc_ur::prelude::*;
-use bc_xid::{XIDDocument, XIDVerifySignature};
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike};
+use super::all::xid_service_all;
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error};
 
 /// Retrieve the XID Document's service at the given index
 #[derive(Debug, Args)]
@@ -18,19 +18,22 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let envelope = self.read_envelope()?;
-        XIDDocument::from_envelope(&envelope, None, XIDVerifySignature::None)?; // Validation only
-        let service_assertions =
-            envelope.assertions_with_predicate(known_values::SERVICE);
-        let service_assertion = service_assertions
-            .get(self.index)
-            .ok_or_else(|| anyhow!("Index out of bounds"))?;
-        let service = service_assertion.try_object()?;
-        Ok(service.ur_string())
+        Ok(xid_service_at(&envelope, self.index)?.ur_string())
     }
 }
+
+/// Returns the service of the XID document in `envelope` at `index`,
+/// failing with [`Error::NotFound`] if there is none.
+pub fn xid_service_at(envelope: &Envelope, index: usize) -> Result<Envelope> {
+    let services = xid_service_all(envelope)?;
+    let service = services
+        .get(index)
+        .ok_or_else(|| Error::NotFound("Index out of bounds".to_string()))?;
+    Ok(service.clone())
+}
@@ -1,9 +1,10 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use bc_envelope::EnvelopeEncodable;
 use bc_ur::prelude::*;
+use bc_xid::{Service, XIDDocument};
 use clap::Args;
 
-use crate::{EnvelopeArgs, EnvelopeArgsLike, xid::XIDDocumentReadable};
+use crate::{EnvelopeArgs, EnvelopeArgsLike, Error, xid::XIDDocumentReadable};
 
 /// Find the XID document's services by assigned name. May return multiple
 /// services.
@@ -17,7 +18,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -25,19 +26,30 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let xid_document = self.read_xid_document()?;
-
-        let services = xid_document.services();
+        let services = xid_service_find_name(&xid_document, &self.name)?;
         let result = services
             .iter()
-            .filter_map(|service| {
-                if service.name() == self.name {
-                    Some(service.to_envelope().ur_string())
-                } else {
-                    None
-                }
-            })
+            .map(|service| service.to_envelope().ur_string())
             .collect::<Vec<String>>()
             .join("\n");
         Ok(result)
     }
 }
+
+/// Returns the services of `xid_document` named `name`, failing with
+/// [`Error::NotFound`] if there are none.
+pub fn xid_service_find_name(
+    xid_document: &XIDDocument,
+    name: &str,
+) -> Result<Vec<Service>> {
+    let services = xid_document
+        .services()
+        .iter()
+        .filter(|service| service.name() == name)
+        .cloned()
+        .collect::<Vec<_>>();
+    if services.is_empty() {
+        bail!(Error::NotFound("No service with that name".to_string()));
+    }
+    Ok(services)
+}
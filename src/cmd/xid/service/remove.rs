@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bc_components::URI;
+use bc_xid::XIDDocument;
 use clap::Args;
 
 use crate::{
@@ -35,7 +36,7 @@ pub struct CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -48,7 +49,8 @@ impl crate::Exec for CommandArgs {
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
-        xid_document.remove_service(&uri)?;
+
+        xid_service_remove(&mut xid_document, &uri)?;
 
         let signing_options = self
             .signing_args
@@ -63,3 +65,12 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Removes the service at `uri` from `xid_document`.
+pub fn xid_service_remove(
+    xid_document: &mut XIDDocument,
+    uri: &URI,
+) -> Result<()> {
+    xid_document.remove_service(uri)?;
+    Ok(())
+}
@@ -16,6 +16,35 @@ pub trait ServiceArgsLike {
     fn delegates(&self) -> &[XIDDocument];
 
     fn read_uri(&self) -> Result<URI> { read_uri(self.uri()) }
+
+    fn service_options(&self) -> ServiceOptions {
+        ServiceOptions {
+            name: self.name().map(str::to_string),
+            capability: self.capability().map(str::to_string),
+            permissions: self.permissions().to_vec(),
+            keys: self.keys().to_vec(),
+            delegates: self.delegates().to_vec(),
+        }
+    }
+}
+
+/// The properties of a service set by [`xid_service_add`] and
+/// [`xid_service_update`].
+///
+/// [`xid_service_add`]: super::add::xid_service_add
+/// [`xid_service_update`]: super::update::xid_service_update
+#[derive(Debug, Clone, Default)]
+pub struct ServiceOptions {
+    /// A user-assigned name for the service.
+    pub name: Option<String>,
+    /// The capability identifier of the service.
+    pub capability: Option<String>,
+    /// The permissions granted to the service.
+    pub permissions: Vec<XIDPrivilege>,
+    /// Keys of the XID document for use with the service.
+    pub keys: Vec<PublicKeys>,
+    /// Delegates of the XID document for the service.
+    pub delegates: Vec<XIDDocument>,
 }
 
 fn parse_public_keys(s: &str) -> Result<PublicKeys, String> {
@@ -1,9 +1,9 @@
 use anyhow::{Result, anyhow};
 use bc_components::URI;
-use bc_xid::HasPermissions;
+use bc_xid::{HasPermissions, XIDDocument};
 use clap::Args;
 
-use super::service_args::{ServiceArgs, ServiceArgsLike};
+use super::service_args::{ServiceArgs, ServiceArgsLike, ServiceOptions};
 use crate::{
     EnvelopeArgs, EnvelopeArgsLike,
     xid::{
@@ -53,7 +53,7 @@ impl ServiceArgsLike for CommandArgs {
 }
 
 impl EnvelopeArgsLike for CommandArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope_args.envelope() }
+    fn envelope_args(&self) -> &EnvelopeArgs { &self.envelope_args }
 }
 
 impl XIDDocumentReadable for CommandArgs {}
@@ -61,53 +61,13 @@ impl XIDDocumentReadable for CommandArgs {}
 impl crate::Exec for CommandArgs {
     fn exec(&self) -> Result<String> {
         let uri = self.read_uri()?;
-
         let mut xid_document = self
             .read_xid_document_with_password_and_verify(
                 &self.password_args.read,
                 self.verify_args.verify_signature(),
             )?;
 
-        let mut service = xid_document
-            .find_service_by_uri(&uri)
-            .cloned()
-            .ok_or_else(|| anyhow!("Service not found"))?;
-
-        xid_document.take_service(&uri);
-
-        if let Some(name) = self.name().filter(|n| !n.is_empty()) {
-            service.set_name(name)?;
-        }
-
-        if let Some(capability) = self.capability().filter(|c| !c.is_empty()) {
-            service.set_capability(capability);
-        }
-
-        if !self.permissions().is_empty() {
-            service.clear_all_permissions();
-            for privilege in self.permissions() {
-                service.add_allow((*privilege).into());
-            }
-        }
-
-        if !self.keys().is_empty() {
-            service.key_referenecs_mut().clear();
-            for key in self.keys() {
-                xid_document.check_contains_key(key)?;
-                service.add_key(key)?;
-            }
-        }
-
-        if !self.delegates().is_empty() {
-            service.delegate_references_mut().clear();
-            for delegate in self.delegates() {
-                xid_document.check_contains_delegate(delegate)?;
-                service.add_delegate(delegate)?;
-            }
-        }
-
-        xid_document.check_service_consistency(&service)?;
-        xid_document.add_service(service)?;
+        xid_service_update(&mut xid_document, &uri, &self.service_options())?;
 
         let signing_options = self
             .signing_args
@@ -122,3 +82,51 @@ impl crate::Exec for CommandArgs {
         )
     }
 }
+
+/// Updates the service at `uri` in `xid_document`, replacing whichever of
+/// its properties are given in `options`. The service's keys and delegates
+/// must be in the document.
+pub fn xid_service_update(
+    xid_document: &mut XIDDocument,
+    uri: &URI,
+    options: &ServiceOptions,
+) -> Result<()> {
+    let mut service = xid_document
+        .find_service_by_uri(uri)
+        .cloned()
+        .ok_or_else(|| anyhow!("Service not found"))?;
+    xid_document.take_service(uri);
+
+    if let Some(name) = options.name.as_deref().filter(|n| !n.is_empty()) {
+        service.set_name(name)?;
+    }
+    if let Some(capability) =
+        options.capability.as_deref().filter(|c| !c.is_empty())
+    {
+        service.set_capability(capability);
+    }
+    if !options.permissions.is_empty() {
+        service.clear_all_permissions();
+        for privilege in &options.permissions {
+            service.add_allow((*privilege).into());
+        }
+    }
+    if !options.keys.is_empty() {
+        service.key_referenecs_mut().clear();
+        for key in &options.keys {
+            xid_document.check_contains_key(key)?;
+            service.add_key(key)?;
+        }
+    }
+    if !options.delegates.is_empty() {
+        service.delegate_references_mut().clear();
+        for delegate in &options.delegates {
+            xid_document.check_contains_delegate(delegate)?;
+            service.add_delegate(delegate)?;
+        }
+    }
+
+    xid_document.check_service_consistency(&service)?;
+    xid_document.add_service(service)?;
+    Ok(())
+}
@@ -8,7 +8,7 @@ use bc_xid::{
 };
 
 use super::{OutputOptions, ReadPasswordArgs, WritePasswordArgs, XIDPrivilege};
-use crate::EnvelopeArgsLike;
+use crate::{EnvelopeArgsLike, read_command_line};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputKey {
@@ -23,9 +23,7 @@ pub fn read_key(key: Option<&str>) -> Result<InputKey> {
         key_str.to_string()
     } else {
         // Read from stdin
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        input.trim().to_string()
+        read_command_line()?.trim().to_string()
     };
 
     if key_string.is_empty() {
@@ -189,9 +187,7 @@ pub fn read_uri(uri: Option<&URI>) -> Result<URI> {
     let uri_string = if let Some(u) = uri {
         u.to_string()
     } else {
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        input
+        read_command_line()?
     };
     if uri_string.is_empty() {
         bail!("No URI provided");
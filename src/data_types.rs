@@ -1,22 +1,27 @@
 use std::str::FromStr;
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use bc_components::{ARID, Digest};
 use bc_envelope::prelude::*;
 use clap::ValueEnum;
 
+use crate::Error;
+
 #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DataType {
     /// ARID: Apparently Random Identifier (ur:arid)
     Arid,
 
+    /// Integer of any size, as a CBOR bignum (tags 2 and 3)
+    Bignum,
+
     /// Boolean (`true` or `false`)
     Bool,
 
-    /// CBOR data in hex
+    /// CBOR data in hex or diagnostic notation
     Cbor,
 
-    /// Binary byte string in hex
+    /// Binary byte string in hex, or `@PATH` to read it from a file
     Data,
 
     /// Date (ISO 8601)
@@ -28,15 +33,21 @@ pub enum DataType {
     /// Envelope (ur:envelope)
     Envelope,
 
+    /// Integer from -2^64 to 2^64-1, kept exact
+    Int,
+
     /// Known Value (number or string)
     Known,
 
-    /// Numeric value,
+    /// Numeric value; integers are kept exact
     Number,
 
     /// UTF-8 String
     String,
 
+    /// Unsigned integer from 0 to 2^64-1, kept exact
+    Uint,
+
     /// Unit Known Value (deliberate emptiness, no value)
     Unit,
 
@@ -66,15 +77,18 @@ pub fn parse_data_type_to_envelope(
     if let Some(s) = s {
         match data_type {
             DataType::Arid => parse_arid(s),
+            DataType::Bignum => parse_bignum(s),
             DataType::Bool => parse_boolean(s),
             DataType::Cbor => parse_cbor(s),
             DataType::Data => parse_data(s),
             DataType::Date => parse_date(s),
             DataType::Digest => parse_digest(s),
             DataType::Envelope => parse_envelope(s),
+            DataType::Int => parse_int(s),
             DataType::Known => parse_known_value(s),
             DataType::Number => parse_number(s),
             DataType::String => parse_string(s),
+            DataType::Uint => parse_uint(s),
             DataType::Unit => unreachable!(),
             DataType::Ur => parse_ur(s, ur_cbor_tag_value),
             DataType::Uri => parse_uri(s),
@@ -82,7 +96,7 @@ pub fn parse_data_type_to_envelope(
             DataType::Wrapped => parse_wrapped_envelope(s),
         }
     } else {
-        bail!("No value provided")
+        bail!(Error::MissingInput("No value provided".to_string()))
     }
 }
 
@@ -96,7 +110,7 @@ fn parse_arid(s: &str) -> Result<Envelope> {
     } else if let Ok(arid) = ARID::from_ur_string(s) {
         Ok(Envelope::new(arid))
     } else {
-        bail!("Invalid ARID")
+        bail!(Error::Parse("Invalid ARID".to_string()))
     }
 }
 
@@ -109,15 +123,37 @@ fn parse_boolean(s: &str) -> Result<Envelope> {
 }
 
 /// Parse a CBOR envelope from a string.
+///
+/// Accepts either hex or CBOR diagnostic notation. A string made only of hex
+/// digits is read as hex. Hex must already be deterministic (dCBOR);
+/// diagnostic notation is canonicalized as it is parsed.
 fn parse_cbor(s: &str) -> Result<Envelope> {
-    let cbor = CBOR::try_from_hex(s)?;
+    let cbor = if !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        CBOR::try_from_hex(s).map_err(|e| {
+            Error::Parse(format!("the hex is not valid dCBOR: {}", e))
+        })?
+    } else {
+        dcbor_parse::parse_dcbor_item(s).map_err(|e| {
+            Error::Parse(format!(
+                "invalid CBOR diagnostic notation: {}",
+                e.full_message(s)
+            ))
+        })?
+    };
     Ok(Envelope::new(cbor))
 }
 
 /// Parse a bytestring from a string.
+///
+/// Accepts either hex or `@PATH`, which reads the bytes from a file.
 fn parse_data(s: &str) -> Result<Envelope> {
-    let data = CBOR::to_byte_string(hex::decode(s)?);
-    Ok(Envelope::new(data))
+    let data = if let Some(path) = s.strip_prefix('@') {
+        std::fs::read(path)
+            .with_context(|| format!("could not read data from {}", path))?
+    } else {
+        hex::decode(s)?
+    };
+    Ok(Envelope::new(CBOR::to_byte_string(data)))
 }
 
 /// Parse a Date from a string.
@@ -149,18 +185,72 @@ fn parse_known_value(s: &str) -> Result<Envelope> {
             {
                 Ok(Envelope::new(known_value))
             } else {
-                bail!("Unknown known value")
+                bail!(Error::Parse("Unknown known value".to_string()))
             }
         })
     }
 }
 
 /// Parse a numeric value from a string.
+///
+/// Integers in the range of CBOR integers are encoded exactly; anything else
+/// is read as a float.
 fn parse_number(s: &str) -> Result<Envelope> {
+    if let Some(cbor) = s.parse::<i128>().ok().and_then(int_cbor) {
+        return Ok(Envelope::new(cbor));
+    }
     let number = s.parse::<f64>()?;
     Ok(Envelope::new(number))
 }
 
+/// Parse an integer from -2^64 to 2^64-1 from a string.
+fn parse_int(s: &str) -> Result<Envelope> {
+    let n = s.parse::<i128>().map_err(|_| {
+        Error::Parse(format!("invalid integer: {}", s))
+    })?;
+    match int_cbor(n) {
+        Some(cbor) => Ok(Envelope::new(cbor)),
+        None => bail!(Error::Parse(format!(
+            "{} is out of the range of int; use bignum",
+            s
+        ))),
+    }
+}
+
+/// Parse an unsigned integer from 0 to 2^64-1 from a string.
+fn parse_uint(s: &str) -> Result<Envelope> {
+    let n = s.parse::<u64>().map_err(|_| {
+        Error::Parse(format!("invalid unsigned integer: {}", s))
+    })?;
+    Ok(Envelope::new(n))
+}
+
+/// Returns the CBOR integer for `n`, if it is in the range of CBOR integers.
+fn int_cbor(n: i128) -> Option<CBOR> {
+    if n >= 0 {
+        u64::try_from(n).ok().map(CBOR::from)
+    } else {
+        u64::try_from(-1 - n).ok().map(|n| CBORCase::Negative(n).into())
+    }
+}
+
+/// Parse a decimal integer of any size from a string, encoded as a bignum.
+fn parse_bignum(s: &str) -> Result<Envelope> {
+    let n = s
+        .parse::<BigInt>()
+        .map_err(|_| Error::Parse(format!("invalid integer: {}", s)))?;
+    Ok(Envelope::new(CBOR::from(n)))
+}
+
+/// Returns the decimal value of a bignum or a CBOR integer.
+pub(crate) fn bignum_string(cbor: &CBOR) -> Result<String> {
+    let n = BigInt::try_from(cbor.clone()).map_err(|e| match e {
+        dcbor::Error::WrongType => Error::Parse("not a bignum".to_string()),
+        e => Error::Parse(e.to_string()),
+    })?;
+    Ok(n.to_string())
+}
+
 /// Parse a string from a string.
 fn parse_string(s: &str) -> Result<Envelope> { Ok(Envelope::new(s)) }
 
@@ -192,7 +282,7 @@ fn parse_ur(s: &str, cbor_tag_value: Option<u64>) -> Result<Envelope> {
             let content_cbor = CBOR::to_tagged_value(cbor_tag, cbor);
             Ok(Envelope::new(content_cbor))
         } else {
-            bail!("Unknown UR type")
+            bail!(Error::Parse("Unknown UR type".to_string()))
         }
     }
 }
@@ -249,7 +339,8 @@ pub fn parse_ur_to_cbor(s: &str, cbor_tag_value: Option<u64>) -> Result<CBOR> {
         Ok(CBOR::to_tagged_value(cbor_tag, cbor))
     } else {
         bail!(
-            "Unknown UR type: '{}'. Use --ur-tag to specify the CBOR tag value.",
+            "Unknown UR type: '{}'. Use --ur-tag to specify the CBOR tag \
+             value, or register the type with --tags.",
             ur.ur_type_str()
         )
     }
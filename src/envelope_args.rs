@@ -1,14 +1,76 @@
-use anyhow::Result;
+use std::{
+    io::{BufRead, Read},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result, bail};
 use bc_envelope::prelude::*;
-use clap::Args;
+use clap::{Args, ValueEnum};
+
+use crate::{
+    Error, has_envelope_input, read_command_envelope,
+    read_command_envelope_string, read_envelope, reject_envelope_argument,
+};
 
-use crate::read_envelope;
+/// The encoding of an envelope supplied as input.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum InputFormat {
+    /// Detect the encoding from the input.
+    #[default]
+    Auto,
+    /// UR text (ur:envelope or ur:xid).
+    Ur,
+    /// Binary tagged CBOR.
+    Cbor,
+    /// Tagged CBOR in hex.
+    Hex,
+}
 
 pub trait EnvelopeArgsLike {
-    fn envelope(&self) -> Option<&str>;
+    fn envelope_args(&self) -> &EnvelopeArgs;
+
+    fn envelope(&self) -> Option<&str> {
+        self.envelope_args().envelope.as_deref()
+    }
 
     fn read_envelope(&self) -> Result<Envelope> {
-        read_envelope(self.envelope())
+        let args = self.envelope_args();
+        if let Some(path) = &args.input {
+            reject_envelope_argument()?;
+            let data = std::fs::read(path).with_context(|| {
+                format!("could not read envelope from {}", path.display())
+            })?;
+            return envelope_from_data(&data, args.in_format);
+        }
+        match args.in_format {
+            InputFormat::Ur => read_command_envelope(self.envelope()),
+            InputFormat::Hex => {
+                envelope_from_hex(&read_command_envelope_string(
+                    self.envelope(),
+                )?)
+            }
+            InputFormat::Cbor => {
+                if self.envelope().is_some() || has_envelope_input() {
+                    bail!(Error::Usage(
+                        "binary CBOR input must be read from --in or stdin"
+                            .to_string()
+                    ));
+                }
+                envelope_from_data(&read_stdin_data()?, InputFormat::Cbor)
+            }
+            InputFormat::Auto => {
+                if self.envelope().is_none()
+                    && !has_envelope_input()
+                    && stdin_is_binary()?
+                {
+                    envelope_from_data(&read_stdin_data()?, InputFormat::Cbor)
+                } else {
+                    envelope_from_text(&read_command_envelope_string(
+                        self.envelope(),
+                    )?)
+                }
+            }
+        }
     }
 }
 
@@ -20,8 +82,94 @@ pub struct EnvelopeArgs {
     /// If the envelope is not supplied on the command line, it is read from
     /// stdin.
     envelope: Option<String>,
+
+    /// Read the envelope from a file instead of the command line or stdin.
+    #[arg(long = "in", value_name = "PATH", conflicts_with = "envelope")]
+    input: Option<PathBuf>,
+
+    /// The encoding of the input envelope.
+    ///
+    /// `auto` accepts UR text, hex, or binary tagged CBOR (tag 200).
+    #[arg(long, value_name = "FORMAT", default_value = "auto")]
+    in_format: InputFormat,
 }
 
 impl EnvelopeArgsLike for EnvelopeArgs {
-    fn envelope(&self) -> Option<&str> { self.envelope.as_deref() }
+    fn envelope_args(&self) -> &EnvelopeArgs { self }
+}
+
+/// Decodes an envelope from raw input bytes in the given format.
+pub fn envelope_from_data(
+    data: &[u8],
+    format: InputFormat,
+) -> Result<Envelope> {
+    match format {
+        InputFormat::Cbor => envelope_from_cbor_data(data),
+        InputFormat::Auto if !is_text(data) => envelope_from_cbor_data(data),
+        _ => {
+            let text = std::str::from_utf8(data)
+                .context("envelope input is not valid UTF-8")?
+                .trim();
+            if text.is_empty() {
+                bail!(Error::MissingInput("No envelope provided".to_string()));
+            }
+            match format {
+                InputFormat::Ur => read_envelope(Some(text)),
+                InputFormat::Hex => envelope_from_hex(text),
+                _ => envelope_from_text(text),
+            }
+        }
+    }
+}
+
+/// Decodes an envelope from text that is either a UR or hex-encoded CBOR.
+fn envelope_from_text(text: &str) -> Result<Envelope> {
+    if text.get(..3).is_some_and(|p| p.eq_ignore_ascii_case("ur:")) {
+        read_envelope(Some(text))
+    } else if text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        envelope_from_hex(text)
+    } else {
+        bail!(Error::Parse(
+            "unrecognized envelope input: expected a UR, hex, or CBOR"
+                .to_string()
+        ))
+    }
+}
+
+fn envelope_from_hex(text: &str) -> Result<Envelope> {
+    let data = hex::decode(text.trim()).context("invalid hex envelope")?;
+    envelope_from_cbor_data(&data)
+}
+
+/// Decodes tagged envelope CBOR, falling back to untagged envelope CBOR.
+fn envelope_from_cbor_data(data: &[u8]) -> Result<Envelope> {
+    Envelope::from_tagged_cbor_data(data)
+        .or_else(|_| Envelope::from_untagged_cbor_data(data))
+        .context("input is not envelope CBOR")
+}
+
+/// Returns `true` if the data looks like text rather than binary CBOR.
+fn is_text(data: &[u8]) -> bool {
+    std::str::from_utf8(data).is_ok_and(|s| {
+        s.chars().all(|c| !c.is_control() || c.is_ascii_whitespace())
+    })
+}
+
+/// Peeks at stdin to see whether it starts with binary data. UR and hex input
+/// always begin with printable ASCII.
+fn stdin_is_binary() -> Result<bool> {
+    let mut stdin = std::io::stdin().lock();
+    let buffer = stdin.fill_buf()?;
+    Ok(buffer
+        .first()
+        .is_some_and(|&b| !b.is_ascii_graphic() && !b.is_ascii_whitespace()))
+}
+
+fn read_stdin_data() -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    std::io::stdin().read_to_end(&mut data)?;
+    if data.is_empty() {
+        bail!(Error::MissingInput("No envelope provided".to_string()));
+    }
+    Ok(data)
 }
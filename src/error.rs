@@ -0,0 +1,238 @@
+use std::fmt;
+
+use serde_json::json;
+
+use crate::is_json_output;
+
+/// The kinds of failure reported by the tool, each with its own process exit
+/// code.
+///
+/// Errors that don't fall into any of the other kinds are `General` and exit
+/// with 1. Exit code 2 is used for usage errors, as reported by clap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    General,
+    Usage,
+    Parse,
+    MissingInput,
+    NotFound,
+    Crypto,
+    Verification,
+    Io,
+}
+
+impl ErrorKind {
+    /// The process exit code for this kind of error.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::General => 1,
+            Self::Usage => 2,
+            Self::Parse => 3,
+            Self::MissingInput => 4,
+            Self::NotFound => 5,
+            Self::Crypto => 6,
+            Self::Verification => 7,
+            Self::Io => 8,
+        }
+    }
+
+    /// The name of this kind in JSON error output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::Usage => "usage",
+            Self::Parse => "parse",
+            Self::MissingInput => "missing_input",
+            Self::NotFound => "not_found",
+            Self::Crypto => "crypto",
+            Self::Verification => "verification",
+            Self::Io => "io",
+        }
+    }
+}
+
+/// An error raised by a command, classified by kind.
+///
+/// Commands return `anyhow::Result`; an `Error` can be returned through it
+/// with `bail!(Error::NotFound(...))` and is recovered by [`error_kind`].
+#[derive(Debug)]
+pub enum Error {
+    /// The command line is inconsistent in a way clap can't detect.
+    Usage(String),
+    /// An input could not be parsed.
+    Parse(String),
+    /// A required input was not provided.
+    MissingInput(String),
+    /// A lookup, such as an index or a match, found nothing.
+    NotFound(String),
+    /// Encryption, decryption, or another cryptographic operation failed.
+    Crypto(String),
+    /// A signature or proof could not be verified.
+    Verification(String),
+    /// Reading or writing failed.
+    Io(std::io::Error),
+}
+
+impl Error {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Usage(_) => ErrorKind::Usage,
+            Self::Parse(_) => ErrorKind::Parse,
+            Self::MissingInput(_) => ErrorKind::MissingInput,
+            Self::NotFound(_) => ErrorKind::NotFound,
+            Self::Crypto(_) => ErrorKind::Crypto,
+            Self::Verification(_) => ErrorKind::Verification,
+            Self::Io(_) => ErrorKind::Io,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(message)
+            | Self::Parse(message)
+            | Self::MissingInput(message)
+            | Self::NotFound(message)
+            | Self::Crypto(message)
+            | Self::Verification(message) => f.write_str(message),
+            Self::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Classifies an error by the first cause in its chain that has a known kind.
+///
+/// Besides [`Error`], this recognizes the error types of the underlying
+/// libraries, so that, for example, a malformed UR is a parse error and a bad
+/// signature is a verification error wherever they occur.
+pub fn error_kind(error: &anyhow::Error) -> ErrorKind {
+    error
+        .chain()
+        .find_map(cause_kind)
+        .unwrap_or(ErrorKind::General)
+}
+
+fn cause_kind(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    if let Some(error) = cause.downcast_ref::<Error>() {
+        Some(error.kind())
+    } else if cause.is::<std::io::Error>() {
+        Some(ErrorKind::Io)
+    } else if let Some(error) = cause.downcast_ref::<bc_envelope::Error>() {
+        envelope_error_kind(error)
+    } else if let Some(error) = cause.downcast_ref::<bc_components::Error>() {
+        components_error_kind(error)
+    } else if let Some(error) = cause.downcast_ref::<bc_xid::Error>() {
+        xid_error_kind(error)
+    } else if cause.is::<bc_ur::Error>()
+        || cause.is::<dcbor::Error>()
+        || cause.is::<serde_json::Error>()
+        || cause.is::<hex::FromHexError>()
+        || cause.is::<std::num::ParseIntError>()
+    {
+        Some(ErrorKind::Parse)
+    } else {
+        None
+    }
+}
+
+fn envelope_error_kind(error: &bc_envelope::Error) -> Option<ErrorKind> {
+    use bc_envelope::Error as E;
+    match error {
+        E::UnverifiedSignature
+        | E::UnverifiedInnerSignature
+        | E::InvalidOuterSignatureType
+        | E::InvalidInnerSignatureType
+        | E::InvalidSignatureType => Some(ErrorKind::Verification),
+        E::NotEncrypted
+        | E::AlreadyEncrypted
+        | E::UnknownRecipient
+        | E::UnknownSecret
+        | E::InvalidShares
+        | E::SSKR(_) => Some(ErrorKind::Crypto),
+        E::NonexistentPredicate
+        | E::NonexistentAttachment
+        | E::NonexistentEdge => Some(ErrorKind::NotFound),
+        E::InvalidFormat
+        | E::InvalidAssertion
+        | E::InvalidAttachment
+        | E::InvalidType
+        | E::InvalidDigest => Some(ErrorKind::Parse),
+        // Wrapped errors are classified by their source, which follows them
+        // in the chain.
+        E::Cbor(_) | E::Components(_) => None,
+        _ => Some(ErrorKind::General),
+    }
+}
+
+fn components_error_kind(error: &bc_components::Error) -> Option<ErrorKind> {
+    use bc_components::Error as E;
+    match error {
+        E::InvalidSize { .. }
+        | E::InvalidData { .. }
+        | E::DataTooShort { .. }
+        | E::Uri(_)
+        | E::Hex(_) => Some(ErrorKind::Parse),
+        E::Crypto(_)
+        | E::Sskr(_)
+        | E::Ssh(_)
+        | E::PostQuantum(_)
+        | E::LevelMismatch
+        | E::SshAgent(_)
+        | E::SshAgentClient(_) => Some(ErrorKind::Crypto),
+        E::Cbor(_) => None,
+        _ => Some(ErrorKind::General),
+    }
+}
+
+fn xid_error_kind(error: &bc_xid::Error) -> Option<ErrorKind> {
+    use bc_xid::Error as E;
+    match error {
+        E::EnvelopeNotSigned | E::SignatureVerificationFailed => {
+            Some(ErrorKind::Verification)
+        }
+        E::InvalidPassword => Some(ErrorKind::Crypto),
+        E::NotFound { .. }
+        | E::KeyNotFoundInDocument { .. }
+        | E::DelegateNotFoundInDocument { .. }
+        | E::NoProvenanceMark
+        | E::NoGenerator => Some(ErrorKind::NotFound),
+        E::InvalidXid
+        | E::UnexpectedPredicate { .. }
+        | E::UnexpectedNestedAssertions => Some(ErrorKind::Parse),
+        E::EnvelopeParsing(_)
+        | E::Component(_)
+        | E::Cbor(_)
+        | E::ProvenanceMark(_) => None,
+        _ => Some(ErrorKind::General),
+    }
+}
+
+/// Writes `error` to stderr, as a line of JSON if `--json` is in effect.
+///
+/// The JSON form is `{"error": KIND, "exit_code": CODE, "message": MESSAGE}`,
+/// where KIND is the [`ErrorKind::name`].
+pub fn report_error(error: &anyhow::Error) {
+    if is_json_output() {
+        let kind = error_kind(error);
+        eprintln!(
+            "{}",
+            json!({
+                "error": kind.name(),
+                "exit_code": kind.exit_code(),
+                "message": format!("{:#}", error),
+            })
+        );
+    } else {
+        eprintln!("Error: {:#}", error);
+    }
+}
@@ -0,0 +1,420 @@
+use std::cell::RefCell;
+
+use bc_envelope::prelude::*;
+
+/// Options for [`dot_format`] and [`svg_format`].
+#[derive(Clone, Copy, Default)]
+pub struct GraphFormatOpts {
+    hide_nodes: bool,
+    monochrome: bool,
+    digest_display: DigestDisplayFormat,
+    orientation: MermaidOrientation,
+}
+
+impl GraphFormatOpts {
+    /// Hides the NODE case and digests (default is false).
+    pub fn hide_nodes(mut self, hide: bool) -> Self {
+        self.hide_nodes = hide;
+        self
+    }
+
+    /// Draws every node and edge in black (default is false).
+    pub fn monochrome(mut self, monochrome: bool) -> Self {
+        self.monochrome = monochrome;
+        self
+    }
+
+    /// Sets how digests are displayed (default is short).
+    pub fn digest_display(mut self, format: DigestDisplayFormat) -> Self {
+        self.digest_display = format;
+        self
+    }
+
+    /// Sets the direction of the diagram (default is left to right).
+    pub fn orientation(mut self, orientation: MermaidOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+}
+
+/// An element of the envelope, with the label it is drawn with.
+pub(crate) struct GraphElement {
+    pub(crate) envelope: Envelope,
+    pub(crate) lines: Vec<String>,
+    pub(crate) incoming_edge: EdgeType,
+    pub(crate) parent: Option<usize>,
+    pub(crate) depth: usize,
+}
+
+/// Walks the envelope as `tree_format_opt` does, returning its elements in
+/// the order they are visited.
+pub(crate) fn graph_elements(
+    envelope: &Envelope,
+    opts: &GraphFormatOpts,
+) -> Vec<GraphElement> {
+    let elements: RefCell<Vec<GraphElement>> = RefCell::new(Vec::new());
+    let visitor = |envelope: &Envelope,
+                   _: usize,
+                   incoming_edge: EdgeType,
+                   parent: Option<usize>|
+     -> (_, bool) {
+        let mut elements = elements.borrow_mut();
+        let mut lines = vec![with_format_context!(
+            |context: &FormatContext| envelope.summary(20, context)
+        )];
+        if !opts.hide_nodes {
+            lines.push(envelope.short_id(opts.digest_display));
+        }
+        let depth = parent.map_or(0, |parent| elements[parent].depth + 1);
+        elements.push(GraphElement {
+            envelope: envelope.clone(),
+            lines,
+            incoming_edge,
+            parent,
+            depth,
+        });
+        (Some(elements.len() - 1), false)
+    };
+    envelope.walk(opts.hide_nodes, None, &visitor);
+    elements.into_inner()
+}
+
+/// The indexes of the children of each element.
+pub(crate) fn graph_children(elements: &[GraphElement]) -> Vec<Vec<usize>> {
+    let mut children = vec![Vec::new(); elements.len()];
+    for (id, element) in elements.iter().enumerate() {
+        if let Some(parent) = element.parent {
+            children[parent].push(id);
+        }
+    }
+    children
+}
+
+#[rustfmt::skip]
+fn node_color(envelope: &Envelope) -> &'static str {
+    match envelope.case() {
+        EnvelopeCase::Node { .. }       => "red",
+        EnvelopeCase::Leaf { .. }       => "teal",
+        EnvelopeCase::Wrapped { .. }    => "blue",
+        EnvelopeCase::Assertion(..)     => "green",
+        EnvelopeCase::Elided(..)        => "gray",
+        EnvelopeCase::KnownValue { .. } => "goldenrod",
+        EnvelopeCase::Encrypted(..)     => "coral",
+        EnvelopeCase::Compressed(..)    => "purple",
+    }
+}
+
+/// Returns the envelope's structure as a Graphviz DOT digraph.
+///
+/// Each case of envelope is drawn with its own shape, using the same shapes
+/// and colors as the Mermaid output.
+pub fn dot_format(envelope: &Envelope, opts: &GraphFormatOpts) -> String {
+    let elements = graph_elements(envelope, opts);
+    let mut lines = vec![
+        "digraph envelope {".to_string(),
+        format!("    rankdir={};", opts.orientation),
+        "    node [fontname=\"Helvetica\", penwidth=2];".to_string(),
+        "    edge [fontname=\"Helvetica\", penwidth=2];".to_string(),
+    ];
+    for (id, element) in elements.iter().enumerate() {
+        let mut attributes = vec![
+            format!("label=\"{}\"", dot_escape(&element.lines.join("\n"))),
+            dot_shape(&element.envelope).to_string(),
+        ];
+        if !opts.monochrome {
+            let color = node_color(&element.envelope);
+            attributes.push(format!("color={}", color));
+        }
+        lines.push(format!("    {} [{}];", id, attributes.join(", ")));
+    }
+    for (id, element) in elements.iter().enumerate() {
+        let Some(parent) = element.parent else {
+            continue;
+        };
+        let mut attributes = Vec::new();
+        if let Some(label) = element.incoming_edge.label() {
+            attributes.push(format!("label=\"{}\"", label));
+        }
+        if !opts.monochrome
+            && let Some(color) = element.incoming_edge.link_stroke_color()
+        {
+            attributes.push(format!("color={}", color));
+        }
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attributes.join(", "))
+        };
+        lines.push(format!("    {} -> {}{};", parent, id, attributes));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+#[rustfmt::skip]
+fn dot_shape(envelope: &Envelope) -> &'static str {
+    match envelope.case() {
+        EnvelopeCase::Node { .. }       => "shape=circle",
+        EnvelopeCase::Leaf { .. }       => "shape=box",
+        EnvelopeCase::Wrapped { .. }    => "shape=trapezium",
+        EnvelopeCase::Assertion(..)     => "shape=box, style=rounded",
+        EnvelopeCase::Elided(..)        => "shape=hexagon",
+        EnvelopeCase::KnownValue { .. } => "shape=parallelogram",
+        EnvelopeCase::Encrypted(..)     => "shape=cds",
+        EnvelopeCase::Compressed(..)    => "shape=box, peripheries=2",
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+const CHAR_WIDTH: f64 = 7.2;
+const LINE_HEIGHT: f64 = 16.0;
+const PADDING: f64 = 12.0;
+const MARGIN: f64 = 16.0;
+/// The space between ranks, which holds the edge labels.
+const RANK_GAP: f64 = 64.0;
+const FILE_GAP: f64 = 16.0;
+const INSET: f64 = 8.0;
+
+/// Returns the envelope's structure as a standalone SVG image.
+///
+/// The tree is laid out with each level in its own rank and each leaf in its
+/// own row, so no external layout engine is needed.
+pub fn svg_format(envelope: &Envelope, opts: &GraphFormatOpts) -> String {
+    let elements = graph_elements(envelope, opts);
+    let sizes: Vec<(f64, f64)> = elements.iter().map(svg_size).collect();
+    let max_width = sizes.iter().map(|s| s.0).fold(0.0, f64::max);
+    let max_height = sizes.iter().map(|s| s.1).fold(0.0, f64::max);
+
+    let children = graph_children(&elements);
+    let mut rows = vec![0.0; elements.len()];
+    let mut next_row = 0.0;
+    place_rows(&children, 0, &mut rows, &mut next_row);
+    let ranks = elements.iter().map(|e| e.depth).max().unwrap_or_default();
+    let files = (next_row - 1.0_f64).max(0.0);
+
+    let horizontal = matches!(
+        opts.orientation,
+        MermaidOrientation::LeftToRight | MermaidOrientation::RightToLeft
+    );
+    let reversed = matches!(
+        opts.orientation,
+        MermaidOrientation::RightToLeft | MermaidOrientation::BottomToTop
+    );
+    let (rank_step, file_step) = if horizontal {
+        (max_width + RANK_GAP, max_height + FILE_GAP)
+    } else {
+        (max_height + RANK_GAP, max_width + FILE_GAP)
+    };
+    let center = |id: usize| {
+        let depth = elements[id].depth;
+        let rank = if reversed { ranks - depth } else { depth };
+        let along = rank as f64 * rank_step;
+        let across = rows[id] * file_step;
+        let (x, y) = if horizontal {
+            (along, across)
+        } else {
+            (across, along)
+        };
+        (MARGIN + max_width / 2.0 + x, MARGIN + max_height / 2.0 + y)
+    };
+    let (along, across) = (ranks as f64 * rank_step, files * file_step);
+    let (x, y) = if horizontal {
+        (along, across)
+    } else {
+        (across, along)
+    };
+    let (width, height) =
+        (2.0 * MARGIN + max_width + x, 2.0 * MARGIN + max_height + y);
+
+    let mut lines = vec![
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+                r#"width="{:.1}" height="{:.1}" viewBox="0 0 {:.1} {:.1}" "#,
+                r#"font-family="monospace" font-size="12">"#,
+            ),
+            width, height, width, height
+        ),
+        format!(
+            r#"<rect width="{:.1}" height="{:.1}" fill="white"/>"#,
+            width, height
+        ),
+    ];
+    // Edges are drawn first, so that the nodes cover their ends.
+    for (id, element) in elements.iter().enumerate() {
+        let Some(parent) = element.parent else {
+            continue;
+        };
+        let (x1, y1) = center(parent);
+        let (x2, y2) = center(id);
+        let color = match element.incoming_edge.link_stroke_color() {
+            Some(color) if !opts.monochrome => color,
+            _ => "black",
+        };
+        lines.push(format!(
+            concat!(
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" "#,
+                r#"stroke="{}" stroke-width="2"/>"#,
+            ),
+            x1, y1, x2, y2, color
+        ));
+        if let Some(label) = element.incoming_edge.label() {
+            lines.push(svg_text(
+                (x1 + x2) / 2.0,
+                (y1 + y2) / 2.0,
+                r#" stroke="white" stroke-width="4" paint-order="stroke""#,
+                label,
+            ));
+        }
+    }
+    for (id, element) in elements.iter().enumerate() {
+        let (cx, cy) = center(id);
+        let color = if opts.monochrome {
+            "black"
+        } else {
+            node_color(&element.envelope)
+        };
+        lines.push(format!(
+            r#"<g fill="white" stroke="{}" stroke-width="2">{}</g>"#,
+            color,
+            svg_shape(&element.envelope, (cx, cy), sizes[id])
+        ));
+        let count = element.lines.len() as f64;
+        let top = cy - (count - 1.0) * LINE_HEIGHT / 2.0;
+        for (i, line) in element.lines.iter().enumerate() {
+            let y = top + i as f64 * LINE_HEIGHT;
+            lines.push(svg_text(cx, y, "", &xml_escape(line)));
+        }
+    }
+    lines.push("</svg>".to_string());
+    lines.join("\n")
+}
+
+fn svg_size(element: &GraphElement) -> (f64, f64) {
+    let chars = element
+        .lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+    (
+        chars as f64 * CHAR_WIDTH + 2.0 * PADDING,
+        element.lines.len() as f64 * LINE_HEIGHT + PADDING,
+    )
+}
+
+/// Assigns each element its row. Leaves take the next free row, and a
+/// parent is centered on its children.
+fn place_rows(
+    children: &[Vec<usize>],
+    id: usize,
+    rows: &mut [f64],
+    next_row: &mut f64,
+) {
+    for &child in &children[id] {
+        place_rows(children, child, rows, next_row);
+    }
+    rows[id] = match (children[id].first(), children[id].last()) {
+        (Some(&first), Some(&last)) => (rows[first] + rows[last]) / 2.0,
+        _ => {
+            *next_row += 1.0;
+            *next_row - 1.0
+        }
+    };
+}
+
+fn svg_text(x: f64, y: f64, attributes: &str, text: &str) -> String {
+    format!(
+        concat!(
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" "#,
+            r#"dominant-baseline="middle"{}>{}</text>"#,
+        ),
+        x, y, attributes, text
+    )
+}
+
+fn svg_shape(
+    envelope: &Envelope,
+    (cx, cy): (f64, f64),
+    (w, h): (f64, f64),
+) -> String {
+    let (x0, y0) = (cx - w / 2.0, cy - h / 2.0);
+    let (x1, y1) = (cx + w / 2.0, cy + h / 2.0);
+    let rect = |rx: f64| {
+        format!(
+            concat!(
+                r#"<rect x="{:.1}" y="{:.1}" "#,
+                r#"width="{:.1}" height="{:.1}" rx="{:.1}"/>"#,
+            ),
+            x0, y0, w, h, rx
+        )
+    };
+    let polygon = |points: &[(f64, f64)]| {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(r#"<polygon points="{}"/>"#, points)
+    };
+    match envelope.case() {
+        EnvelopeCase::Node { .. } => format!(
+            r#"<ellipse cx="{:.1}" cy="{:.1}" rx="{:.1}" ry="{:.1}"/>"#,
+            cx,
+            cy,
+            w / 2.0,
+            h / 2.0
+        ),
+        EnvelopeCase::Leaf { .. } => rect(0.0),
+        EnvelopeCase::Wrapped { .. } => polygon(&[
+            (x0 + INSET, y0),
+            (x1 - INSET, y0),
+            (x1, y1),
+            (x0, y1),
+        ]),
+        EnvelopeCase::Assertion(..) => rect(h / 2.0),
+        EnvelopeCase::Elided(..) => polygon(&[
+            (x0, cy),
+            (x0 + INSET, y0),
+            (x1 - INSET, y0),
+            (x1, cy),
+            (x1 - INSET, y1),
+            (x0 + INSET, y1),
+        ]),
+        EnvelopeCase::KnownValue { .. } => polygon(&[
+            (x0 + INSET, y0),
+            (x1, y0),
+            (x1 - INSET, y1),
+            (x0, y1),
+        ]),
+        EnvelopeCase::Encrypted(..) => polygon(&[
+            (x0, y0),
+            (x1, y0),
+            (x1, y1),
+            (x0, y1),
+            (x0 + INSET, cy),
+        ]),
+        EnvelopeCase::Compressed(..) => format!(
+            r#"{}<path d="M{:.1},{:.1}V{:.1}M{:.1},{:.1}V{:.1}"/>"#,
+            rect(0.0),
+            x0 + INSET,
+            y0,
+            y1,
+            x1 - INSET,
+            y0,
+            y1
+        ),
+    }
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
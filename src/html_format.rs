@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use bc_envelope::prelude::*;
+
+use crate::{
+    GraphFormatOpts,
+    graph_format::{GraphElement, graph_children, graph_elements, xml_escape},
+};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+pre, code, .tree { font-family: monospace; }
+pre { background: #f6f6f6; padding: 1em; overflow-x: auto; }
+.tree, .tree ul { list-style: none; padding-left: 1.5em; }
+.tree { padding-left: 0; }
+summary { cursor: pointer; }
+.element { border: 2px solid; border-radius: 4px; padding: 0 0.3em; }
+.element[title] { cursor: help; }
+.edge { color: #666; margin-right: 0.3em; }
+.id { color: #888; margin-left: 0.5em; font-size: 0.9em; }
+.node { border-color: red; }
+.leaf { border-color: teal; }
+.wrapped { border-color: blue; }
+.assertion { border-color: green; border-radius: 1em; }
+.elided { border-color: gray; border-style: dashed; color: gray; }
+.known-value { border-color: goldenrod; }
+.encrypted { border-color: coral; background: #fde8e1; }
+.compressed { border-color: purple; border-style: double; \
+background: #f1e5f5; }
+";
+
+/// Returns a self-contained HTML report of the envelope.
+///
+/// The report shows the envelope notation and a collapsible tree of the
+/// envelope's elements. Hovering over an element shows its full digest and
+/// its `ur:digest`, and known values link to a table of their names.
+pub fn html_format(envelope: &Envelope, opts: &GraphFormatOpts) -> String {
+    let elements = graph_elements(envelope, opts);
+    let children = graph_children(&elements);
+    let digest = envelope.digest();
+
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html lang=\"en\">".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>Envelope {}</title>", digest.short_description()),
+        format!("<style>\n{}</style>", STYLE),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        "<h1>Envelope</h1>".to_string(),
+        format!(
+            "<p>Digest: <code>{}</code></p>",
+            xml_escape(&digest.ur_string())
+        ),
+        "<h2>Envelope Notation</h2>".to_string(),
+        format!("<pre>{}</pre>", xml_escape(&envelope.format())),
+        "<h2>Structure</h2>".to_string(),
+        "<ul class=\"tree\">".to_string(),
+    ];
+    let mut known_values = BTreeMap::new();
+    html_tree(&elements, &children, 0, &mut lines, &mut known_values);
+    lines.push("</ul>".to_string());
+    if !known_values.is_empty() {
+        lines.push("<h2>Known Values</h2>".to_string());
+        lines.push("<dl>".to_string());
+        for (value, name) in known_values {
+            lines.push(format!(
+                "<dt id=\"known-value-{}\">{}</dt><dd>{}</dd>",
+                value,
+                xml_escape(&name),
+                value
+            ));
+        }
+        lines.push("</dl>".to_string());
+    }
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines.join("\n")
+}
+
+/// Appends the list item of an element and its descendants.
+fn html_tree(
+    elements: &[GraphElement],
+    children: &[Vec<usize>],
+    id: usize,
+    lines: &mut Vec<String>,
+    known_values: &mut BTreeMap<u64, String>,
+) {
+    let element = &elements[id];
+    let indent = "  ".repeat(element.depth + 1);
+    let label = html_label(element, known_values);
+    if children[id].is_empty() {
+        lines.push(format!("{}<li>{}</li>", indent, label));
+        return;
+    }
+    lines.push(format!(
+        "{}<li><details open><summary>{}</summary><ul>",
+        indent, label
+    ));
+    for &child in &children[id] {
+        html_tree(elements, children, child, lines, known_values);
+    }
+    lines.push(format!("{}</ul></details></li>", indent));
+}
+
+fn html_label(
+    element: &GraphElement,
+    known_values: &mut BTreeMap<u64, String>,
+) -> String {
+    let envelope = &element.envelope;
+    let digest = envelope.digest();
+    let mut summary = xml_escape(&element.lines[0]);
+    if let EnvelopeCase::KnownValue { value, .. } = envelope.case() {
+        let name = with_format_context!(|context: &FormatContext| {
+            context.known_values().name(value.clone())
+        });
+        known_values.insert(value.value(), name);
+        summary = format!(
+            "<a href=\"#known-value-{}\">{}</a>",
+            value.value(),
+            summary
+        );
+    }
+    let edge = element
+        .incoming_edge
+        .label()
+        .map(|label| format!("<span class=\"edge\">{}</span>", label))
+        .unwrap_or_default();
+    let id = element
+        .lines
+        .get(1)
+        .map(|id| format!("<span class=\"id\">{}</span>", xml_escape(id)))
+        .unwrap_or_default();
+    format!(
+        "{}<span class=\"element {}\" title=\"{}&#10;{}\">{}</span>{}",
+        edge,
+        html_class(envelope),
+        digest.hex(),
+        digest.ur_string(),
+        summary,
+        id
+    )
+}
+
+#[rustfmt::skip]
+fn html_class(envelope: &Envelope) -> &'static str {
+    match envelope.case() {
+        EnvelopeCase::Node { .. }       => "node",
+        EnvelopeCase::Leaf { .. }       => "leaf",
+        EnvelopeCase::Wrapped { .. }    => "wrapped",
+        EnvelopeCase::Assertion(..)     => "assertion",
+        EnvelopeCase::Elided(..)        => "elided",
+        EnvelopeCase::KnownValue { .. } => "known-value",
+        EnvelopeCase::Encrypted(..)     => "encrypted",
+        EnvelopeCase::Compressed(..)    => "compressed",
+    }
+}
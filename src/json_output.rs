@@ -0,0 +1,61 @@
+use std::cell::Cell;
+
+use anyhow::Result;
+use bc_envelope::prelude::*;
+use serde_json::{Value, json};
+
+thread_local! {
+    static JSON_OUTPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Selects JSON output for the commands that support it.
+pub fn set_json_output(json: bool) { JSON_OUTPUT.set(json); }
+
+/// Returns `true` if `--json` was given.
+pub fn is_json_output() -> bool { JSON_OUTPUT.get() }
+
+/// Serializes a JSON value as a single line of output.
+pub fn json_string(value: &Value) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// The JSON form of a digest: `{"digest": <ur>, "hex": <hex>}`.
+pub fn digest_json(digest: &Digest) -> Value {
+    json!({
+        "digest": digest.ur_string(),
+        "hex": digest.hex(),
+    })
+}
+
+/// The JSON form of an array of digests, sorted as in the text output.
+pub fn digests_json(digests: impl IntoIterator<Item = Digest>) -> Value {
+    let mut digests = digests.into_iter().collect::<Vec<_>>();
+    digests.sort();
+    Value::Array(digests.iter().map(digest_json).collect())
+}
+
+/// A one-line summary of an envelope in envelope notation, or `null` if the
+/// envelope is absent.
+pub fn summary_json(envelope: Option<Envelope>) -> Value {
+    envelope.map_or(Value::Null, |e| Value::String(e.format_flat()))
+}
+
+/// The JSON form of a list of assertions, in envelope order.
+///
+/// Each assertion is `{"digest", "envelope", "predicate", "object"}`, where
+/// `predicate` and `object` are `null` if the assertion is obscured.
+pub fn assertions_json(assertions: &[Envelope]) -> Value {
+    Value::Array(
+        assertions
+            .iter()
+            .map(|assertion| {
+                json!({
+                    "digest": assertion.digest().ur_string(),
+                    "envelope": assertion.ur_string(),
+                    "predicate": summary_json(assertion.as_predicate()),
+                    "object": summary_json(assertion.as_object()),
+                })
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,136 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::known_values::{DirectoryConfig, KNOWN_VALUES, KnownValue};
+use clap::Args;
+use serde::Deserialize;
+
+use crate::Error;
+
+/// Options for adding known values to the standard registry.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct KnownValuesArgs {
+    /// A JSON or TOML file of known values to add to the standard ones. May be
+    /// repeated.
+    ///
+    /// Known values in JSON files in `~/.known-values/` are loaded
+    /// automatically, and so are those in TOML files there. Values given here
+    /// replace any with the same codepoint.
+    #[arg(long, value_name = "FILE", global = true)]
+    known_values: Vec<PathBuf>,
+}
+
+impl KnownValuesArgs {
+    /// Adds the known values in the config directory and in the files given
+    /// on the command line to the global registry.
+    ///
+    /// This must be called before the tags are registered, since the format
+    /// context takes a copy of the registry when it is created.
+    pub fn load(&self) -> Result<()> {
+        let mut values = read_known_values_directory(
+            &DirectoryConfig::default_directory(),
+        )?;
+        for path in &self.known_values {
+            values.extend(read_known_values_file(path)?);
+        }
+        add_known_values(values);
+        Ok(())
+    }
+}
+
+/// A file of known value definitions, in the layout of the JSON registry
+/// files in `~/.known-values/`. Other fields of the registry files, such as
+/// `ontology`, are ignored.
+#[derive(Debug, Deserialize)]
+struct KnownValuesFile {
+    entries: Vec<KnownValueEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KnownValueEntry {
+    codepoint: u64,
+    name: String,
+    /// Documents the value for readers of the file; it is not used.
+    #[allow(dead_code)]
+    description: Option<String>,
+}
+
+/// Reads the known values defined in a JSON or TOML file. The format is
+/// chosen by the file extension, and files without a `.toml` extension are
+/// read as JSON.
+///
+/// ```json
+/// { "entries": [ { "codepoint": 1000, "name": "myPredicate" } ] }
+/// ```
+///
+/// ```toml
+/// [[entries]]
+/// codepoint = 1000
+/// name = "myPredicate"
+/// description = "An example predicate."
+/// ```
+pub fn read_known_values_file(path: &Path) -> Result<Vec<KnownValue>> {
+    let text = fs::read_to_string(path).with_context(|| {
+        format!("could not read known values from {}", path.display())
+    })?;
+    let file: KnownValuesFile = if is_toml(path) {
+        toml::from_str(&text).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    }
+    .map_err(|e| {
+        Error::Parse(format!(
+            "invalid known values in {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    file.entries
+        .into_iter()
+        .map(|entry| {
+            if entry.name.is_empty() || entry.name.contains('\'') {
+                bail!(Error::Parse(format!(
+                    "invalid known value name \"{}\" in {}",
+                    entry.name,
+                    path.display()
+                )));
+            }
+            Ok(KnownValue::new_with_name(entry.codepoint, entry.name))
+        })
+        .collect()
+}
+
+/// Reads the known values in the TOML files of `directory`, in file name
+/// order. JSON files there are loaded by the known values registry itself.
+fn read_known_values_directory(directory: &Path) -> Result<Vec<KnownValue>> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file() && is_toml(path))
+        .collect();
+    paths.sort();
+    let mut values = Vec::new();
+    for path in paths {
+        values.extend(read_known_values_file(&path)?);
+    }
+    Ok(values)
+}
+
+/// Returns whether a definitions file should be read as TOML rather than
+/// JSON.
+pub(crate) fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "toml")
+}
+
+/// Adds `values` to the global known values registry, replacing any values
+/// with the same codepoints.
+pub fn add_known_values(values: impl IntoIterator<Item = KnownValue>) {
+    let mut store = KNOWN_VALUES.get();
+    let store = store.as_mut().unwrap();
+    for value in values {
+        store.insert(value);
+    }
+}
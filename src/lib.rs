@@ -1 +1,102 @@
+//! The library behind the `envelope` command line tool. See the main repo
+//! [README](https://github.com/BlockchainCommons/bc-envelope-cli-rust/blob/master/README.md).
+//!
+//! Each command lives in its own module, such as [`sskr::split`] or
+//! [`xid::key::add`]. The `CommandArgs` struct of a module is the clap adapter
+//! used by the binary. It parses the command's arguments, calls the module's
+//! typed function, and formats the result. The typed functions can also be
+//! called directly. Most are named after their command, for example:
+//!
+//! - [`sign::sign`], [`verify::verify`], [`seal::seal`], and
+//!   [`unseal::unseal`]
+//! - [`encrypt::encrypt`], [`decrypt::decrypt`], and
+//!   [`decrypt::envelope_locks`]
+//! - [`locks::add::locks_add`] and [`locks::remove::locks_remove`]
+//! - [`compress::compress`], [`salt::salt`], and [`digest::envelope_digests`]
+//! - [`elide::removing::elide_removing`] and
+//!   [`elide::revealing::elide_revealing`]
+//! - [`proof::create::proof_create`] and [`proof::confirm::proof_confirm`]
+//! - [`assertion::add::assertion_add`] and
+//!   [`assertion::find::predicate::assertion_find_predicate`]
+//! - [`attachment::create::attachment_create`] and
+//!   [`attachment::find::attachment_find`]
+//! - [`extract::extract`], [`format::format_envelope`], and
+//!   [`info::object_info`]
+//! - [`pattern::parse_pattern`] and [`pattern::match_paths`]
+//! - [`walk::matching::walk_matching`] and [`walk::replace::walk_replace`]
+//! - [`generate::seed::generate_seed`] and
+//!   [`generate::keypairs::generate_keypairs`]
+//! - [`import::import_private_key`] and [`export::export_private_key`]
+//! - [`sskr::split::sskr_split`] and [`sskr::join::sskr_join`]
+//! - [`diff::envelope_diff`] and [`patch::apply::apply_patch`]
+//! - [`parse::parse_envelope_notation`],
+//!   [`convert::from_json::envelope_from_json`], and
+//!   [`convert::to_json::envelope_to_json`]
+//! - [`xid::new::xid_new`], [`xid::id::xid_id`],
+//!   [`xid::key::add::xid_key_add`], [`xid::service::add::xid_service_add`],
+//!   and [`xid::export::xid_export_elided`]
+//! - [`run::run_recipe`]
+//!
+//! ```no_run
+//! use bc_envelope::prelude::*;
+//! use bc_envelope_cli::sskr::split::{SskrSplitOptions, sskr_split};
+//!
+//! let envelope = Envelope::new("Hello.");
+//! let options = SskrSplitOptions {
+//!     groups: vec![(2, 3)],
+//!     ..Default::default()
+//! };
+//! let shares = sskr_split(&envelope, &options).unwrap();
+//! assert_eq!(shares.len(), 3);
+//! ```
+//!
+//! These functions take all of their input as arguments and do not depend on
+//! the global `--json` or `--each` options, which only the `CommandArgs`
+//! adapters consult. They don't prompt for passwords either: the adapters
+//! read them and pass them in. The interactive `shell` is the only command
+//! without a typed function.
+
+#[doc(hidden)]
+mod batch_args;
+pub use batch_args::*;
+#[doc(hidden)]
+mod cmd;
+pub use cmd::*;
+#[doc(hidden)]
+mod data_types;
+pub use data_types::*;
+#[doc(hidden)]
+mod envelope_args;
+pub use envelope_args::*;
+#[doc(hidden)]
+mod error;
+pub use error::*;
+#[doc(hidden)]
+mod exec;
+pub use exec::*;
+#[doc(hidden)]
+mod graph_format;
+pub use graph_format::*;
+#[doc(hidden)]
+mod html_format;
+pub use html_format::*;
+#[doc(hidden)]
+mod json_output;
+pub use json_output::*;
+#[doc(hidden)]
+mod known_values_args;
+pub use known_values_args::*;
+#[doc(hidden)]
+mod output_args;
+pub use output_args::*;
+#[doc(hidden)]
+mod pred_obj_args;
+pub use pred_obj_args::*;
+#[doc(hidden)]
+mod subject_args;
+pub use subject_args::*;
+#[doc(hidden)]
+mod tags_args;
+pub use tags_args::*;
 pub mod utils;
+pub use utils::*;
@@ -1,34 +1,12 @@
 //! A command line tool for manipulating the Gordian Envelope data type. See the main repo [README](https://github.com/BlockchainCommons/bc-envelope-cli-rust/blob/master/README.md).
 
-#[doc(hidden)]
-mod cmd;
-pub use cmd::*;
-#[doc(hidden)]
-mod data_types;
-pub use data_types::*;
-#[doc(hidden)]
-mod envelope_args;
-pub use envelope_args::*;
-#[doc(hidden)]
-mod exec;
-pub use exec::*;
-#[doc(hidden)]
-mod pred_obj_args;
-pub use pred_obj_args::*;
 #[doc(hidden)]
 mod styles;
-#[doc(hidden)]
-mod subject_args;
-pub use subject_args::*;
-#[doc(hidden)]
-mod utils;
-use std::io::{self, ErrorKind, Write};
+use std::{env, process::ExitCode};
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-pub use utils::*;
-
-use crate::Exec;
+use anyhow::{Result, bail};
+use bc_envelope_cli::*;
+use clap::Parser;
 
 /// A tool for manipulating the Gordian Envelope data type.
 #[derive(Debug, Parser)]
@@ -40,83 +18,80 @@ use crate::Exec;
 struct Cli {
     #[command(subcommand)]
     command: MainCommands,
-}
 
-#[derive(Debug, Subcommand)]
-#[doc(hidden)]
-enum MainCommands {
-    Assertion(assertion::CommandArgs),
-    Attachment(attachment::CommandArgs),
-    Compress(compress::CommandArgs),
-    Decrypt(decrypt::CommandArgs),
-    Digest(digest::CommandArgs),
-    Elide(elide::CommandArgs),
-    Encrypt(encrypt::CommandArgs),
-    Export(export::CommandArgs),
-    Extract(extract::CommandArgs),
-    Format(format::CommandArgs),
-    Generate(generate::CommandArgs),
-    Import(import::CommandArgs),
-    Info(info::CommandArgs),
-    Match(pattern::CommandArgs),
-    Proof(proof::CommandArgs),
-    Salt(salt::CommandArgs),
-    Sign(sign::CommandArgs),
-    Sskr(sskr::CommandArgs),
-    Subject(subject::CommandArgs),
-    Decompress(decompress::CommandArgs),
-    Verify(verify::CommandArgs),
-    Walk(walk::CommandArgs),
-    Xid(xid::CommandArgs),
+    #[command(flatten)]
+    batch_args: BatchArgs,
+
+    #[command(flatten)]
+    output_args: OutputArgs,
+
+    #[command(flatten)]
+    known_values_args: KnownValuesArgs,
+
+    #[command(flatten)]
+    tags_args: TagsArgs,
 }
 
 #[doc(hidden)]
-fn main() -> Result<()> {
+fn main() -> ExitCode {
+    // Some arguments are URs decoded while parsing, which needs the tags.
     bc_components::register_tags();
-    bc_envelope::register_tags();
-    provenance_mark::register_tags();
-
-    let cli = Cli::parse();
 
-    let output = match cli.command {
-        MainCommands::Assertion(args) => args.exec(),
-        MainCommands::Attachment(args) => args.exec(),
-        MainCommands::Compress(args) => args.exec(),
-        MainCommands::Decrypt(args) => args.exec(),
-        MainCommands::Digest(args) => args.exec(),
-        MainCommands::Elide(args) => args.exec(),
-        MainCommands::Encrypt(args) => args.exec(),
-        MainCommands::Export(args) => args.exec(),
-        MainCommands::Extract(args) => args.exec(),
-        MainCommands::Format(args) => args.exec(),
-        MainCommands::Generate(args) => args.exec(),
-        MainCommands::Import(args) => args.exec(),
-        MainCommands::Info(args) => args.exec(),
-        MainCommands::Match(args) => args.exec(),
-        MainCommands::Proof(args) => args.exec(),
-        MainCommands::Salt(args) => args.exec(),
-        MainCommands::Sign(args) => args.exec(),
-        MainCommands::Sskr(args) => args.exec(),
-        MainCommands::Subject(args) => args.exec(),
-        MainCommands::Decompress(args) => args.exec(),
-        MainCommands::Verify(args) => args.exec(),
-        MainCommands::Walk(args) => args.exec(),
-        MainCommands::Xid(args) => args.exec(),
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(error) => {
+            // Help and version requests are reported as clap errors too, but
+            // go to stdout and succeed.
+            if error.use_stderr() && env::args().any(|arg| arg == "--json") {
+                let text = error.to_string();
+                let message = text.lines().next().unwrap_or_default();
+                let message = message.trim_start_matches("error: ");
+                set_json_output(true);
+                report_error(&Error::Usage(message.to_string()).into());
+                return ExitCode::from(ErrorKind::Usage.exit_code());
+            }
+            error.exit();
+        }
     };
-    let output = output?;
-    write_output(&output)?;
-    Ok(())
-}
 
-fn write_output(output: &str) -> Result<()> {
-    if output.is_empty() {
-        return Ok(());
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            report_error(&error);
+            ExitCode::from(error_kind(&error).exit_code())
+        }
     }
+}
 
-    let mut stdout = io::stdout().lock();
-    match writeln!(stdout, "{output}") {
-        Ok(()) => Ok(()),
-        Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(()),
-        Err(error) => Err(error.into()),
+#[doc(hidden)]
+fn run(cli: &Cli) -> Result<()> {
+    let output_args = &cli.output_args;
+    set_json_output(output_args.is_json());
+    if output_args.is_json() && output_args.out_format() != OutputFormat::Ur {
+        bail!(Error::Usage(
+            "--json cannot be combined with --out-format".to_string()
+        ));
+    }
+    // Custom known values must be in the registry before the tags are
+    // registered, since the format context copies it.
+    cli.known_values_args.load()?;
+    bc_envelope::register_tags();
+    provenance_mark::register_tags();
+    cli.tags_args.load()?;
+    if cli.batch_args.is_each() {
+        if output_args.out_format() == OutputFormat::Cbor {
+            bail!(Error::Usage(
+                "--each cannot be combined with binary CBOR output".to_string()
+            ));
+        }
+        let mut writer = output_args.writer()?;
+        return cli.batch_args.run_each(&cli.command, |output| {
+            write_rendered(&mut writer, &output_args.render(output)?)
+        });
     }
+
+    let output = cli.command.exec()?;
+    let rendered = output_args.render(&output)?;
+    write_rendered(&mut output_args.writer()?, &rendered)?;
+    Ok(())
 }
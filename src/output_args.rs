@@ -0,0 +1,178 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use bc_envelope::prelude::*;
+use bc_ur::{MultipartEncoder, bytewords};
+use clap::{Args, ValueEnum};
+
+/// The encoding used to write UR output.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// UR text.
+    #[default]
+    Ur,
+    /// Binary tagged CBOR.
+    Cbor,
+    /// Tagged CBOR in hex.
+    Hex,
+    /// Tagged CBOR as minimal bytewords.
+    Bytewords,
+    /// Multipart UR fragments, one per line.
+    Multipart,
+}
+
+/// Options controlling where and how command output is written.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct OutputArgs {
+    /// Write the output to a file instead of stdout.
+    #[arg(long, value_name = "PATH", global = true)]
+    out: Option<PathBuf>,
+
+    /// The encoding of envelope and other UR output.
+    ///
+    /// Formats other than `ur` apply only to commands whose output is one or
+    /// more URs. Multiple URs are written one per line, except for `cbor`,
+    /// which requires a single result.
+    #[arg(long, value_name = "FORMAT", default_value = "ur", global = true)]
+    out_format: OutputFormat,
+
+    /// For `multipart`, the maximum length of each fragment.
+    ///
+    /// Smaller fragments produce more parts, each of which fits in a less
+    /// dense QR code.
+    #[arg(long, value_name = "N", default_value = "200", global = true)]
+    max_fragment_len: usize,
+
+    /// Write the result of query and inspection commands as JSON.
+    ///
+    /// Supported by `info`, `digest`, `walk`, `match`, `diff`,
+    /// `extract array`, `extract map`, `assertion all`, `assertion find`,
+    /// `xid id`, and `xid key all`. See `docs/JSON-Output.md` for the schema
+    /// of each command.
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+impl OutputArgs {
+    pub fn out_format(&self) -> OutputFormat { self.out_format }
+
+    pub fn is_json(&self) -> bool { self.json }
+
+    /// Opens the destination for the output: the `--out` file if given,
+    /// otherwise stdout.
+    pub fn writer(&self) -> Result<Box<dyn Write>> {
+        Ok(match &self.out {
+            Some(path) => Box::new(File::create(path).with_context(|| {
+                format!("could not create {}", path.display())
+            })?),
+            None => Box::new(io::stdout()),
+        })
+    }
+
+    /// Renders the textual output of a command in the selected format.
+    ///
+    /// Text output is terminated with a newline; empty output renders as no
+    /// bytes at all.
+    pub fn render(&self, output: &str) -> Result<Vec<u8>> {
+        if output.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self.out_format {
+            OutputFormat::Ur => return Ok(format!("{output}\n").into_bytes()),
+            OutputFormat::Multipart => {
+                let parts = output
+                    .split_whitespace()
+                    .map(|ur| multipart_ur_parts(ur, self.max_fragment_len))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(lines(parts.into_iter().flatten()));
+            }
+            _ => {}
+        }
+        let datas = output
+            .split_whitespace()
+            .map(ur_to_tagged_cbor_data)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(match self.out_format {
+            OutputFormat::Ur | OutputFormat::Multipart => unreachable!(),
+            OutputFormat::Cbor => {
+                if datas.len() != 1 {
+                    bail!(
+                        "binary CBOR output requires a single result, but the command produced {}",
+                        datas.len()
+                    );
+                }
+                datas.into_iter().next().unwrap()
+            }
+            OutputFormat::Hex => lines(datas.iter().map(hex::encode)),
+            OutputFormat::Bytewords => lines(datas.iter().map(|data| {
+                bytewords::encode(data, bytewords::Style::Minimal)
+            })),
+        })
+    }
+}
+
+/// Writes rendered output, treating a closed pipe as success.
+pub fn write_rendered(writer: &mut dyn Write, data: &[u8]) -> Result<()> {
+    if data.is_empty() {
+        return Ok(());
+    }
+    match writer.write_all(data).and_then(|_| writer.flush()) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn lines(items: impl Iterator<Item = String>) -> Vec<u8> {
+    items
+        .map(|item| format!("{item}\n"))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Splits a UR into the fountain-coded fragments that together make up the
+/// whole message.
+fn multipart_ur_parts(
+    ur_string: &str,
+    max_fragment_len: usize,
+) -> Result<Vec<String>> {
+    let ur = parse_output_ur(ur_string)?;
+    let mut encoder = MultipartEncoder::new(&ur, max_fragment_len)?;
+    (0..encoder.parts_count())
+        .map(|_| encoder.next_part().map_err(Into::into))
+        .collect()
+}
+
+fn parse_output_ur(ur_string: &str) -> Result<UR> {
+    UR::from_ur_string(ur_string).map_err(|_| {
+        anyhow!(
+            "output is not a UR and can only be written with --out-format ur"
+        )
+    })
+}
+
+/// Converts a UR to the tagged CBOR it represents.
+///
+/// Envelopes (including `ur:xid` documents) become tag-200 envelope CBOR; other
+/// types are tagged using the tag registered for their UR type.
+fn ur_to_tagged_cbor_data(ur_string: &str) -> Result<Vec<u8>> {
+    let ur = parse_output_ur(ur_string)?;
+    if let Ok(envelope) = Envelope::from_ur(&ur) {
+        return Ok(envelope.tagged_cbor_data());
+    }
+    if let Ok(envelope) = Envelope::from_tagged_cbor(ur.cbor()) {
+        return Ok(envelope.tagged_cbor_data());
+    }
+    let tag = with_format_context!(|context: &FormatContext| {
+        context.tags().tag_for_name(ur.ur_type_str())
+    });
+    match tag {
+        Some(tag) => Ok(CBOR::to_tagged_value(tag, ur.cbor()).to_cbor_data()),
+        None => bail!("Unknown UR type: {}", ur.ur_type_str()),
+    }
+}
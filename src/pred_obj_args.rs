@@ -12,7 +12,7 @@ pub trait PredObjArgsLike {
     fn pred_tag(&self) -> Option<u64>;
     fn obj_tag(&self) -> Option<u64>;
 
-    fn assertion_envelope(&self) -> Result<Envelope> {
+    fn predicate_and_object(&self) -> Result<(Envelope, Envelope)> {
         let predicate = parse_data_type_to_envelope(
             self.pred_type(),
             Some(self.pred_value()),
@@ -23,6 +23,11 @@ pub trait PredObjArgsLike {
             Some(self.obj_value()),
             self.obj_tag(),
         )?;
+        Ok((predicate, object))
+    }
+
+    fn assertion_envelope(&self) -> Result<Envelope> {
+        let (predicate, object) = self.predicate_and_object()?;
         Ok(Envelope::new_assertion(predicate, object))
     }
 }
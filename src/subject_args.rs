@@ -1,7 +1,7 @@
 use anyhow::{Result, bail};
 use clap::Args;
 
-use crate::DataType;
+use crate::{DataType, read_command_line};
 
 pub trait SubjectArgsLike {
     fn subject_type(&self) -> DataType;
@@ -9,12 +9,10 @@ pub trait SubjectArgsLike {
     fn ur_tag(&self) -> Option<u64>;
 
     fn read_subject_value(&self) -> Result<String> {
-        let mut string = String::new();
-        if self.subject_value().is_none() {
-            std::io::stdin().read_line(&mut string)?;
-        } else {
-            string = self.subject_value().as_ref().unwrap().to_string();
-        }
+        let string = match self.subject_value() {
+            Some(value) => value.to_string(),
+            None => read_command_line()?,
+        };
         if string.is_empty() {
             bail!("No value provided");
         }
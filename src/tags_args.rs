@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result, bail};
+use bc_envelope::prelude::*;
+use clap::Args;
+use serde::Deserialize;
+
+use crate::{Error, is_toml};
+
+/// Options for adding CBOR tags and UR types to the standard registry.
+#[derive(Debug, Args)]
+#[group(skip)]
+pub struct TagsArgs {
+    /// A JSON or TOML file of CBOR tags, each with a UR type name, to add to
+    /// the standard ones. May be repeated.
+    ///
+    /// Tags in `~/.envelope/tags.toml` and `~/.envelope/tags.json` are loaded
+    /// automatically.
+    #[arg(long, value_name = "FILE", global = true)]
+    tags: Vec<PathBuf>,
+}
+
+impl TagsArgs {
+    /// Adds the tags in the user's tag files and in the files given on the
+    /// command line to the global tags and the format context.
+    ///
+    /// This must be called after the standard tags are registered, so that
+    /// tags that conflict with them are reported.
+    pub fn load(&self) -> Result<()> {
+        let mut paths: Vec<PathBuf> = user_tags_paths()
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect();
+        paths.extend(self.tags.iter().cloned());
+        for path in paths {
+            for definition in read_tags_file(&path)? {
+                add_tag_definition(definition).with_context(|| {
+                    format!("could not add tags from {}", path.display())
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The tag files in the user's `~/.envelope` directory.
+fn user_tags_paths() -> Vec<PathBuf> {
+    let Some(home) = std::env::home_dir() else {
+        return Vec::new();
+    };
+    let directory = home.join(".envelope");
+    vec![directory.join("tags.toml"), directory.join("tags.json")]
+}
+
+/// A CBOR tag defined in a tags file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagDefinition {
+    /// The tag number.
+    pub value: u64,
+    /// The UR type and tag name, such as `my-thing`.
+    pub name: String,
+    /// A description of the type, shown by `info`.
+    pub description: Option<String>,
+    /// How `format` shows a value with this tag. `{}` is replaced by the
+    /// value's content in CBOR diagnostic notation; without a summary the
+    /// whole tagged value is shown in diagnostic notation.
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsFile {
+    tags: Vec<TagDefinition>,
+}
+
+/// The tags added by [`add_tag_definition`].
+static TAG_DEFINITIONS: Mutex<Vec<TagDefinition>> = Mutex::new(Vec::new());
+
+/// Reads the tags defined in a JSON or TOML file. The format is chosen by
+/// the file extension, and files without a `.toml` extension are read as
+/// JSON.
+///
+/// ```toml
+/// [[tags]]
+/// value = 40500
+/// name = "my-thing"
+/// description = "My Thing"
+/// summary = "MyThing({})"
+/// ```
+pub fn read_tags_file(path: &Path) -> Result<Vec<TagDefinition>> {
+    let text = fs::read_to_string(path).with_context(|| {
+        format!("could not read tags from {}", path.display())
+    })?;
+    let file: TagsFile = if is_toml(path) {
+        toml::from_str(&text).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    }
+    .map_err(|e| {
+        Error::Parse(format!("invalid tags in {}: {}", path.display(), e))
+    })?;
+    Ok(file.tags)
+}
+
+/// Registers a tag in the global tags and the format context, so that its
+/// name can be used as a UR type. A tag whose number or name is already
+/// registered is an error.
+pub fn add_tag_definition(definition: TagDefinition) -> Result<()> {
+    let name = &definition.name;
+    if name.is_empty()
+        || !name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'
+        })
+    {
+        bail!(Error::Parse(format!(
+            "invalid UR type \"{}\": use lowercase letters, digits, and \
+             hyphens",
+            name
+        )));
+    }
+    let tag = Tag::new(definition.value, name.as_str());
+    bc_envelope::with_format_context_mut!(|context: &mut FormatContext| {
+        let tags = context.tags_mut();
+        if let Some(existing) = tags.tag_for_value(definition.value) {
+            bail!(Error::Usage(format!(
+                "tag {} is already registered as \"{}\"",
+                definition.value,
+                existing.name().unwrap_or_default()
+            )));
+        }
+        if tags.tag_for_name(name).is_some() {
+            bail!(Error::Usage(format!(
+                "the UR type \"{}\" is already registered",
+                name
+            )));
+        }
+        tags.insert(tag.clone());
+        if let Some(summary) = &definition.summary {
+            let summary = summary.clone();
+            tags.set_summarizer(
+                definition.value,
+                Arc::new(move |cbor: CBOR, _flat: bool| {
+                    Ok(summary.replace("{}", &cbor.diagnostic_flat()))
+                }),
+            );
+        }
+        Ok(())
+    })?;
+    dcbor::with_tags_mut!(|tags: &mut TagsStore| tags.insert(tag));
+    TAG_DEFINITIONS.lock().unwrap().push(definition);
+    Ok(())
+}
+
+/// Returns the definition of a tag added from a tags file, by UR type.
+pub fn tag_definition_for_name(name: &str) -> Option<TagDefinition> {
+    TAG_DEFINITIONS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|definition| definition.name == name)
+        .cloned()
+}
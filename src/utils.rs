@@ -10,8 +10,11 @@ use std::{
 use anyhow::{Result, bail};
 use bc_components::XID;
 use bc_envelope::prelude::*;
+use bc_ur::MultipartDecoder;
 use bc_xid::XIDDocument;
 
+use crate::Error;
+
 /// Reads a password either from the provided argument, via the system's askpass
 /// tool when enabled, or interactively via rpassword.
 ///
@@ -113,7 +116,7 @@ pub fn read_argument(argument: Option<&str>) -> Result<String> {
         s
     };
     if string.is_empty() {
-        bail!("No argument provided");
+        bail!(Error::MissingInput("No argument provided".to_string()));
     }
     Ok(string)
 }
@@ -130,23 +133,93 @@ pub fn envelope_from_ur(ur: &UR) -> Result<Envelope> {
         let doc = XIDDocument::from(xid);
         return Ok(doc.into_envelope());
     }
-    bail!("Invalid envelope");
+    bail!(Error::Parse("Invalid envelope".to_string()));
 }
 
-pub fn read_envelope(envelope: Option<&str>) -> Result<Envelope> {
+/// Reads the textual form of an envelope from the argument if present,
+/// otherwise from the next line of stdin.
+///
+/// If the input is a set of multipart UR parts, they are reassembled and the
+/// equivalent single-part UR is returned. On the command line the parts are
+/// separated by whitespace; on stdin they are one per line, and reading stops
+/// once enough parts have been received.
+pub fn read_envelope_string(envelope: Option<&str>) -> Result<String> {
     let ur_string = if let Some(env) = envelope {
         env.to_string()
     } else {
         let mut s = String::new();
         std::io::stdin().read_line(&mut s)?;
+        if is_multipart_ur(s.trim()) {
+            let lines = std::io::stdin().lines().map_while(Result::ok);
+            let parts = std::iter::once(s).chain(lines);
+            return Ok(join_multipart_ur(parts)?.string());
+        }
         s
     };
     let ur_string = ur_string.trim();
     if ur_string.is_empty() {
-        bail!("No envelope provided");
+        bail!(Error::MissingInput("No envelope provided".to_string()));
+    }
+    if ur_string.split_whitespace().next().is_some_and(is_multipart_ur) {
+        let parts = ur_string.split_whitespace().map(str::to_string);
+        return Ok(join_multipart_ur(parts)?.string());
+    }
+    Ok(ur_string.to_string())
+}
+
+/// Returns `true` if the string is one part of a multipart UR, such as
+/// `ur:envelope/2-3/...`.
+pub fn is_multipart_ur(s: &str) -> bool {
+    let Some(scheme) = s.get(..3) else {
+        return false;
+    };
+    if !scheme.eq_ignore_ascii_case("ur:") {
+        return false;
+    }
+    let components: Vec<&str> = s[3..].split('/').collect();
+    components.len() == 3
+        && components[1].split_once('-').is_some_and(|(seq, count)| {
+            !seq.is_empty()
+                && !count.is_empty()
+                && seq.bytes().all(|b| b.is_ascii_digit())
+                && count.bytes().all(|b| b.is_ascii_digit())
+        })
+}
+
+/// Reassembles a UR from its fountain-coded parts.
+///
+/// The parts may arrive in any order and may contain duplicates. Parts after
+/// the point where the UR is complete are ignored; blank entries are skipped.
+pub fn join_multipart_ur(
+    parts: impl IntoIterator<Item = String>,
+) -> Result<UR> {
+    let mut decoder = MultipartDecoder::new();
+    for part in parts {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if !is_multipart_ur(part) {
+            bail!(Error::Parse(format!(
+                "expected a multipart UR part, got: {}",
+                part
+            )));
+        }
+        decoder.receive(&part.to_ascii_lowercase())?;
+        if decoder.is_complete() {
+            break;
+        }
+    }
+    match decoder.message()? {
+        Some(ur) => Ok(ur),
+        None => bail!("incomplete multipart UR: more parts are needed"),
     }
-    Envelope::from_ur_string(ur_string)
-        .or_else(|_| envelope_from_ur(&UR::from_ur_string(ur_string)?))
+}
+
+pub fn read_envelope(envelope: Option<&str>) -> Result<Envelope> {
+    let ur_string = read_envelope_string(envelope)?;
+    Envelope::from_ur_string(&ur_string)
+        .or_else(|_| envelope_from_ur(&UR::from_ur_string(&ur_string)?))
 }
 
 pub fn parse_digest(target: &str) -> Result<Digest> {
@@ -155,7 +228,10 @@ pub fn parse_digest(target: &str) -> Result<Digest> {
         "digest" => Digest::from_ur(&ur)?,
         "envelope" => Envelope::from_ur(&ur)?.digest(),
         _ => {
-            bail!("Invalid digest type: {}", ur.ur_type_str());
+            bail!(Error::Parse(format!(
+                "Invalid digest type: {}",
+                ur.ur_type_str()
+            )));
         }
     };
     Ok(digest)
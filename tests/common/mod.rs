@@ -215,3 +215,9 @@ pub fn run_cli_piped(cmds: &[&[&str]]) -> Result<String> {
 pub fn run_cli_raw_piped(cmds: &[&[&str]]) -> Result<String> {
     run_cli_raw_piped_stdin(cmds, "")
 }
+
+/// A path in the temp directory that is unique to this test process.
+pub fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir()
+        .join(format!("envelope-cli-{}-{}", std::process::id(), name))
+}
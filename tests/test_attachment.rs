@@ -237,17 +237,15 @@ fn test_attachment_find() -> Result<()> {
         .len(),
         2
     );
-    assert_eq!(
+    assert!(
         run_cli(&[
             "attachment",
             "find",
             "--vendor",
             "bar",
             &envelope_v1_v2()?
-        ])?
-        .lines()
-        .len(),
-        0
+        ])
+        .is_err()
     );
 
     assert_eq!(
@@ -262,17 +260,15 @@ fn test_attachment_find() -> Result<()> {
         .len(),
         1
     );
-    assert_eq!(
+    assert!(
         run_cli(&[
             "attachment",
             "find",
             "--conforms-to",
             "foo",
             &envelope_v1_v2()?
-        ])?
-        .lines()
-        .len(),
-        0
+        ])
+        .is_err()
     );
 
     Ok(())
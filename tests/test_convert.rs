@@ -0,0 +1,153 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+const PERSON_JSON: &str = r#"{
+    "@subject": "Alice",
+    "knows": ["Bob", "Carol"],
+    "born": {"@type": "date", "@value": "1990-01-01"},
+    "'isA'": {"@type": "known", "@value": "Seed"},
+    "address": {"city": "Boston"}
+}"#;
+
+#[test]
+fn test_convert_from_json() -> Result<()> {
+    let envelope = run_cli(&["convert", "from-json", PERSON_JSON])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["format", &envelope],
+        indoc!(r#"
+            "Alice" [
+                'isA': 'Seed'
+                "address": '' [
+                    "city": "Boston"
+                ]
+                "born": 1990-01-01
+                "knows": "Bob"
+                "knows": "Carol"
+            ]
+        "#)
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_from_json_stdin() -> Result<()> {
+    run_cli_expect_stdin(
+        &["convert", "from-json"],
+        ALICE_KNOWS_BOB_EXAMPLE,
+        r#"{"@subject": "Alice", "knows": "Bob"}"#,
+    )
+}
+
+#[test]
+fn test_convert_to_json() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["convert", "to-json", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"{"@subject":"Alice","knows":"Bob"}"#,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_round_trip() -> Result<()> {
+    let envelope = run_cli(&["convert", "from-json", PERSON_JSON])?;
+    let json = run_cli(&["convert", "to-json", &envelope])?;
+    run_cli_expect(&["convert", "from-json", &json], &envelope)
+}
+
+#[test]
+fn test_convert_to_json_rejects_non_text_predicates() -> Result<()> {
+    let numbered = run_cli_piped(&[
+        &["subject", "type", "string", "Alice"],
+        &["assertion", "add", "pred-obj", "number", "42", "string", "answer"],
+    ])?;
+    let predicate = run_cli_piped(&[
+        &["subject", "type", "string", "knows"],
+        &["assertion", "add", "pred-obj", "string", "since", "number", "2020"],
+    ])?;
+    let annotated = run_cli_piped(&[
+        &["subject", "type", "string", "Alice"],
+        &[
+            "assertion", "add", "pred-obj", "envelope", &predicate, "string",
+            "Bob",
+        ],
+    ])?;
+    for envelope in [&numbered, &annotated] {
+        let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+            .env("RUST_BACKTRACE", "0")
+            .args(["convert", "to-json", envelope])
+            .output()?;
+        assert_eq!(output.status.code(), Some(2));
+        assert!(output.stdout.is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_convert_to_json_escapes_reserved_predicates() -> Result<()> {
+    let envelope = run_cli_piped(&[
+        &["subject", "type", "string", "Alice"],
+        &["assertion", "add", "pred-obj", "string", "@subject", "string", "X"],
+        &["assertion", "add", "pred-obj", "string", "'isA'", "string", "Y"],
+    ])?;
+    run_cli_expect(
+        &["convert", "to-json", &envelope],
+        r#"{"@'isA'":"Y","@@subject":"X","@subject":"Alice"}"#,
+    )?;
+    let json = run_cli(&["convert", "to-json", &envelope])?;
+    run_cli_expect(&["convert", "from-json", &json], &envelope)
+}
+
+#[test]
+fn test_convert_to_json_obscured() -> Result<()> {
+    let bob_digest = run_cli_piped(&[
+        &["subject", "type", "string", "Bob"],
+        &["digest"],
+    ])?;
+    let elided = run_cli(&[
+        "elide",
+        "removing",
+        &bob_digest,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["convert", "to-json", &elided],
+        r#"{"@subject":"Alice","knows":{"@elided":"ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl"}}"#,
+    )?;
+
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--key",
+        KEY_EXAMPLE,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let json = run_cli(&["convert", "to-json", &encrypted])?;
+    assert!(json.starts_with(r#"{"@subject":{"@encrypted":"ur:digest/"#));
+
+    // Obscured nodes can't be converted back.
+    assert!(run_cli(&["convert", "from-json", &json]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_convert_from_json_invalid() {
+    assert!(run_cli(&["convert", "from-json", "[1, 2]"]).is_err());
+    assert!(run_cli(&["convert", "from-json", r#"{"a": [[1]]}"#]).is_err());
+    assert!(
+        run_cli(&[
+            "convert",
+            "from-json",
+            r#"{"a": {"@type": "date", "@value": "yesterday"}}"#
+        ])
+        .is_err()
+    );
+    assert!(run_cli(&["convert", "from-json", r#"{"@bogus": 1}"#]).is_err());
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+fn bob_elided() -> Result<String> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob_digest = run_cli(&["digest", &bob])?;
+    run_cli(&["elide", "removing", &bob_digest, ALICE_KNOWS_BOB_EXAMPLE])
+}
+
+#[test]
+fn test_diff_assertions() -> Result<()> {
+    let changed = run_cli_piped(&[
+        &[
+            "assertion",
+            "add",
+            "pred-obj",
+            "known",
+            "note",
+            "string",
+            "hi",
+            ALICE_KNOWS_BOB_EXAMPLE,
+        ],
+        &[
+            "assertion",
+            "remove",
+            "pred-obj",
+            "string",
+            "knows",
+            "string",
+            "Bob",
+        ],
+    ])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &changed],
+        indoc! {r#"
+            different
+            - 8955db5e "knows": "Bob"
+            + 8955db5e 'note': "hi"
+        "#},
+    )?;
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE],
+        "identical",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_subject() -> Result<()> {
+    let carol = run_cli(&["subject", "type", "string", "Carol"])?;
+    let changed = run_cli(&[
+        "assertion",
+        "add",
+        "pred-obj",
+        "string",
+        "knows",
+        "string",
+        "Bob",
+        &carol,
+    ])?;
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &changed],
+        "different\n~ 8955db5e subject \"Alice\" -> \"Carol\"",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_elision() -> Result<()> {
+    let elided = bob_elided()?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["diff", ALICE_KNOWS_BOB_EXAMPLE, &elided],
+        indoc! {r#"
+            equivalent
+            < 13b74194 elided "Bob"
+        "#},
+    )?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect_stdin(
+        &["diff", &elided],
+        indoc! {r#"
+            equivalent
+            > 13b74194 revealed "Bob"
+        "#},
+        ALICE_KNOWS_BOB_EXAMPLE,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_xid_key_update() -> Result<()> {
+    let xid_doc = run_cli_piped(&[
+        &["xid", "new", "--nickname", "Alice", ALICE_PUBKEYS],
+        &["xid", "key", "add", "--nickname", "Bob", BOB_PUBKEYS],
+    ])?;
+    let updated = run_cli(&[
+        "xid",
+        "key",
+        "update",
+        BOB_PUBKEYS,
+        "--allow",
+        "encrypt",
+        "--allow",
+        "sign",
+        &xid_doc,
+    ])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["diff", &xid_doc, &updated],
+        indoc! {r#"
+            different
+            - c5721468 'allow': 'All'
+            + c5721468 'allow': 'Sign'
+            + c5721468 'allow': 'Encrypt'
+        "#},
+    )?;
+
+    let patch = run_cli(&["diff", "--patch", &xid_doc, &updated])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["format", &patch],
+        indoc! {r#"
+            "patch" [
+                "from": Digest(ebef944e)
+                "operation": "addAssertion" [
+                    "envelope": {
+                        'allow': 'Encrypt'
+                    }
+                    "target": Digest(c5721468)
+                ]
+                "operation": "addAssertion" [
+                    "envelope": {
+                        'allow': 'Sign'
+                    }
+                    "target": Digest(c5721468)
+                ]
+                "operation": "removeAssertion" [
+                    "assertion": Digest(3543810c)
+                    "target": Digest(c5721468)
+                ]
+                "to": Digest(25fd61a4)
+            ]
+        "#},
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_json() -> Result<()> {
+    let elided = bob_elided()?;
+    let output =
+        run_cli(&["diff", "--json", ALICE_KNOWS_BOB_EXAMPLE, &elided])?;
+    let json: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(json["status"], "equivalent");
+    assert_eq!(json["equivalent"], true);
+    assert_eq!(json["identical"], false);
+    assert_eq!(json["changes"][0]["operation"], "elide");
+    assert_eq!(json["changes"][0]["original"], "\"Bob\"");
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+fn run_each_raw(args: &[&str], stdin: &str) -> Result<(bool, String, String)> {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .write_stdin(stdin)
+        .output()?;
+    Ok((
+        output.status.success(),
+        String::from_utf8(output.stdout)?,
+        String::from_utf8(output.stderr)?,
+    ))
+}
+
+#[test]
+fn test_each_digest() -> Result<()> {
+    let stdin =
+        format!("{}\n\n{}\n", HELLO_ENVELOPE_UR, ALICE_KNOWS_BOB_EXAMPLE);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect_stdin(
+        &["digest", "--each"],
+        indoc!(r#"
+            ur:digest/hdcxlksojzuyktbykovsecbygebsldeninbdfptkwebtwzdpadglwetbgltnwdmwhlhksbbthtpy
+            ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds
+        "#),
+        &stdin,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_each_assertion_add() -> Result<()> {
+    let stdin =
+        format!("{}\n{}\n", HELLO_ENVELOPE_UR, ALICE_KNOWS_BOB_EXAMPLE);
+    let output = run_cli_stdin(
+        &[
+            "--each", "assertion", "add", "pred-obj", "string", "note",
+            "string", "batch",
+        ],
+        &stdin,
+    )?;
+    let results: Vec<&str> = output.lines().collect();
+    assert_eq!(results.len(), 2);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["format", results[1]],
+        indoc!(r#"
+            "Alice" [
+                "knows": "Bob"
+                "note": "batch"
+            ]
+        "#)
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_each_fails_fast() -> Result<()> {
+    let stdin = format!(
+        "{}\nur:bad\n{}\n",
+        HELLO_ENVELOPE_UR, ALICE_KNOWS_BOB_EXAMPLE
+    );
+    let (success, stdout, stderr) =
+        run_each_raw(&["format", "--each"], &stdin)?;
+    assert!(!success);
+    assert_actual_expected!(stdout, "\"Hello.\"\n");
+    assert_actual_expected!(stderr, "Error: line 2: no UR type specified\n");
+    Ok(())
+}
+
+#[test]
+fn test_each_keep_going() -> Result<()> {
+    let stdin = format!(
+        "{}\nur:bad\n{}\n",
+        HELLO_ENVELOPE_UR, ALICE_KNOWS_BOB_EXAMPLE
+    );
+    let (success, stdout, stderr) =
+        run_each_raw(&["format", "--each", "--keep-going"], &stdin)?;
+    assert!(!success);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(stdout, indoc!(r#"
+        "Hello."
+        "Alice" [
+            "knows": "Bob"
+        ]
+    "#));
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(stderr, indoc!(r#"
+        Error: line 2: no UR type specified
+        Error: 1 of 3 envelopes failed
+    "#));
+    Ok(())
+}
+
+#[test]
+fn test_each_rejects_envelope_argument() -> Result<()> {
+    // The stream on stdin would be ignored in favor of the argument.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["digest", "--each", HELLO_ENVELOPE_UR])
+        .write_stdin(format!("{}\n", ALICE_KNOWS_BOB_EXAMPLE))
+        .output()?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        String::from_utf8(output.stderr)?,
+        "Error: line 1: --each reads the envelopes from stdin; omit the envelope argument\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_each_argument_commands() -> Result<()> {
+    // Commands that read an argument rather than an envelope get each line
+    // as their argument.
+    let stdin =
+        format!("{}\n{}\n", HELLO_ENVELOPE_UR, ALICE_KNOWS_BOB_EXAMPLE);
+    let output =
+        run_cli_stdin(&["subject", "type", "string", "--each"], &stdin)?;
+    let results: Vec<&str> = output.lines().collect();
+    assert_eq!(results.len(), 2);
+    run_cli_expect(
+        &["extract", "string", results[1]],
+        ALICE_KNOWS_BOB_EXAMPLE,
+    )?;
+
+    let output = run_cli_stdin(&["info", "--each"], &stdin)?;
+    assert_eq!(output.matches("Format: ur:envelope").count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_each_rejects_commands_without_input() -> Result<()> {
+    // These commands ignore stdin, so they would repeat one result per line.
+    for args in [
+        vec!["generate", "nonce", "--each"],
+        vec!["subject", "type", "string", "Alice", "--each"],
+        vec!["info", HELLO_ENVELOPE_UR, "--each"],
+    ] {
+        let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+            .env("RUST_BACKTRACE", "0")
+            .args(&args)
+            .write_stdin(format!("{}\n", ALICE_KNOWS_BOB_EXAMPLE))
+            .output()?;
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(output.stdout.is_empty());
+        assert_actual_expected!(
+            String::from_utf8(output.stderr)?,
+            "Error: this command doesn't read its input from stdin, so it \
+             can't be used with --each\n"
+        );
+    }
+    Ok(())
+}
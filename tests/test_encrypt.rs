@@ -47,3 +47,223 @@ fn test_encrypt_password() {
     .unwrap();
     assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
 }
+
+#[test]
+fn test_encrypt_whole() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--whole",
+        "--key",
+        KEY_EXAMPLE,
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--password",
+        "password",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    // The assertions are encrypted along with the subject. Sort the lines,
+    // since the lock order varies from run to run.
+    let format = run_cli(&["format", &encrypted]).unwrap();
+    let mut lines: Vec<&str> = format.lines().collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        [
+            "    'hasRecipient': SealedMessage",
+            "    'hasSecret': EncryptedKey(Argon2id)",
+            "ENCRYPTED [",
+            "]",
+        ]
+    );
+
+    // Any of the locks opens it.
+    for credential in [
+        ["--key", KEY_EXAMPLE],
+        ["--recipient", ALICE_PRVKEYS],
+        ["--password", "password"],
+    ] {
+        let decrypted = run_cli(
+            &[&["decrypt", "--whole"][..], &credential, &[&encrypted]].concat(),
+        )
+        .unwrap();
+        assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
+    }
+
+    // Without --whole, decrypting leaves the envelope wrapped.
+    let decrypted = run_cli_piped(&[
+        &["decrypt", "--key", KEY_EXAMPLE, &encrypted],
+        &["extract", "wrapped"],
+    ])
+    .unwrap();
+    assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
+
+    // An envelope whose subject alone was encrypted can't be unwrapped.
+    let subject_only =
+        run_cli(&["encrypt", "--key", KEY_EXAMPLE, ALICE_KNOWS_BOB_EXAMPLE])
+            .unwrap();
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["decrypt", "--whole", "--key", KEY_EXAMPLE, &subject_only])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_decrypt_locks() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--recipient",
+        BOB_PUBKEYS,
+        "--password",
+        "password",
+        "--password-derivation",
+        "scrypt",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    // The locks are listed in assertion order, which depends on the random
+    // content key.
+    let locks = run_cli(&["decrypt", "--locks", &encrypted]).unwrap();
+    let mut locks: Vec<&str> = locks.lines().collect();
+    locks.sort();
+    assert_eq!(
+        locks,
+        ["recipient X25519", "recipient X25519", "secret Scrypt"]
+    );
+
+    // Without a credential, the error names the locks.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["decrypt", &encrypted])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("locked by 2 X25519 recipients, 1 Scrypt secret"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_decrypt_multiple_credentials() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--recipient",
+        BOB_PUBKEYS,
+        "--password",
+        "password",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    let expected =
+        run_cli(&["decrypt", "--recipient", BOB_PRVKEY_BASE, &encrypted])
+            .unwrap();
+
+    // Each credential is tried in turn until one works.
+    let decrypted = run_cli(&[
+        "decrypt",
+        "--key",
+        KEY_EXAMPLE,
+        "--recipient",
+        ALICE_PRVKEY_BASE,
+        "--recipient",
+        BOB_PRVKEY_BASE,
+        &encrypted,
+    ])
+    .unwrap();
+    assert_eq!(decrypted, expected);
+    let decrypted = run_cli(&[
+        "decrypt",
+        "--recipient",
+        ALICE_PRVKEY_BASE,
+        "--password",
+        "password",
+        &encrypted,
+    ])
+    .unwrap();
+    assert_eq!(decrypted, expected);
+
+    // Recipients' private keys can also come from a keyring file.
+    let keyring = std::env::temp_dir()
+        .join(format!("envelope-{}-keyring", std::process::id()));
+    std::fs::write(
+        &keyring,
+        format!(
+            "# Alice\n{}\n\n# Bob\n{}\n",
+            ALICE_PRVKEY_BASE, BOB_PRVKEY_BASE
+        ),
+    )
+    .unwrap();
+    let keyring = keyring.display().to_string();
+    let decrypted =
+        run_cli(&["decrypt", "--keyring", &keyring, &encrypted]).unwrap();
+    std::fs::remove_file(&keyring).unwrap();
+    assert_eq!(decrypted, expected);
+
+    // When none works, the error says why each one failed.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["decrypt", "--recipient", ALICE_PRVKEY_BASE, "--password"])
+        .args(["wrong", &encrypted])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(6));
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Error: decrypt failed: none of the 2 credentials unlocks the content key, which is locked by 1 X25519 recipient, 1 Argon2id secret (recipient 1: the private key is not a recipient of this envelope; password: wrong password)\n"
+    );
+}
+
+#[test]
+fn test_encrypt_mlkem() {
+    let keypairs =
+        run_cli(&["generate", "keypairs", "--encryption", "mlkem768"]).unwrap();
+    let (pq_prvkeys, pq_pubkeys) = keypairs.split_once(' ').unwrap();
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["info", pq_pubkeys],
+        indoc!(r#"
+            Format: ur:crypto-pubkeys
+            CBOR Size: 1232
+            Description: Public Keys
+            Signing: Schnorr
+            Encapsulation: MLKEM768
+        "#)
+    ).unwrap();
+
+    // Encrypt to a classical and a post-quantum recipient together.
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--whole",
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--recipient",
+        pq_pubkeys,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    let info = run_cli(&["info", &encrypted]).unwrap();
+    assert!(
+        info.ends_with("Locks: 1 MLKEM768 recipient, 1 X25519 recipient"),
+        "{info}"
+    );
+    for prvkeys in [ALICE_PRVKEYS, pq_prvkeys] {
+        let decrypted = run_cli(&[
+            "decrypt",
+            "--whole",
+            "--recipient",
+            prvkeys,
+            &encrypted,
+        ])
+        .unwrap();
+        assert_eq!(decrypted, ALICE_KNOWS_BOB_EXAMPLE);
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+fn run_cli_status(args: &[&str]) -> Result<(i32, String)> {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .write_stdin("")
+        .output()?;
+    Ok((
+        output.status.code().unwrap_or(-1),
+        String::from_utf8(output.stderr)?,
+    ))
+}
+
+#[test]
+fn test_exit_codes() -> Result<()> {
+    let signed =
+        run_cli(&["sign", "--signer", ALICE_PRVKEY_BASE, HELLO_ENVELOPE_UR])?;
+    let encrypted =
+        run_cli(&["encrypt", "--key", KEY_EXAMPLE, HELLO_ENVELOPE_UR])?;
+    let wrong_key = run_cli(&["generate", "key"])?;
+    let xid_doc = run_cli(&["xid", "new", ALICE_PUBKEYS])?;
+    let other_xid_doc = run_cli(&["xid", "new", BOB_PUBKEYS])?;
+
+    let cases: &[(&[&str], i32)] = &[
+        (&["format", "--bogus"], 2),
+        (&["format", "ur:bad"], 3),
+        (&["format"], 4),
+        (&["match", "number", HELLO_ENVELOPE_UR], 5),
+        (&["assertion", "at", "1", ALICE_KNOWS_BOB_EXAMPLE], 5),
+        (
+            &[
+                "assertion",
+                "find",
+                "predicate",
+                "string",
+                "loves",
+                ALICE_KNOWS_BOB_EXAMPLE,
+            ],
+            5,
+        ),
+        (&["xid", "key", "find", "name", "Wolf", &xid_doc], 5),
+        (&["xid", "key", "find", "public", BOB_PUBKEYS, &xid_doc], 5),
+        (&["xid", "service", "find", "name", "Wolf", &xid_doc], 5),
+        (
+            &["xid", "service", "find", "uri", "https://example.com", &xid_doc],
+            5,
+        ),
+        (&["xid", "delegate", "find", &other_xid_doc, &xid_doc], 5),
+        (&["xid", "edge", "find", &xid_doc], 5),
+        (&["xid", "attachment", "find", &xid_doc], 5),
+        (&["attachment", "find", HELLO_ENVELOPE_UR], 5),
+        (&["decrypt", "--key", &wrong_key, &encrypted], 6),
+        (&["verify", "--verifier", BOB_PUBKEYS, &signed], 7),
+    ];
+    for (args, expected) in cases {
+        let (code, stderr) = run_cli_status(args)?;
+        assert_eq!(code, *expected, "{:?}: {}", args, stderr);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_error_messages() -> Result<()> {
+    let encrypted =
+        run_cli(&["encrypt", "--key", KEY_EXAMPLE, HELLO_ENVELOPE_UR])?;
+    let (_, stderr) = run_cli_status(&["decrypt", "-p", "secret", &encrypted])?;
+    assert_actual_expected!(
+        stderr,
+        "Error: envelope is not locked with a password\n"
+    );
+
+    let (_, stderr) = run_cli_status(&[
+        "decrypt",
+        "--recipient",
+        BOB_PRVKEY_BASE,
+        HELLO_ENVELOPE_UR,
+    ])?;
+    assert_actual_expected!(stderr, "Error: envelope subject is not encrypted\n");
+    Ok(())
+}
+
+#[test]
+fn test_json_errors() -> Result<()> {
+    let (code, stderr) = run_cli_status(&["format", "--json", "ur:bad"])?;
+    assert_eq!(code, 3);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        stderr,
+        "{\"error\":\"parse\",\"exit_code\":3,\"message\":\"no UR type specified\"}\n"
+    );
+
+    let (code, stderr) = run_cli_status(&["format", "--json", "--bogus"])?;
+    assert_eq!(code, 2);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        stderr,
+        "{\"error\":\"usage\",\"exit_code\":2,\"message\":\"unexpected argument '--bogus' found\"}\n"
+    );
+    Ok(())
+}
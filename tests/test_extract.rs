@@ -2,6 +2,7 @@ mod common;
 use anyhow::Result;
 use bc_envelope::prelude::*;
 use common::*;
+use indoc::indoc;
 
 const ALICE_KNOWS_BOB: &str =
     "ur:envelope/lftpsoihfpjziniaihoytpsoihjejtjlktjktpsoiafwjlidutgmnnns";
@@ -165,3 +166,100 @@ fn test_extract_assertion_subject() -> Result<()> {
     run_cli_expect(&["extract", "predicate", &ur], predicate_envelope)?;
     run_cli_expect(&["extract", "object", &ur], object_envelope)
 }
+
+#[test]
+fn test_extract_array() -> Result<()> {
+    let e = run_cli(&[
+        "subject",
+        "type",
+        "cbor",
+        "[\"a\", -2, 1.5, h'0102', 1(1700000000), 32(\"https://example.com\"), \
+         [1, 2]]",
+    ])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["extract", "array", &e],
+        indoc! {r#"
+            a
+            -2
+            1.5
+            0102
+            2023-11-14T22:13:20Z
+            https://example.com
+            [1,2]
+        "#},
+    )?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["--json", "extract", "array", &e],
+        r#"["a",-2,1.5,"0102","2023-11-14T22:13:20Z","https://example.com",[1,2]]"#,
+    )?;
+
+    let dates = run_cli(&["subject", "type", "cbor", "[1(1700000000), 1(0)]"])?;
+    run_cli_expect(
+        &["extract", "array", "--element", "date", &dates],
+        "2023-11-14T22:13:20Z\n1970-01-01",
+    )?;
+    assert!(run_cli(&["extract", "array", "--element", "int", &e]).is_err());
+    assert!(run_cli(&["extract", "array", ALICE_KNOWS_BOB]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_extract_map() -> Result<()> {
+    let e = run_cli(&[
+        "subject",
+        "type",
+        "cbor",
+        "{1: \"a\", 2: \"b\", 3: [\"c\", 4]}",
+    ])?;
+    run_cli_expect(&["extract", "map", &e], "1: a\n2: b\n3: [\"c\",4]")?;
+    run_cli_expect(
+        &["--json", "extract", "map", &e],
+        r#"{"1":"a","2":"b","3":["c",4]}"#,
+    )?;
+    assert!(
+        run_cli(&["extract", "map", "--element", "string", &e]).is_err()
+    );
+    run_cli_expect(
+        &["extract", "map", "--key", "int", "--element", "cbor", &e],
+        "1: 6161\n2: 6162\n3: 82616304",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_extract_rejects_lossy_values() -> Result<()> {
+    let duplicate = run_cli(&["subject", "type", "cbor", r#"{1: 2, "1": 3}"#])?;
+    let nested = run_cli(&["subject", "type", "cbor", r#"[{1: 2, "1": 3}]"#])?;
+    let nan = run_cli(&["subject", "type", "cbor", "[1.5, NaN]"])?;
+    let infinity = run_cli(&["subject", "type", "cbor", "{1: Infinity}"])?;
+    for (args, error) in [
+        (
+            &["extract", "map", &duplicate] as &[&str],
+            "Error: duplicate map key: 1\n",
+        ),
+        (
+            &["extract", "array", &nested],
+            "Error: element 0: duplicate map key: 1\n",
+        ),
+        (
+            &["extract", "array", &nan],
+            "Error: element 1: NaN cannot be represented in JSON\n",
+        ),
+        (
+            &["extract", "map", &infinity],
+            "Error: value of 1: inf cannot be represented in JSON\n",
+        ),
+    ] {
+        let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+            .args(args)
+            .env("RUST_BACKTRACE", "0")
+            .output()?;
+        assert_eq!(output.status.code(), Some(3));
+        assert_actual_expected!(String::from_utf8(output.stderr)?, error);
+    }
+    Ok(())
+}
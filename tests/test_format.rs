@@ -239,3 +239,124 @@ fn test_format_tree() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_format_dot() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["format", "--type", "dot", ALICE_KNOWS_BOB_EXAMPLE],
+        indoc! {r#"
+            digraph envelope {
+                rankdir=LR;
+                node [fontname="Helvetica", penwidth=2];
+                edge [fontname="Helvetica", penwidth=2];
+                0 [label="NODE\n8955db5e", shape=circle, color=red];
+                1 [label="\"Alice\"\n13941b48", shape=box, color=teal];
+                2 [label="ASSERTION\n78d666eb", shape=box, style=rounded, color=green];
+                3 [label="\"knows\"\ndb7dd21c", shape=box, color=teal];
+                4 [label="\"Bob\"\n13b74194", shape=box, color=teal];
+                0 -> 1 [label="subj", color=red];
+                0 -> 2;
+                2 -> 3 [label="pred", color=cyan];
+                2 -> 4 [label="obj", color=magenta];
+            }
+        "#},
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_format_dot_hide_nodes() -> Result<()> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob_digest = run_cli(&["digest", &bob])?;
+    let elided =
+        run_cli(&["elide", "removing", &bob_digest, ALICE_KNOWS_BOB_EXAMPLE])?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &[
+            "format", "--type", "dot", "--hide-nodes", "--monochrome",
+            "--orientation", "top-to-bottom", &elided,
+        ],
+        indoc! {r#"
+            digraph envelope {
+                rankdir=TB;
+                node [fontname="Helvetica", penwidth=2];
+                edge [fontname="Helvetica", penwidth=2];
+                0 [label="\"Alice\"", shape=box];
+                1 [label="ASSERTION", shape=box, style=rounded];
+                2 [label="\"knows\"", shape=box];
+                3 [label="ELIDED", shape=hexagon];
+                0 -> 1;
+                1 -> 2 [label="pred"];
+                1 -> 3 [label="obj"];
+            }
+        "#},
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_format_svg() -> Result<()> {
+    let output =
+        run_cli(&["format", "--type", "svg", ALICE_KNOWS_BOB_EXAMPLE])?;
+    assert!(output.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(output.ends_with("</svg>"));
+    assert_eq!(output.matches("<ellipse ").count(), 1);
+    assert_eq!(output.matches("<rect ").count(), 5);
+    assert_eq!(output.matches("<line ").count(), 4);
+    assert!(output.contains(">&quot;Alice&quot;</text>"));
+    assert!(output.contains(">8955db5e</text>"));
+
+    let output = run_cli(&[
+        "format",
+        "--type",
+        "svg",
+        "--hide-nodes",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    assert_eq!(output.matches("<ellipse ").count(), 0);
+    assert_eq!(output.matches("<line ").count(), 3);
+    assert!(!output.contains(">8955db5e</text>"));
+    Ok(())
+}
+
+#[test]
+fn test_format_html() -> Result<()> {
+    let envelope = run_cli(&[
+        "assertion",
+        "add",
+        "pred-obj",
+        "known",
+        "note",
+        "string",
+        "hi",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob_digest = run_cli(&["digest", &bob])?;
+    let envelope = run_cli(&["elide", "removing", &bob_digest, &envelope])?;
+    let output = run_cli(&["format", "--type", "html", &envelope])?;
+    assert!(output.starts_with("<!DOCTYPE html>"));
+    assert!(output.ends_with("</html>"));
+    assert!(output.contains(indoc! {r#"
+        <pre>&quot;Alice&quot; [
+            &quot;knows&quot;: ELIDED
+            'note': &quot;hi&quot;
+        ]</pre>"#}));
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert!(output.contains(
+        r#"<li><span class="edge">obj</span><span class="element elided" title="13b741949c37b8e09cc3daa3194c58e4fd6b2f14d4b1d0f035a46d6d5a1d3f11&#10;ur:digest/hdcxbwrlfpmwnsemrovtnssrtnotcfgshdvezcjedlbbtypatiwtecoxjnjnhtcafhbysptsnsnl">ELIDED</span><span class="id">13b74194</span></li>"#
+    ));
+    assert!(output.contains(r##"<a href="#known-value-4">'note'</a>"##));
+    assert!(output.contains(r#"<dt id="known-value-4">note</dt><dd>4</dd>"#));
+    assert_eq!(output.matches("<details open>").count(), 3);
+
+    let output =
+        run_cli(&["format", "--type", "html", "--hide-nodes", &envelope])?;
+    assert!(!output.contains(r#"class="element node""#));
+    assert!(!output.contains(r#"<span class="id">"#));
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+const ALICE_KNOWS_BOB_HEX: &str =
+    "d8c882d8c965416c696365a1d8c9656b6e6f7773d8c963426f62";
+
+const ALICE_KNOWS_BOB_FORMAT: &str = indoc!(r#"
+    "Alice" [
+        "knows": "Bob"
+    ]
+"#);
+
+fn run_cli_binary_stdin(args: &[&str], stdin: Vec<u8>) -> Result<String> {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(args)
+        .write_stdin(stdin)
+        .output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+#[test]
+fn test_input_hex() -> Result<()> {
+    run_cli_expect(&["format", ALICE_KNOWS_BOB_HEX], ALICE_KNOWS_BOB_FORMAT)?;
+    run_cli_expect(
+        &["format", "--in-format", "hex", ALICE_KNOWS_BOB_HEX],
+        ALICE_KNOWS_BOB_FORMAT,
+    )?;
+    run_cli_expect_stdin(
+        &["format", "--type", "ur"],
+        ALICE_KNOWS_BOB_EXAMPLE,
+        ALICE_KNOWS_BOB_HEX,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_input_binary_cbor_stdin() -> Result<()> {
+    let data = hex::decode(ALICE_KNOWS_BOB_HEX)?;
+    let output = run_cli_binary_stdin(&["format"], data.clone())?;
+    assert_actual_expected!(output, ALICE_KNOWS_BOB_FORMAT.trim());
+    let output =
+        run_cli_binary_stdin(&["digest", "--in-format", "cbor"], data)?;
+    assert_actual_expected!(
+        output,
+        "ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_input_files() -> Result<()> {
+    let cbor_path = temp_path("alice.cbor");
+    std::fs::write(&cbor_path, hex::decode(ALICE_KNOWS_BOB_HEX)?)?;
+    run_cli_expect(
+        &["format", "--in", cbor_path.to_str().unwrap()],
+        ALICE_KNOWS_BOB_FORMAT,
+    )?;
+
+    let ur_path = temp_path("alice.ur");
+    std::fs::write(&ur_path, format!("{}\n", ALICE_KNOWS_BOB_EXAMPLE))?;
+    run_cli_expect(
+        &["format", "--in", ur_path.to_str().unwrap(), "--type", "cbor"],
+        ALICE_KNOWS_BOB_HEX,
+    )?;
+
+    let hex_path = temp_path("alice.hex");
+    std::fs::write(&hex_path, ALICE_KNOWS_BOB_HEX)?;
+    run_cli_expect(
+        &[
+            "format",
+            "--in",
+            hex_path.to_str().unwrap(),
+            "--in-format",
+            "hex",
+        ],
+        ALICE_KNOWS_BOB_FORMAT,
+    )?;
+
+    std::fs::remove_file(cbor_path)?;
+    std::fs::remove_file(ur_path)?;
+    std::fs::remove_file(hex_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_input_format_mismatch() -> Result<()> {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(["format", "--in-format", "cbor", ALICE_KNOWS_BOB_EXAMPLE])
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    assert_eq!(output.status.code(), Some(2));
+    assert_actual_expected!(
+        String::from_utf8(output.stderr)?,
+        "Error: binary CBOR input must be read from --in or stdin\n"
+    );
+    assert!(
+        run_cli(&["format", "--in-format", "ur", ALICE_KNOWS_BOB_HEX]).is_err()
+    );
+    assert!(run_cli(&["format", "not an envelope"]).is_err());
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_json_info() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["info", "--json", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"{"cbor_size":24,"description":"Gordian Envelope","format":"ur:envelope"}"#,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_json_digest() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["digest", "--json", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"[{"digest":"ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds","hex":"8955db5e016affb133df56c11fe6c5c82fa3036263d651286d134c7e56c0e9f2"}]"#,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_json_walk() -> Result<()> {
+    let output = run_cli(&["walk", "--json", ALICE_KNOWS_BOB_EXAMPLE])?;
+    let nodes: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(nodes.as_array().unwrap().len(), 5);
+    run_cli_expect(
+        &["walk", "--json", ALICE_KNOWS_BOB_EXAMPLE, "matching", "--elided"],
+        "[]",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_json_match() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["match", "--json", "--last-only", "assertpred(text)", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"[[{"digest":"ur:digest/hdcxkstbiywmmygsasktnbfwhtrppkclwdcmmugejesokejlbnftrdwspsmdcechbboerhzebtws","summary":"78d666eb ASSERTION \"knows\": \"Bob\""}]]"#,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_json_assertion_find() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["assertion", "find", "predicate", "string", "knows", "--json", ALICE_KNOWS_BOB_EXAMPLE],
+        r#"[{"digest":"ur:digest/hdcxkstbiywmmygsasktnbfwhtrppkclwdcmmugejesokejlbnftrdwspsmdcechbboerhzebtws","envelope":"ur:envelope/oytpsoihjejtjlktjktpsoiafwjlidgdvttdjn","object":"\"Bob\"","predicate":"\"knows\""}]"#,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_json_xid() -> Result<()> {
+    let xid_doc = run_cli(&[
+        "xid",
+        "new",
+        ALICE_PUBKEYS,
+        "--nickname",
+        "Alice",
+        "--endpoint",
+        "https://example.com",
+    ])?;
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["xid", "id", "--json", &xid_doc],
+        r#"{"bytemoji":"🅧 🛵 📦 🧦 🐔","bytewords":"🅧 MENU ONYX TINY VOID","hex":"XID(93a4d4e7)","ur":"ur:xid/hdcxmuoxtyvddifztyryhymkgolbmefhssmejsgaykcljtjnfmaelrrkvwayehbzfesspmwerowy"}"#,
+    )?;
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["xid", "key", "all", "--json", &xid_doc],
+        r#"[{"endpoints":["https://example.com"],"has_private_key":false,"nickname":"Alice","permissions":{"allow":["all"],"deny":[]},"private_key_encrypted":false,"public_keys":"ur:crypto-pubkeys/lftanshfhdcxrdhgfsfsfsosrloebgwmfrfhsnlskegsjydecawybniadyzovehncacnlbmdbesstansgrhdcxytgefrmnbzftltcmcnaspaimhftbjehlatjklkhktidrpmjobslewkfretcaetbnwksorlbd"}]"#,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn test_json_xid_key_encrypted() -> Result<()> {
+    let xid_doc = run_cli(&[
+        "xid",
+        "new",
+        ALICE_PRVKEYS,
+        "--private",
+        "encrypt",
+        "--encrypt-password",
+        "secret",
+    ])?;
+    let output = run_cli(&["xid", "key", "all", "--json", &xid_doc])?;
+    let keys: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(keys[0]["has_private_key"], true);
+    assert_eq!(keys[0]["private_key_encrypted"], true);
+    assert_eq!(keys[0]["nickname"], serde_json::Value::Null);
+    assert!(keys[0].get("private_key").is_none());
+
+    let output = run_cli(&[
+        "xid",
+        "key",
+        "all",
+        "--json",
+        "--private",
+        "--password",
+        "secret",
+        &xid_doc,
+    ])?;
+    let keys: serde_json::Value = serde_json::from_str(&output)?;
+    assert_eq!(keys[0]["private_key"], ALICE_PRVKEYS);
+    Ok(())
+}
+
+#[test]
+fn test_json_with_out_format_fails() {
+    assert!(
+        run_cli(&[
+            "digest",
+            "--json",
+            "--out-format",
+            "hex",
+            ALICE_KNOWS_BOB_EXAMPLE
+        ])
+        .is_err()
+    );
+}
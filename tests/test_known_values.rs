@@ -0,0 +1,109 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+const TOML_VALUES: &str = indoc! {r#"
+    [[entries]]
+    codepoint = 100000
+    name = "myPredicate"
+    description = "An example predicate."
+"#};
+
+const JSON_VALUES: &str =
+    r#"{ "entries": [ { "codepoint": 100001, "name": "myValue" } ] }"#;
+
+/// Writes `contents` to a file in the temporary directory, returning its
+/// path.
+fn write_temp(name: &str, contents: &str) -> Result<String> {
+    let path = std::env::temp_dir()
+        .join(format!("envelope-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents)?;
+    Ok(path.display().to_string())
+}
+
+#[test]
+fn test_known_values_file() -> Result<()> {
+    let toml = write_temp("known-values.toml", TOML_VALUES)?;
+    let json = write_temp("known-values.json", JSON_VALUES)?;
+    let options = ["--known-values", &toml, "--known-values", &json];
+
+    let e = run_cli(
+        &[
+            &["assertion", "add", "pred-obj", "known", "myPredicate"],
+            &["known", "myValue", ALICE_KNOWS_BOB_EXAMPLE][..],
+            &options,
+        ]
+        .concat(),
+    )?;
+    let with_names = run_cli(&[&["format", &e][..], &options].concat())?;
+    #[rustfmt::skip]
+    // expected-text-output-rubric:
+    assert_actual_expected!(with_names, indoc! {r#"
+        "Alice" [
+            "knows": "Bob"
+            'myPredicate': 'myValue'
+        ]
+    "#}.trim());
+    // Without the files, the values are shown by number.
+    #[rustfmt::skip]
+    // expected-text-output-rubric:
+    run_cli_expect(&["format", &e], indoc! {r#"
+        "Alice" [
+            "knows": "Bob"
+            '100000': '100001'
+        ]
+    "#}.trim())?;
+
+    let matched = run_cli(
+        &[&["match", "search('myPredicate')", &e][..], &options].concat(),
+    )?;
+    assert!(matched.contains("KNOWN_VALUE 'myPredicate'"));
+
+    assert!(run_cli(&["subject", "type", "known", "myPredicate"]).is_err());
+    std::fs::remove_file(&toml)?;
+    std::fs::remove_file(&json)?;
+    Ok(())
+}
+
+#[test]
+fn test_known_values_config_directory() -> Result<()> {
+    let home = std::env::temp_dir()
+        .join(format!("envelope-{}-home", std::process::id()));
+    std::fs::create_dir_all(home.join(".known-values"))?;
+    std::fs::write(home.join(".known-values/mine.toml"), TOML_VALUES)?;
+
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("HOME", &home)
+        .args(["subject", "type", "known", "myPredicate"])
+        .output()?;
+    std::fs::remove_dir_all(&home)?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?.trim(),
+        "ur:envelope/cyaeadlnnbhfrsmndw"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_known_values_file_errors() -> Result<()> {
+    let missing_name = write_temp(
+        "missing-name.json",
+        r#"{ "entries": [ { "codepoint": 1 } ] }"#,
+    )?;
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["--known-values", &missing_name, "format", HELLO_ENVELOPE_UR])
+        .output()?;
+    std::fs::remove_file(&missing_name)?;
+    assert_eq!(output.status.code(), Some(3));
+
+    let missing_file = ["--known-values", "/nonexistent.json"];
+    assert!(
+        run_cli(&[&["format", HELLO_ENVELOPE_UR][..], &missing_file].concat())
+            .is_err()
+    );
+    Ok(())
+}
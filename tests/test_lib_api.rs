@@ -0,0 +1,117 @@
+use anyhow::Result;
+use bc_components::{PrivateKeys, PublicKeys, SymmetricKey};
+use bc_envelope::prelude::*;
+use bc_envelope_cli::{
+    decrypt::{UnlockMethod, decrypt},
+    encrypt::{EncryptOptions, encrypt},
+    envelope_from_ur,
+    sign::{SignOptions, SignerKey, sign},
+    sskr::{
+        join::sskr_join,
+        split::{SskrSplitOptions, parse_group_spec, sskr_split},
+    },
+    verify::{VerifierKey, verify},
+    xid::{GeneratorOptions, PrivateOptions, export::xid_export_elided},
+};
+use bc_xid::{XIDDocument, XIDVerifySignature};
+
+mod common;
+use common::*;
+
+fn hello() -> Envelope { Envelope::from_ur_string(HELLO_ENVELOPE_UR).unwrap() }
+
+#[test]
+fn test_lib_sskr_split_join() -> Result<()> {
+    bc_envelope::register_tags();
+    let options = SskrSplitOptions {
+        group_threshold: 1,
+        groups: vec![parse_group_spec("2-of-3")?],
+        recipients: vec![PublicKeys::from_ur_string(ALICE_PUBKEYS)?],
+        ..Default::default()
+    };
+    let shares = sskr_split(&hello(), &options)?;
+    assert_eq!(shares.len(), 3);
+    assert!(shares.iter().all(|share| share.recipients().unwrap().len() == 1));
+
+    assert_eq!(sskr_join(&shares[1..])?.ur_string(), HELLO_ENVELOPE_UR);
+    assert!(sskr_join(&shares[..1]).is_err());
+    assert!(parse_group_spec("two-of-three").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_lib_encrypt_decrypt() -> Result<()> {
+    bc_envelope::register_tags();
+    let key = SymmetricKey::from_ur_string(KEY_EXAMPLE)?;
+    let options = EncryptOptions {
+        key: Some(key.clone()),
+        recipients: vec![PublicKeys::from_ur_string(ALICE_PUBKEYS)?],
+        password: Some("secret".to_string()),
+        ..Default::default()
+    };
+    let encrypted = encrypt(&hello(), &options)?;
+
+    for method in [
+        UnlockMethod::Key(key),
+        UnlockMethod::Password("secret".to_string()),
+        UnlockMethod::Recipient(PrivateKeys::from_ur_string(ALICE_PRVKEYS)?),
+    ] {
+        let decrypted = decrypt(&encrypted, &method)?;
+        assert_eq!(decrypted.subject().ur_string(), HELLO_ENVELOPE_UR);
+    }
+    assert!(decrypt(
+        &encrypted,
+        &UnlockMethod::parse_recipient(BOB_PRVKEY_BASE)?
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_lib_sign_verify() -> Result<()> {
+    bc_envelope::register_tags();
+    let signers = vec![SignerKey::from_ur_string(ALICE_PRVKEYS)?];
+    let signed = sign(&hello(), &signers, &SignOptions::default())?;
+
+    let alice = VerifierKey::from_ur_string(ALICE_PUBKEYS)?;
+    let bob = VerifierKey::from_ur_string(BOB_PUBKEYS)?;
+    verify(&signed, std::slice::from_ref(&alice), 1)?;
+    assert!(verify(&signed, std::slice::from_ref(&bob), 1).is_err());
+    assert!(verify(&signed, &[alice, bob], 2).is_err());
+    assert!(SignerKey::from_ur_string(ALICE_PUBKEYS).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_lib_xid_export_elided() -> Result<()> {
+    bc_envelope::register_tags();
+    let signed_xid = run_cli(&[
+        "xid",
+        "new",
+        ALICE_PRVKEYS,
+        "--sign",
+        "inception",
+    ])?;
+    let envelope = envelope_from_ur(&UR::from_ur_string(&signed_xid)?)?;
+
+    let elided = xid_export_elided(
+        &envelope,
+        PrivateOptions::Elide,
+        GeneratorOptions::Elide,
+        XIDVerifySignature::Inception,
+    )?;
+    assert!(!elided.is_identical_to(&envelope));
+    assert_eq!(elided.digest(), envelope.digest());
+    XIDDocument::from_envelope(&elided, None, XIDVerifySignature::Inception)?;
+
+    assert!(
+        xid_export_elided(
+            &envelope,
+            PrivateOptions::Omit,
+            GeneratorOptions::Include,
+            XIDVerifySignature::None,
+        )
+        .is_err()
+    );
+    Ok(())
+}
@@ -0,0 +1,140 @@
+mod common;
+use common::*;
+
+/// Returns the lock kinds listed by `locks list`, without their indices and
+/// sorted, since the lock order depends on the random content key.
+fn lock_kinds(envelope: &str) -> Vec<String> {
+    let locks = run_cli(&["locks", "list", envelope]).unwrap();
+    let mut kinds: Vec<String> = locks
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let (line_index, kind) = line.split_once(' ').unwrap();
+            assert_eq!(line_index, index.to_string());
+            kind.to_string()
+        })
+        .collect();
+    kinds.sort();
+    kinds
+}
+
+#[test]
+fn test_locks_add() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--recipient",
+        ALICE_PUBKEYS,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    assert_eq!(lock_kinds(&encrypted), ["recipient X25519"]);
+
+    let added = run_cli(&[
+        "locks",
+        "add",
+        "--recipient",
+        ALICE_PRVKEYS,
+        "--new-recipient",
+        CAROL_PUBKEYS,
+        "--new-password",
+        "password",
+        "--new-password-derivation",
+        "scrypt",
+        &encrypted,
+    ])
+    .unwrap();
+    assert_eq!(
+        lock_kinds(&added),
+        ["recipient X25519", "recipient X25519", "secret Scrypt"]
+    );
+
+    // The encrypted subject is unchanged, and every lock opens it.
+    let subject = run_cli(&["extract", "envelope", &encrypted]).unwrap();
+    assert_eq!(run_cli(&["extract", "envelope", &added]).unwrap(), subject);
+    for credential in [
+        ["--recipient", ALICE_PRVKEYS],
+        ["--recipient", CAROL_PRVKEYS],
+        ["--password", "password"],
+    ] {
+        let decrypted = run_cli_piped(&[
+            &[&["decrypt"][..], &credential, &[&added]].concat(),
+            &["format"],
+        ])
+        .unwrap();
+        assert!(decrypted.starts_with("\"Alice\" ["), "{decrypted}");
+    }
+
+    // Adding needs a credential that unlocks the content key.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args([
+            "locks",
+            "add",
+            "--recipient",
+            CAROL_PRVKEYS,
+            "--new-recipient",
+            BOB_PUBKEYS,
+            &encrypted,
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(6));
+}
+
+#[test]
+fn test_locks_remove() {
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--recipient",
+        CAROL_PUBKEYS,
+        "--password",
+        "password",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+
+    // Remove Carol's lock by her private key.
+    let removed =
+        run_cli(&["locks", "remove", "--recipient", CAROL_PRVKEYS, &encrypted])
+            .unwrap();
+    assert_eq!(
+        lock_kinds(&removed),
+        ["recipient X25519", "secret Argon2id"]
+    );
+    assert!(
+        run_cli(&["decrypt", "--recipient", CAROL_PRVKEYS, &removed]).is_err()
+    );
+    run_cli(&["decrypt", "--recipient", ALICE_PRVKEYS, &removed]).unwrap();
+
+    // Remove the password lock by its index.
+    let locks = run_cli(&["locks", "list", &removed]).unwrap();
+    let (index, _) = locks
+        .lines()
+        .find_map(|line| line.split_once(" secret"))
+        .unwrap();
+    let removed =
+        run_cli(&["locks", "remove", "--index", index, &removed]).unwrap();
+    assert_eq!(lock_kinds(&removed), ["recipient X25519"]);
+
+    // An index past the last lock is not found.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["locks", "remove", "--index", "1", &removed])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(5));
+    assert_actual_expected!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Error: no lock at index 1: the envelope has 1 locks\n"
+    );
+
+    // The last lock can't be removed.
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["locks", "remove", "--index", "0", &removed])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
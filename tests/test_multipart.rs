@@ -0,0 +1,64 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+fn large_envelope() -> Result<String> {
+    run_cli(&["subject", "type", "string", &"Gordian ".repeat(60)])
+}
+
+fn multipart_parts(envelope: &str) -> Result<Vec<String>> {
+    let parts = run_cli(&[
+        "format",
+        "--type",
+        "ur",
+        "--out-format",
+        "multipart",
+        "--max-fragment-len",
+        "100",
+        envelope,
+    ])?;
+    Ok(parts.lines().map(str::to_string).collect())
+}
+
+#[test]
+fn test_multipart_output() -> Result<()> {
+    let envelope = large_envelope()?;
+    let parts = multipart_parts(&envelope)?;
+    assert_eq!(parts.len(), 5);
+    for (i, part) in parts.iter().enumerate() {
+        assert!(part.starts_with(&format!("ur:envelope/{}-5/", i + 1)));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_multipart_input_stdin_any_order() -> Result<()> {
+    let envelope = large_envelope()?;
+    let parts = multipart_parts(&envelope)?;
+    let mut shuffled = parts.clone();
+    shuffled.reverse();
+    shuffled.insert(2, parts[0].clone());
+    shuffled.insert(0, parts[3].clone());
+    let stdin = shuffled.join("\n");
+    run_cli_expect_stdin(&["format", "--type", "ur"], &envelope, &stdin)?;
+    Ok(())
+}
+
+#[test]
+fn test_multipart_input_argument() -> Result<()> {
+    let envelope = large_envelope()?;
+    let parts = multipart_parts(&envelope)?;
+    let argument = parts.join(" ").to_uppercase();
+    run_cli_expect(&["format", "--type", "ur", &argument], &envelope)?;
+    Ok(())
+}
+
+#[test]
+fn test_multipart_input_incomplete() -> Result<()> {
+    let envelope = large_envelope()?;
+    let parts = multipart_parts(&envelope)?;
+    let stdin = parts[..3].join("\n");
+    assert!(run_cli_stdin(&["format"], &stdin).is_err());
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_output_hex() -> Result<()> {
+    run_cli_expect(
+        &["subject", "type", "string", "Hello", "--out-format", "hex"],
+        "d8c8d8c96548656c6c6f",
+    )?;
+    run_cli_expect(
+        &[
+            "--out-format",
+            "hex",
+            "format",
+            "--type",
+            "ur",
+            ALICE_KNOWS_BOB_EXAMPLE,
+        ],
+        "d8c882d8c965416c696365a1d8c9656b6e6f7773d8c963426f62",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_output_bytewords() -> Result<()> {
+    run_cli_expect(
+        &["subject", "type", "string", "Hello", "--out-format", "bytewords"],
+        "tpsptpsoihfdihjzjzjlgavegyce",
+    )
+}
+
+#[test]
+fn test_output_cbor_file() -> Result<()> {
+    let path = temp_path("hello.cbor");
+    let path_str = path.to_str().unwrap();
+    run_cli_raw_expect(
+        &[
+            "subject", "type", "string", "Hello", "--out", path_str,
+            "--out-format", "cbor",
+        ],
+        "",
+    )?;
+    assert_eq!(std::fs::read(&path)?, hex::decode("d8c8d8c96548656c6c6f")?);
+    run_cli_expect(&["format", "--in", path_str], r#""Hello""#)?;
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_output_ur_file() -> Result<()> {
+    let path = temp_path("alice.ur");
+    let path_str = path.to_str().unwrap();
+    run_cli(&[
+        "format",
+        "--type",
+        "ur",
+        "--out",
+        path_str,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    assert_eq!(
+        std::fs::read_to_string(&path)?,
+        format!("{}\n", ALICE_KNOWS_BOB_EXAMPLE)
+    );
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_output_multiple_urs() -> Result<()> {
+    let shares = run_cli(&[
+        "sskr",
+        "split",
+        "--group",
+        "2-of-3",
+        "--out-format",
+        "hex",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    assert_eq!(shares.lines().count(), 3);
+    assert!(shares.lines().all(|line| line.starts_with("d8c8")));
+    assert!(
+        run_cli(&[
+            "sskr",
+            "split",
+            "--group",
+            "2-of-3",
+            "--out-format",
+            "cbor",
+            ALICE_KNOWS_BOB_EXAMPLE,
+        ])
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_output_non_ur_text() -> Result<()> {
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["format", ALICE_KNOWS_BOB_EXAMPLE],
+        indoc!(r#"
+            "Alice" [
+                "knows": "Bob"
+            ]
+        "#)
+    )?;
+    assert!(
+        run_cli(&["format", "--out-format", "hex", ALICE_KNOWS_BOB_EXAMPLE])
+            .is_err()
+    );
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_parse_notation() -> Result<()> {
+    run_cli_expect(
+        &["parse", r#""Alice" [ "knows": "Bob" ]"#],
+        ALICE_KNOWS_BOB_EXAMPLE,
+    )?;
+
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    let notation = indoc! {r#"
+        {
+            "Alice" [
+                "knows": "Bob"
+            ]
+        } [
+            'isA': "Thing"
+            "count": 42
+            "created": 2024-01-02
+            "ratio": -1.5
+            "tags": ["a", "b"]
+            "valid": true
+        ]
+    "#};
+    let envelope = run_cli_stdin(&["parse"], notation)?;
+    run_cli_expect(&["format", &envelope], notation.trim_end())?;
+    Ok(())
+}
+
+#[test]
+fn test_parse_round_trip() -> Result<()> {
+    let envelopes = [
+        ALICE_KNOWS_BOB_EXAMPLE.to_string(),
+        run_cli(&["subject", "type", "unit"])?,
+        run_cli(&["subject", "type", "uri", "https://example.com"])?,
+        run_cli(&["subject", "type", "date", "2024-01-02T03:04:05Z"])?,
+        run_cli(&["subject", "assertion", "string", "a", "string", "b"])?,
+        run_cli_piped(&[
+            &["subject", "assertion", "known", "note", "string", "a\nb"],
+            &["assertion", "add", "pred-obj", "known", "isA", "number", "1"],
+        ])?,
+        run_cli_piped(&[
+            &["subject", "type", "wrapped", ALICE_KNOWS_BOB_EXAMPLE],
+            &["subject", "type", "wrapped"],
+        ])?,
+        run_cli(&["subject", "type", "bignum", "--", "-18446744073709551617"])?,
+        run_cli(&["subject", "type", "cbor", "{1: 2, 3: 10}"])?,
+        run_cli(&["subject", "type", "cbor", r#"{"a": {1: [2]}}"#])?,
+        run_cli(&["subject", "type", "string", r#"say "hi""#])?,
+        run_cli(&[
+            "subject", "assertion", "string", r#"a"b"#, "cbor", "{1: 2}",
+        ])?,
+        run_cli(&["subject", "type", "string", r#"say "hi" now"#])?,
+        run_cli(&["subject", "type", "string", r#"a\"b" [ "c": "d" ]"#])?,
+        run_cli(&["sign", "--signer", ALICE_PRVKEYS, ALICE_KNOWS_BOB_EXAMPLE])?,
+        run_cli_piped(&[
+            &["subject", "type", "arid", ALICE_ARID],
+            &["assertion", "add", "pred-obj", "string", "id", "arid", ARID],
+        ])?,
+        run_cli_piped(&[
+            &["subject", "type", "digest", DIGEST_EXAMPLE],
+            &["assertion", "add", "pred-obj", "string", "data", "data", "00ff"],
+        ])?,
+        run_cli(&["subject", "type", "cbor", "h''"])?,
+        run_cli(&["subject", "assertion", "string", "a", "string", "b"])
+            .and_then(|a| run_cli(&["subject", "type", "wrapped", &a]))?,
+    ];
+    for envelope in envelopes {
+        let notation = run_cli(&["format", "--lossless", &envelope])?;
+        let parsed = run_cli(&["parse", &notation])?;
+        run_cli_expect(&["diff", &envelope, &parsed], "identical")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() -> Result<()> {
+    for (notation, error) in [
+        (
+            "\"Alice\" [\n    \"knows\"\n]",
+            "Error: line 3, column 1: expected ':'\n",
+        ),
+        (
+            "ARID(0102)",
+            "Error: line 1, column 1: ARID(...) needs all 64 hex digits; \
+             use `format --lossless`\n",
+        ),
+        (
+            "\"Alice\" [ ELIDED ]",
+            "Error: line 1, column 11: obscured elements cannot be parsed\n",
+        ),
+        (
+            "Bytes(4)",
+            "Error: line 1, column 1: Bytes(...) only gives the length of a \
+             byte string; use `format --lossless`\n",
+        ),
+    ] {
+        let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+            .args(["parse", notation])
+            .env("RUST_BACKTRACE", "0")
+            .output()?;
+        assert_eq!(output.status.code(), Some(3));
+        assert_actual_expected!(String::from_utf8(output.stderr)?, error);
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_patch_xid_key_update() -> Result<()> {
+    let xid_doc = run_cli_piped(&[
+        &["xid", "new", "--nickname", "Alice", ALICE_PUBKEYS],
+        &["xid", "key", "add", "--nickname", "Bob", BOB_PUBKEYS],
+    ])?;
+    let updated = run_cli(&[
+        "xid",
+        "key",
+        "update",
+        BOB_PUBKEYS,
+        "--allow",
+        "encrypt",
+        "--allow",
+        "sign",
+        &xid_doc,
+    ])?;
+    let patch = run_cli(&["diff", "--patch", &xid_doc, &updated])?;
+
+    let patched = run_cli(&["patch", "apply", &patch, &xid_doc])?;
+    run_cli_expect(&["diff", &updated, &patched], "identical")?;
+
+    // The patch can be applied to the document piped from another command.
+    let patched = run_cli_stdin(&["patch", "apply", &patch], &xid_doc)?;
+    run_cli_expect(&["diff", &updated, &patched], "identical")?;
+    Ok(())
+}
+
+#[test]
+fn test_patch_reveal_and_subject() -> Result<()> {
+    let bob = run_cli(&["subject", "type", "string", "Bob"])?;
+    let bob_digest = run_cli(&["digest", &bob])?;
+    let elided =
+        run_cli(&["elide", "removing", &bob_digest, ALICE_KNOWS_BOB_EXAMPLE])?;
+    let patch = run_cli(&["diff", "--patch", &elided, ALICE_KNOWS_BOB_EXAMPLE])?;
+    run_cli_expect(
+        &["patch", "apply", &patch, &elided],
+        ALICE_KNOWS_BOB_EXAMPLE,
+    )?;
+
+    let carol = run_cli_piped(&[
+        &["subject", "type", "string", "Carol"],
+        &["assertion", "add", "pred-obj", "string", "knows", "string", "Bob"],
+    ])?;
+    let patch = run_cli(&["diff", "--patch", ALICE_KNOWS_BOB_EXAMPLE, &carol])?;
+    run_cli_expect(
+        &["patch", "apply", &patch, ALICE_KNOWS_BOB_EXAMPLE],
+        &carol,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_patch_wrong_envelope() -> Result<()> {
+    let note = run_cli(&[
+        "assertion",
+        "add",
+        "pred-obj",
+        "known",
+        "note",
+        "string",
+        "hi",
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])?;
+    let patch = run_cli(&["diff", "--patch", ALICE_KNOWS_BOB_EXAMPLE, &note])?;
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(["patch", "apply", &patch, HELLO_ENVELOPE_UR])
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    assert_eq!(output.status.code(), Some(7));
+    assert_actual_expected!(
+        String::from_utf8(output.stderr)?,
+        "Error: the patch applies to 8955db5e, not to this envelope \
+         (8cc96cdb)\n"
+    );
+
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(["patch", "apply", HELLO_ENVELOPE_UR, HELLO_ENVELOPE_UR])
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    assert_eq!(output.status.code(), Some(3));
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+const RECIPE: &str = indoc! {r#"
+    [vars]
+    greeting = "Hello."
+
+    [[step]]
+    name = "alice"
+    run = "subject type string Alice"
+    expect_format = '"Alice"'
+
+    [[step]]
+    name = "key"
+    run = "generate key"
+    check = false
+
+    [[step]]
+    name = "encrypted"
+    run = "encrypt --key $key $alice"
+
+    [[step]]
+    run = "decrypt --key $key $encrypted"
+    expect_format = '"Alice"'
+
+    [[step]]
+    run = "subject type string $greeting"
+    expect = "ur:envelope/tpsoiyfdihjzjzjldmksbaoede"
+
+    [[step]]
+    run = "format ur:bad"
+    fails = true
+"#};
+
+fn recipe_dir(name: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("envelope-test-run-{}", name));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn run_cli_failure(args: &[&str]) -> Result<(i32, String)> {
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .args(args)
+        .env("RUST_BACKTRACE", "0")
+        .write_stdin("")
+        .output()?;
+    assert!(!output.status.success());
+    Ok((
+        output.status.code().unwrap_or(-1),
+        String::from_utf8(output.stderr)?,
+    ))
+}
+
+#[test]
+fn test_run_recipe() -> Result<()> {
+    let dir = recipe_dir("recipe")?;
+    let recipe = dir.join("recipe.toml");
+    fs::write(&recipe, RECIPE)?;
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect(
+        &["run", recipe.to_str().unwrap()],
+        indoc! {r#"
+            step 1 (alice): ok
+            step 2 (key): ok
+            step 3 (encrypted): ok
+            step 4: ok
+            step 5: ok
+            step 6: ok
+        "#},
+    )?;
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_run_reports_failed_step() -> Result<()> {
+    let dir = recipe_dir("failed-step")?;
+    let recipe = dir.join("recipe.toml");
+    fs::write(&recipe, RECIPE.replace("string Alice", "string Bob"))?;
+    let (code, stderr) = run_cli_failure(&["run", recipe.to_str().unwrap()])?;
+    assert_eq!(code, 7);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        stderr,
+        indoc! {r#"
+            Error: step 1 (alice): unexpected envelope notation:
+            - "Alice"
+            + "Bob"
+        "#}
+    );
+
+    fs::write(&recipe, "[[step]]\nrun = \"format ur:bad\"\n")?;
+    let (code, stderr) = run_cli_failure(&["run", recipe.to_str().unwrap()])?;
+    assert_eq!(code, 3);
+    assert_actual_expected!(stderr, "Error: step 1: no UR type specified\n");
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_run_record_check() -> Result<()> {
+    let dir = recipe_dir("record-check")?;
+    let recipe = dir.join("recipe.toml");
+    let outputs = dir.join("recipe.out.toml");
+    let recipe = recipe.to_str().unwrap();
+    fs::write(recipe, RECIPE)?;
+
+    let report = run_cli(&["run", "--record", recipe])?;
+    assert!(report.ends_with(&format!(
+        "recorded 6 outputs to {}",
+        outputs.display()
+    )));
+
+    // The key and ciphertext differ from the recorded ones, but the key's
+    // step isn't checked and the ciphertext has the same notation.
+    let report = run_cli(&["run", "--check", recipe])?;
+    assert!(
+        report.ends_with(&format!("all outputs match {}", outputs.display()))
+    );
+
+    fs::write(
+        recipe,
+        RECIPE
+            .replace("string Alice", "string Bob")
+            .replace("expect_format = '\"Alice\"'\n", ""),
+    )?;
+    let (code, stderr) = run_cli_failure(&["run", "--check", recipe])?;
+    assert_eq!(code, 7);
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        stderr,
+        indoc! {r#"
+            Error: step 1 (alice) differs from the recorded output:
+            - "Alice"
+            + "Bob"
+            step 4 differs from the recorded output:
+            - "Alice"
+            + "Bob"
+        "#}
+    );
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_run_rejects_recursive_recipe() -> Result<()> {
+    let dir = recipe_dir("recursive")?;
+    let recipe = dir.join("recipe.toml");
+    fs::write(
+        &recipe,
+        format!("[[step]]\nrun = \"run {}\"\n", recipe.display()),
+    )?;
+    let (code, stderr) = run_cli_failure(&["run", recipe.to_str().unwrap()])?;
+    assert_eq!(code, 2);
+    assert_actual_expected!(
+        stderr,
+        format!("Error: step 1: the recipe {} runs itself\n", recipe.display())
+    );
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
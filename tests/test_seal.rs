@@ -0,0 +1,87 @@
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_seal_unseal() {
+    let sealed = run_cli(&[
+        "seal",
+        "--signer",
+        ALICE_PRVKEYS,
+        "--recipient",
+        ALICE_PUBKEYS,
+        "--recipient",
+        CAROL_PUBKEYS,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    // The assertions and the signature are encrypted with the subject.
+    #[rustfmt::skip]
+    // expected-text-output-rubric:
+    run_cli_expect(
+        &["format", &sealed],
+        indoc!(r#"
+            ENCRYPTED [
+                'hasRecipient': SealedMessage
+                'hasRecipient': SealedMessage
+            ]
+        "#),
+    ).unwrap();
+
+    for recipient in [ALICE_PRVKEYS, CAROL_PRVKEYS] {
+        let unsealed = run_cli(&[
+            "unseal",
+            "--recipient",
+            recipient,
+            "--verifier",
+            ALICE_PUBKEYS,
+            &sealed,
+        ])
+        .unwrap();
+        assert_eq!(unsealed, ALICE_KNOWS_BOB_EXAMPLE);
+    }
+}
+
+#[test]
+fn test_unseal_errors() {
+    let sealed = run_cli(&[
+        "seal",
+        "--signer",
+        ALICE_PRVKEYS,
+        "--recipient",
+        CAROL_PUBKEYS,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    let encrypted = run_cli(&[
+        "encrypt",
+        "--recipient",
+        CAROL_PUBKEYS,
+        ALICE_KNOWS_BOB_EXAMPLE,
+    ])
+    .unwrap();
+    for (recipient, verifier, envelope, code) in [
+        // Signed by someone else.
+        (CAROL_PRVKEYS, CAROL_PUBKEYS, &sealed, 7),
+        // Not a recipient.
+        (ALICE_PRVKEYS, ALICE_PUBKEYS, &sealed, 6),
+        // Encrypted, but not sealed.
+        (CAROL_PRVKEYS, ALICE_PUBKEYS, &encrypted, 3),
+    ] {
+        let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+            .env("RUST_BACKTRACE", "0")
+            .args([
+                "unseal",
+                "--recipient",
+                recipient,
+                "--verifier",
+                verifier,
+                envelope,
+            ])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(code));
+        assert!(output.stdout.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use indoc::{formatdoc, indoc};
+
+mod common;
+use common::*;
+
+#[test]
+fn test_shell_variables() -> Result<()> {
+    let script = indoc! {r#"
+        # Lines starting with `#` are ignored.
+        let alice = subject type string Alice
+        let bob = subject type string Bob
+        assertion add pred-obj string knows envelope $bob $alice
+        digest
+    "#};
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect_stdin(
+        &["shell"],
+        indoc! {r#"
+            "Alice"
+            "Bob"
+            ur:envelope/lftpsoihfpjziniaihoytpsoihjejtjlktjktpsoiafwjlidutgmnnns
+            "Alice" [
+                "knows": "Bob"
+            ]
+            ur:digest/hdcxldgouyhyadimzmpaeourhfsectvaskspdlotaxidiatbgydejnbwgskbhfrtwlwzneroatds
+        "#},
+        script,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_shell_errors_continue() -> Result<()> {
+    let script = indoc! {r#"
+        format $nobody
+        bogus
+        subject type string Hello.
+    "#};
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .arg("shell")
+        .env("RUST_BACKTRACE", "0")
+        .write_stdin(script)
+        .output()?;
+    assert!(output.status.success());
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        String::from_utf8(output.stdout)?,
+        formatdoc! {r#"
+            {HELLO_ENVELOPE_UR}
+            "Hello."
+        "#}
+    );
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    assert_actual_expected!(
+        String::from_utf8(output.stderr)?,
+        indoc! {r#"
+            Error: unknown variable $nobody
+            Error: unrecognized subcommand 'bogus'
+        "#}
+    );
+    Ok(())
+}
+
+#[test]
+fn test_shell_argument_reads_last_envelope() -> Result<()> {
+    // `info` takes an argument rather than an envelope. Without one it reads
+    // the last envelope, not the rest of the script.
+    let script = indoc! {r#"
+        info
+        subject type string Alice
+        info
+    "#};
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .arg("shell")
+        .env("RUST_BACKTRACE", "0")
+        .write_stdin(script)
+        .output()?;
+    assert!(output.status.success());
+    assert_actual_expected!(
+        String::from_utf8(output.stdout)?,
+        indoc! {r#"
+            ur:envelope/tpsoihfpjziniaihmebdmodl
+            "Alice"
+            Format: ur:envelope
+            CBOR Size: 8
+            Description: Gordian Envelope
+        "#}
+    );
+    assert_actual_expected!(
+        String::from_utf8(output.stderr)?,
+        "Error: No input provided\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_shell_save_load() -> Result<()> {
+    let dir = std::env::temp_dir().join("envelope-test-shell-save-load");
+    std::fs::create_dir_all(&dir)?;
+    let session = dir.join("session.json");
+    let session = session.to_str().unwrap();
+
+    let script = formatdoc! {r#"
+        let hello = subject type string Hello.
+        :save {session}
+        :quit
+        format $hello
+    "#};
+    run_cli_expect_stdin(&["shell"], r#""Hello.""#, &script)?;
+
+    let script = formatdoc! {r#"
+        :load {session}
+        :vars
+        :history
+    "#};
+    // expected-text-output-rubric:
+    #[rustfmt::skip]
+    run_cli_expect_stdin(
+        &["shell"],
+        &formatdoc! {r#"
+            $hello = {HELLO_ENVELOPE_UR}
+               1  let hello = subject type string Hello.
+               2  :save {session}
+               3  :load {session}
+               4  :vars
+               5  :history
+        "#},
+        &script,
+    )?;
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
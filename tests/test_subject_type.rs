@@ -170,6 +170,47 @@ fn test_cbor_subject() -> Result<()> {
     run_cli_expect(&["subject", "type", "cbor", &cbor_array_example], &e)
 }
 
+#[test]
+fn test_cbor_diagnostic_subject() -> Result<()> {
+    let e = run_cli(&["subject", "type", "cbor", "[1, 2, 3]"])?;
+    assert_eq!(e, "ur:envelope/tpsolsadaoaxzerkykme");
+
+    let e = run_cli(&[
+        "subject",
+        "type",
+        "cbor",
+        "{2: [h'0102', 1(1700000000)], 1: \"a\"}",
+    ])?;
+    run_cli_expect(
+        &["format", &e],
+        r#"{1: "a", 2: [h'0102', 2023-11-14T22:13:20Z]}"#,
+    )?;
+
+    // Floats are reduced to their shortest form.
+    let e = run_cli(&["subject", "type", "cbor", "[2.0, 1.5]"])?;
+    run_cli_expect(&["extract", "cbor", &e], "8202f93e00")?;
+
+    let e = run_cli_piped(&[
+        &["subject", "type", "string", "Alice"],
+        &["assertion", "add", "pred-obj", "string", "list", "cbor", "[1, 2]"],
+    ])?;
+    run_cli_expect(&["format", &e], "\"Alice\" [\n    \"list\": [1, 2]\n]")?;
+    Ok(())
+}
+
+#[test]
+fn test_cbor_subject_not_deterministic() -> Result<()> {
+    // Duplicate map keys.
+    assert!(run_cli(&["subject", "type", "cbor", "{1: 2, 1: 3}"]).is_err());
+    assert!(run_cli(&["subject", "type", "cbor", "a201020103"]).is_err());
+    // A float that could be encoded in half precision.
+    assert!(
+        run_cli(&["subject", "type", "cbor", "fb3ff8000000000000"]).is_err()
+    );
+    assert!(run_cli(&["subject", "type", "cbor", "[1, 2,]"]).is_err());
+    Ok(())
+}
+
 #[test]
 fn test_arid_subject() -> Result<()> {
     let e = run_cli(&["subject", "type", "arid", ARID_HEX])?;
@@ -294,6 +335,94 @@ fn test_negative_int_subject() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_large_int_subject() -> Result<()> {
+    // 2^63 + 1 can't be represented exactly as a float.
+    let value = "9223372036854775809";
+    let e = run_cli(&["subject", "type", "int", value])?;
+    run_cli_expect(&["format", &e], value)?;
+    run_cli_expect(&["extract", "int", &e], value)?;
+    run_cli_expect(&["extract", "number", &e], value)?;
+    run_cli_expect(&["extract", "cbor", &e], "1b8000000000000001")?;
+    run_cli_expect(&["subject", "type", "number", value], &e)?;
+
+    let value = "-18446744073709551616";
+    let e = run_cli(&["subject", "type", "int", "--", value])?;
+    run_cli_expect(&["extract", "int", &e], value)?;
+    run_cli_expect(&["extract", "cbor", &e], "3bffffffffffffffff")?;
+
+    assert!(
+        run_cli(&["subject", "type", "int", "18446744073709551616"]).is_err()
+    );
+    assert!(run_cli(&["subject", "type", "int", "1.5"]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_uint_subject() -> Result<()> {
+    let value = "18446744073709551615";
+    let e = run_cli(&["subject", "type", "uint", value])?;
+    run_cli_expect(&["extract", "uint", &e], value)?;
+    run_cli_expect(&["extract", "cbor", &e], "1bffffffffffffffff")?;
+
+    assert!(run_cli(&["subject", "type", "uint", "--", "-1"]).is_err());
+    let e = run_cli(&["subject", "type", "int", "--", "-1"])?;
+    assert!(run_cli(&["extract", "uint", &e]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_bignum_subject() -> Result<()> {
+    // 2^128 + 1
+    let value = "340282366920938463463374607431768211457";
+    let e = run_cli(&["subject", "type", "bignum", value])?;
+    run_cli_expect(
+        &["format", &e],
+        "bignum(340282366920938463463374607431768211457)",
+    )?;
+    run_cli_expect(
+        &["extract", "cbor", &e],
+        "c2510100000000000000000000000000000001",
+    )?;
+    run_cli_expect(&["extract", "bignum", &e], value)?;
+
+    let e = run_cli(&["subject", "type", "bignum", "--", "-256"])?;
+    run_cli_expect(&["extract", "cbor", &e], "c341ff")?;
+    run_cli_expect(&["extract", "bignum", &e], "-256")?;
+
+    let e = run_cli(&["subject", "type", "bignum", "0"])?;
+    run_cli_expect(&["extract", "cbor", &e], "c240")?;
+    run_cli_expect(&["extract", "bignum", &e], "0")?;
+
+    // CBOR integers are also read as bignums.
+    let e = run_cli(&["subject", "type", "int", "42"])?;
+    run_cli_expect(&["extract", "bignum", &e], "42")?;
+
+    // A magnitude with a leading zero is not deterministic.
+    let e = run_cli(&["subject", "type", "cbor", "c2420001"])?;
+    assert!(run_cli(&["extract", "bignum", &e]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_data_file_subject() -> Result<()> {
+    let path = std::env::temp_dir()
+        .join(format!("envelope-data-{}.bin", std::process::id()));
+    std::fs::write(&path, [0xca, 0xfe, 0xba, 0xbe])?;
+    let e = run_cli(&[
+        "subject",
+        "type",
+        "data",
+        &format!("@{}", path.display()),
+    ])?;
+    std::fs::remove_file(&path)?;
+    run_cli_expect(&["subject", "type", "data", "cafebabe"], &e)?;
+    run_cli_expect(&["extract", "data", &e], "cafebabe")?;
+
+    assert!(run_cli(&["subject", "type", "data", "@/nonexistent"]).is_err());
+    Ok(())
+}
+
 #[test]
 fn test_known_value_subject() -> Result<()> {
     let value = "note";
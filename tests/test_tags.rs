@@ -0,0 +1,115 @@
+use anyhow::Result;
+use indoc::indoc;
+
+mod common;
+use common::*;
+
+const TAGS: &str = indoc! {r#"
+    [[tags]]
+    value = 40500
+    name = "my-thing"
+    description = "Widget Record"
+    summary = "Widget({})"
+
+    [[tags]]
+    value = 40501
+    name = "other-thing"
+"#};
+
+const MY_THING_UR: &str = "ur:my-thing/oeadiyktinieioihjyaocsdrdwpkgwmh";
+const MY_THING_ENVELOPE: &str =
+    "ur:envelope/tpsotanneeoeadiyktinieioihjyaocsdrksbzchfn";
+
+/// Writes `contents` to a file in the temporary directory, returning its
+/// path.
+fn write_temp(name: &str, contents: &str) -> Result<String> {
+    let path = std::env::temp_dir()
+        .join(format!("envelope-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents)?;
+    Ok(path.display().to_string())
+}
+
+#[test]
+fn test_tags_file() -> Result<()> {
+    let tags = write_temp("tags.toml", TAGS)?;
+    let options = ["--tags", &tags];
+
+    // Without the tags, the UR type is unknown.
+    assert!(run_cli(&["subject", "type", "ur", MY_THING_UR]).is_err());
+    assert!(run_cli(&["info", MY_THING_UR]).is_err());
+    assert!(run_cli(&["extract", "ur", MY_THING_ENVELOPE]).is_err());
+
+    let e = run_cli(
+        &[&["subject", "type", "ur", MY_THING_UR][..], &options].concat(),
+    )?;
+    assert_eq!(e, MY_THING_ENVELOPE);
+    let ur = run_cli(
+        &[&["extract", "ur", MY_THING_ENVELOPE][..], &options].concat(),
+    )?;
+    assert_eq!(ur, MY_THING_UR);
+
+    let summary =
+        run_cli(&[&["format", MY_THING_ENVELOPE][..], &options].concat())?;
+    assert_eq!(summary, r#"Widget({1: "widget", 2: 42})"#);
+    let other = run_cli_piped(&[
+        &[&["subject", "type", "cbor", "40501(1)"][..], &options].concat(),
+        &[&["format"][..], &options].concat(),
+    ])?;
+    assert_eq!(other, "40501(1)");
+
+    let info = run_cli(&[&["info", MY_THING_UR][..], &options].concat())?;
+    #[rustfmt::skip]
+    // expected-text-output-rubric:
+    assert_actual_expected!(info, indoc! {r#"
+        Format: ur:my-thing
+        CBOR Size: 12
+        Description: Widget Record
+        CBOR Tag: 40500
+    "#}.trim());
+    std::fs::remove_file(&tags)?;
+    Ok(())
+}
+
+#[test]
+fn test_tags_user_file() -> Result<()> {
+    let home = std::env::temp_dir()
+        .join(format!("envelope-{}-tags-home", std::process::id()));
+    std::fs::create_dir_all(home.join(".envelope"))?;
+    std::fs::write(home.join(".envelope/tags.toml"), TAGS)?;
+
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("HOME", &home)
+        .args(["subject", "type", "ur", MY_THING_UR])
+        .output()?;
+    std::fs::remove_dir_all(&home)?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?.trim(), MY_THING_ENVELOPE);
+    Ok(())
+}
+
+#[test]
+fn test_tags_file_errors() -> Result<()> {
+    // Tags may not redefine the standard ones.
+    let conflict = write_temp(
+        "conflict-tags.json",
+        r#"{ "tags": [ { "value": 200, "name": "mine" } ] }"#,
+    )?;
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["--tags", &conflict, "format", HELLO_ENVELOPE_UR])
+        .output()?;
+    std::fs::remove_file(&conflict)?;
+    assert_eq!(output.status.code(), Some(2));
+
+    let bad_name = write_temp(
+        "bad-name-tags.json",
+        r#"{ "tags": [ { "value": 40502, "name": "My_Thing" } ] }"#,
+    )?;
+    let output = assert_cmd::cargo::cargo_bin_cmd!("envelope")
+        .env("RUST_BACKTRACE", "0")
+        .args(["--tags", &bad_name, "format", HELLO_ENVELOPE_UR])
+        .output()?;
+    std::fs::remove_file(&bad_name)?;
+    assert_eq!(output.status.code(), Some(3));
+    Ok(())
+}
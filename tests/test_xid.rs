@@ -520,14 +520,13 @@ fn test_xid_key_find() {
 
     // $ envelope xid key find name 'Wolf' $XID_DOC_UPDATED
     //
-    // (nothing returned)
+    // Error: No key with that name
     // ```
 
-    run_cli_expect(
-        &["xid", "key", "find", "name", "Wolf", XID_DOC_UPDATED],
-        "",
-    )
-    .unwrap();
+    assert!(
+        run_cli(&["xid", "key", "find", "name", "Wolf", XID_DOC_UPDATED])
+            .is_err()
+    );
 
     // ##### `xid key find inception`: Find the Document's Inception Key
     //
@@ -596,11 +595,13 @@ fn test_xid_key_remove() {
     //
     // $ envelope xid key find inception $XID_DOC_REMOVED
     //
-    // (nothing returned)
+    // Error: No inception key
     // ```
 
-    run_cli_expect(&["xid", "key", "find", "inception", &xid_doc_removed], "")
-        .unwrap();
+    assert!(
+        run_cli(&["xid", "key", "find", "inception", &xid_doc_removed])
+            .is_err()
+    );
 }
 
 #[test]